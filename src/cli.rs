@@ -9,6 +9,10 @@ pub struct Cli {
   pub mode: Mode,
   #[arg(short, long, conflicts_with("interactive"))]
   pub output: Option<PathBuf>,
+  /// The directory modules are looked up in, instead of the directory of the
+  /// file being run.
+  #[arg(short, long)]
+  pub root: Option<PathBuf>,
 }
 
 #[derive(Debug, Args)]
@@ -1,85 +1,286 @@
+use std::{
+  collections::HashSet,
+  fs::File,
+  io,
+  path::{Path, PathBuf},
+};
+
 use foxy_utils::types::handle::Handle;
 
 use self::{
-  error::{error_handler::ErrorHandler, KonError},
-  grammar::binding::binder::Binder,
+  error::{error_handler::ErrorHandler, InterpreterError, KonError},
+  grammar::{
+    binding::{binder::Binder, environment::Environment},
+    item::ItemKind,
+    span::Span,
+    syntax_tree::SyntaxTree,
+    token::{LiteralToken, Token},
+  },
   lexer::Lexer,
   parser::Parser,
+  util::source_map::SourceMap,
 };
 
 pub mod error;
-mod grammar;
-mod lexer;
+pub mod grammar;
+pub mod lexer;
 mod parser;
-mod util;
+pub mod util;
 
 pub struct Interpreter {
   error_handler: Handle<ErrorHandler>,
+  source_map: SourceMap,
 
-  lexer: Lexer,
   parser: Parser,
   binder: Binder,
+  /// Top-level variables, kept from one run to the next.
+  environment: Environment,
+  /// The directory modules are looked up in, as `a/b.kon` for `a.b`. When
+  /// unset, it is the directory of the file being run.
+  search_root: Option<PathBuf>,
 
   show_tokens: bool,
   show_tree: bool,
 }
 
-impl Default for Interpreter {
-  fn default() -> Self {
-    Self::new()
+impl Interpreter {
+  /// How much of [`STACK_SIZE`](Self::STACK_SIZE) is kept for what runs
+  /// between one call and the next, and for reporting errors.
+  const STACK_RESERVE: usize = 4 * 1024 * 1024;
+  /// How much stack the thread running programs needs for
+  /// [`Environment::MAX_CALL_DEPTH`] nested calls, which take far more of it in
+  /// debug builds.
+  pub const STACK_SIZE: usize = Environment::MAX_CALL_DEPTH * if cfg!(debug_assertions) { 128 } else { 16 } * 1024;
+
+  /// Gives `run` a new interpreter on a thread with
+  /// [`STACK_SIZE`](Self::STACK_SIZE) of stack, as deeply nested calls would
+  /// overflow the stack of the main thread before the interpreter could
+  /// report them. This is the only way to get an interpreter, as one on any
+  /// other thread wouldn't know how much stack it can take.
+  pub fn on_large_stack<T: Send>(run: impl FnOnce(Interpreter) -> T + Send) -> io::Result<T> {
+    std::thread::scope(|scope| {
+      let thread = std::thread::Builder::new()
+        .name("interpreter".into())
+        .stack_size(Self::STACK_SIZE)
+        .spawn_scoped(scope, || {
+          let mut interpreter = Interpreter::new();
+          (interpreter.environment).limit_stack(Self::STACK_SIZE - Self::STACK_RESERVE);
+          run(interpreter)
+        })?;
+      Ok(thread.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+    })
   }
-}
 
-impl Interpreter {
-  pub fn new() -> Interpreter {
+  fn new() -> Interpreter {
     let error_handler = Handle::new(ErrorHandler::new());
-    let lexer = Lexer::new(error_handler.clone());
     let parser = Parser::new(error_handler.clone());
     let binder = Binder::new(error_handler.clone());
 
     Self {
       error_handler,
-      lexer,
+      source_map: SourceMap::new(),
       parser,
       binder,
+      environment: Environment::new(),
+      search_root: None,
       show_tokens: false,
       show_tree: false,
     }
   }
 
   pub fn run(&mut self, source: String) -> Result<String, KonError> {
+    self.run_named("<input>", source)
+  }
+
+  /// Runs `source`, naming it `name` in any errors that get reported.
+  pub fn run_named(&mut self, name: impl Into<String>, source: String) -> Result<String, KonError> {
+    self.error_handler.get_mut().clear();
+    let file_id = self.source_map.add_file(name, source);
+    let tokens = Lexer::new(self.source_map.source(file_id).as_bytes(), file_id, self.error_handler.clone());
+    let tree = self
+      .parser
+      .parse(Self::inspect_tokens(tokens, std::mem::take(&mut self.show_tokens)));
+    let root = self.search_root.clone().unwrap_or_else(|| PathBuf::from("."));
+    self.evaluate(tree, &root, None)
+  }
+
+  /// Runs the file at `path`, streaming it through the lexer rather than
+  /// reading it into memory first. The text is only loaded again if an error
+  /// needs to be shown.
+  pub fn run_file(&mut self, path: impl AsRef<Path>) -> Result<String, KonError> {
     self.error_handler.get_mut().clear();
-    let tokens = self.lexer.lex(&source);
+    let path = path.as_ref();
+    let tree = self.parse_file(path)?;
+    let root = (self.search_root.clone())
+      .or_else(|| path.parent().map(Path::to_path_buf))
+      .unwrap_or_default();
+    // the file is a module too, which the modules it imports can't import back
+    let module = path.strip_prefix(&root).unwrap_or(path).with_extension("");
+    let module: Vec<_> = module.iter().map(|segment| segment.to_string_lossy()).collect();
+    self.evaluate(tree, &root, Some(module.join(".")))
+  }
+
+  /// Looks modules up in `root` rather than in the directory of the file
+  /// being run.
+  pub fn set_search_root(&mut self, root: impl Into<PathBuf>) {
+    self.search_root = Some(root.into());
+  }
 
-    if self.show_tokens {
-      println!("{tokens:#?}");
-      self.show_tokens = false;
+  fn parse_file(&mut self, path: &Path) -> Result<SyntaxTree, KonError> {
+    let file = File::open(path)?;
+    let file_id = self.source_map.add_path(path);
+    let tokens = Lexer::from_reader(file, file_id, self.error_handler.clone());
+    Ok(
+      self
+        .parser
+        .parse(Self::inspect_tokens(tokens, std::mem::take(&mut self.show_tokens))),
+    )
+  }
+
+  /// Parses the modules `tree` imports that aren't loaded yet, along with the
+  /// ones they import, each after the modules it imports. `loading` holds the
+  /// modules whose imports are being loaded, which can't be imported again.
+  fn load_imports(
+    &mut self,
+    tree: &SyntaxTree,
+    root: &Path,
+    loading: &mut Vec<String>,
+    modules: &mut Vec<(String, SyntaxTree)>,
+  ) -> Result<(), KonError> {
+    let mut unresolved = HashSet::new();
+    for (segments, span) in Self::imported_paths(tree) {
+      // the module is the longest part of the path that names one, and the
+      // rest names what is in it
+      let candidates: Vec<_> = (1..=segments.len()).rev().map(|split| &segments[..split]).collect();
+      let found = candidates.iter().find(|candidate| {
+        let module = candidate.join(".");
+        self.binder.has_module(&module)
+          || loading.contains(&module)
+          || modules.iter().any(|(loaded, _)| *loaded == module)
+          || Self::module_file(root, candidate).is_file()
+      });
+      let Some(found) = found else {
+        if unresolved.insert(segments[0].clone()) {
+          let tried: Vec<_> = (candidates.iter())
+            .map(|candidate| format!("`{}`", Self::module_file(root, candidate).display()))
+            .collect();
+          self.error_handler.get_mut().push(InterpreterError::UnresolvedImport {
+            span,
+            module: segments.join("."),
+            tried: tried.join(", "),
+          });
+        }
+        continue;
+      };
+
+      let module = found.join(".");
+      if let Some(start) = loading.iter().position(|loaded| *loaded == module) {
+        let cycle = [&loading[start..], &[module]].concat();
+        self.error_handler.get_mut().push(InterpreterError::ImportCycle {
+          span,
+          cycle: cycle.join(" -> "),
+        });
+        continue;
+      }
+      if self.binder.has_module(&module) || modules.iter().any(|(loaded, _)| *loaded == module) {
+        continue;
+      }
+
+      let imported = self.parse_file(&Self::module_file(root, found))?;
+      loading.push(module.clone());
+      self.load_imports(&imported, root, loading, modules)?;
+      loading.pop();
+      modules.push((module, imported));
     }
+    Ok(())
+  }
 
-    let tree = self.parser.parse(&tokens);
+  /// The paths `tree` imports or includes, split into their names.
+  fn imported_paths(tree: &SyntaxTree) -> Vec<(Vec<String>, Span)> {
+    let lexemes = |segments: &[LiteralToken]| segments.iter().map(|segment| segment.literal.lexeme()).collect();
+    (tree.items.iter())
+      .flat_map(|item| match &item.kind {
+        ItemKind::Import(import) => (import.tree.paths().into_iter())
+          .map(|path| (lexemes(&path.segments), path.span))
+          .collect(),
+        ItemKind::Include(include) => vec![(lexemes(&include.path), include.span)],
+        _ => Vec::new(),
+      })
+      .collect()
+  }
+
+  /// The file the module named by `segments` is in.
+  fn module_file(root: &Path, segments: &[String]) -> PathBuf {
+    let mut file: PathBuf = root
+      .iter()
+      .chain(segments.iter().map(|segment| segment.as_ref()))
+      .collect();
+    file.set_extension("kon");
+    file
+  }
+
+  /// Prints each token as the parser pulls it in, when `show_tokens` is set.
+  fn inspect_tokens<'a>(
+    tokens: impl Iterator<Item = Token> + 'a,
+    show_tokens: bool,
+  ) -> impl Iterator<Item = Token> + 'a {
+    tokens.inspect(move |token| {
+      if show_tokens {
+        println!("{token:#?}");
+      }
+    })
+  }
 
+  /// Runs `tree` once the modules it imports are loaded from `root`. `module`
+  /// is the module the tree is, if it is a file in `root`.
+  fn evaluate(&mut self, tree: SyntaxTree, root: &Path, module: Option<String>) -> Result<String, KonError> {
     if self.show_tree {
       print!("{}", tree); // tree has trailing newline due to recursive impl
       self.show_tree = false;
     }
 
-    let bound_tree = self.binder.bind(tree.root);
+    // type errors in a tree patched up after syntax errors would only be noise
+    self.error_handler.get().try_report_errors(&self.source_map)?;
 
-    self.error_handler.get().try_report_errors()?;
+    let mut modules = Vec::new();
+    self.load_imports(&tree, root, &mut module.into_iter().collect(), &mut modules)?;
+    self.error_handler.get().try_report_errors(&self.source_map)?;
 
-    // print!("Result: ");
-
-    if let Ok(result) = bound_tree.evaluate() {
-      if let Some(&value) = result.downcast_ref::<i64>() {
-        return Ok(value.to_string());
+    let paths: Vec<_> = modules.iter().map(|(path, _)| path.clone()).collect();
+    let bound_modules: Vec<_> = (modules.into_iter())
+      .map(|(path, tree)| self.binder.bind_module(&path, tree))
+      .collect();
+    if let Err(error) = self.error_handler.get().try_report_errors(&self.source_map) {
+      for path in &paths {
+        self.binder.forget_module(path);
       }
+      return Err(error);
+    }
+    for module in &bound_modules {
+      module.define(&mut self.environment);
+    }
+
+    let checkpoint = self.binder.checkpoint();
+    let bound_tree = self.binder.bind(tree);
+    if let Err(error) = self.error_handler.get().try_report_errors(&self.source_map) {
+      self.binder.restore(checkpoint);
+      return Err(error);
+    }
+
+    // print!("Result: ");
 
-      if let Some(value) = result.downcast_ref::<String>() {
-        return Ok(value.clone());
+    // what a line that fails declared may never have been given a value
+    match bound_tree.evaluate(&mut self.environment) {
+      Ok(result) => Ok(result.to_string()),
+      Err(error) => {
+        self.binder.restore(checkpoint);
+        Err(KonError::Evaluation(error.report_string(&self.source_map)))
       }
     }
+  }
 
-    Err(KonError::Evaluation("invalid types".into()))
+  pub fn source_map(&self) -> &SourceMap {
+    &self.source_map
   }
 
   pub fn show_tree(&mut self) {
@@ -90,3 +291,468 @@ impl Interpreter {
     self.show_tokens = true;
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Runs `source` as the command line does, on a thread with enough stack.
+  fn run(source: &str) -> Result<String, KonError> {
+    Interpreter::on_large_stack(|mut interpreter| interpreter.run(source.into())).expect("the thread starts")
+  }
+
+  fn evaluates(source: &str, expected: &str) {
+    match run(source) {
+      Ok(result) => assert_eq!(result, expected, "`{source}`"),
+      Err(error) => panic!("`{source}` failed: {error:?}"),
+    }
+  }
+
+  /// Checks that `source` fails with an error whose message contains
+  /// `message`.
+  fn fails_with(source: &str, message: &str) {
+    failed_with(source, run(source), message);
+  }
+
+  /// Writes `files`, pairs of a path and its source, to a directory of their
+  /// own named after `name`, and runs the first of them.
+  fn run_files(name: &str, files: &[(&str, &str)]) -> Result<String, KonError> {
+    let root = std::env::temp_dir().join(format!("kon-{name}-{}", std::process::id()));
+    for (path, source) in files {
+      let path = root.join(path);
+      std::fs::create_dir_all(path.parent().expect("files are in the directory")).expect("the directory is created");
+      std::fs::write(path, source).expect("the file is written");
+    }
+    let main = root.join(files[0].0);
+    let result = Interpreter::on_large_stack(|mut interpreter| interpreter.run_file(main)).expect("the thread starts");
+    std::fs::remove_dir_all(root).expect("the directory is removed");
+    result
+  }
+
+  /// Checks that running `source` gave `result`, an error whose message
+  /// contains `message`.
+  fn failed_with(source: &str, result: Result<String, KonError>, message: &str) {
+    let messages = match result {
+      Ok(result) => panic!("`{source}` gave `{result}` instead of failing"),
+      Err(KonError::InterpreterErrors(errors)) => errors.iter().map(ToString::to_string).collect(),
+      Err(error) => vec![error.to_string()],
+    };
+    assert!(
+      messages.iter().any(|found| found.contains(message)),
+      "`{source}` failed with {messages:?} instead of `{message}`"
+    );
+  }
+
+  #[test]
+  fn deep_recursion_runs() {
+    evaluates("f: (n: i64) -> i64 = { if n == 0 { return 0; } f(n - 1) + 1 } f(4000)", "4000");
+  }
+
+  #[test]
+  fn infinite_recursion_is_an_error() {
+    fails_with("f: (n: i64) -> i64 = { f(n) } f(1)", "stack overflow while calling `f`");
+    fails_with(
+      "C: type = { n: i64; } C: impl = { go: (&self) -> i64 { loop { if true { return { { self.go() + 1 } }; } } } } \
+       c: C = C { n = 0 }; c.go()",
+      "stack overflow while calling `go`",
+    );
+  }
+
+  #[test]
+  fn if_without_else_has_no_value() {
+    evaluates("x := if true { 1 }; x", "()");
+    evaluates("f: () = { if true { 5 } } f()", "()");
+    fails_with(
+      "x := if false { 1 } else if true { 2 }; x",
+      "`if` and `else` have different types",
+    );
+    evaluates("if 1 < 2 { \"a\" } else { \"b\" }", "a");
+  }
+
+  #[test]
+  fn comparisons_in_arguments_are_not_generic_arguments() {
+    evaluates(
+      "f: (a: bool, b: bool) -> bool { a && b } x := 1; y := 2; z := 0; f(x < y, y > (z))",
+      "true",
+    );
+    evaluates("first<T>: (value: T) -> T { value } first::<string>(\"a\")", "a");
+    evaluates(
+      "Pair<T, U>: type = { first: T; second: U; } p := Pair::<i64, string> { first = 1, second = \"b\" }; p.second",
+      "b",
+    );
+    let boxed = "Box<T>: type = { value: T; } Box<T>: impl = { new: (value: T) -> Self { Box { value } } }";
+    evaluates(&format!("{boxed} Box<i64>.new(5).value"), "5");
+    evaluates(&format!("{boxed} b := Box<string> {{ value = \"c\" }}; b.value"), "c");
+    evaluates("first<T>: (value: T) -> T { value } first<i64>(3) < 4", "true");
+  }
+
+  #[test]
+  fn const_parameters_are_values() {
+    let grid = "Grid<T, N: const i64>: type = { first: T; } \
+                Grid<T, N>: impl = { \
+                  new: (first: T) -> Self { Grid::<T, N> { first = first } } \
+                  size: (&self) -> i64 { N * 2 } \
+                } \
+                length<N: const i64>: () -> i64 { N } \
+                outer<M: const i64>: () -> i64 { length::<M>() + M }";
+    evaluates(&format!("{grid} length::<4>()"), "4");
+    evaluates(&format!("{grid} outer::<5>()"), "10");
+    evaluates(&format!("{grid} g := Grid::<string, 3>.new(\"a\"); g.size()"), "6");
+    fails_with(
+      &format!("{grid} f<N: const i64>: () -> i64 {{ N = 3; N }}"),
+      "cannot change the const parameter `N`",
+    );
+    fails_with(
+      "Grid<T, N: const i64>: type = { first: T; } Show: trait = { show: (&self) -> i64; } \
+       Grid<T, N>: impl Show = { show: (&self) -> i64 { N } }",
+      "const parameter `N` cannot be read in a method implementing a trait",
+    );
+  }
+
+  #[test]
+  fn logical_operators_short_circuit() {
+    let fails = "fails: () -> bool = { zero: i64 = 0; 1 / zero == 1 }";
+    evaluates(&format!("{fails} false && fails()"), "false");
+    evaluates(&format!("{fails} true || fails()"), "true");
+    fails_with(&format!("{fails} true && fails()"), "attempted to divide by zero");
+    evaluates("1 < 2 && 2 < 3 || false", "true");
+    fails_with("1 && true", "cannot apply `&&` to `{integer}` and `bool`");
+  }
+
+  #[test]
+  fn exponents_group_to_the_right() {
+    evaluates("2 ^ 3 ^ 2 == 512", "true");
+    evaluates("2 * 3 ^ 2", "18");
+    evaluates("2.0 ^ 0.5 > 1.41", "true");
+    evaluates("-2 ^ 2", "-4");
+    evaluates("(-2) ^ 2", "4");
+    evaluates("2.0 ^ -1.0", "0.5");
+    fails_with("n: i64 = -1; 2 ^ n", "cannot raise an integer to the negative power `-1`");
+    fails_with("x: i32 = 2; x ^ 40", "integer overflow");
+  }
+
+  #[test]
+  fn compound_assignments_change_their_target() {
+    evaluates("x := 1; x += 2; x *= 5; x -= 1; x /= 2; x", "7");
+    evaluates("b := true; b &= false; b", "false");
+    evaluates("y := 2; y ^= 3; y", "8");
+    evaluates("s := \"a\"; s += \"b\"; s", "ab");
+    evaluates("f := 1.5; f *= 2.0; f", "3.0");
+    evaluates("P: type = { a: i64; } p := P { a = 1 }; p.a += 4; p.a", "5");
+    evaluates("a := (); b := 1; a = b = 5; b", "5");
+    fails_with("34 += 110 * 69", "cannot assign to this expression");
+    fails_with("x := 1; x += \"a\"", "cannot apply `+=` to `{integer}` and `string`");
+  }
+
+  #[test]
+  fn declarations_name_values() {
+    evaluates("x := 10; y: i32 = 5; z: i64; z = x + 1; z", "11");
+    fails_with("y", "Cannot find variable `y` in this scope");
+    fails_with("x: i64; x", "Variable `x` is used before it is given a value");
+    fails_with("true := false; true", "Cannot declare `true`, which is a keyword");
+    evaluates("in := 3; in + 1", "4");
+    fails_with("public := 1;", "Cannot declare `public`, which is a keyword");
+  }
+
+  #[test]
+  fn integer_literals_fit_where_they_go() {
+    let f = "f: (a: u8) -> u8 { a }";
+    fails_with("x: u8 = 300;", "`300` is out of range for `u8`");
+    fails_with("y: i8 = -129;", "`-129` is out of range for `i8`");
+    evaluates("y: i8 = -128; y", "-128");
+    fails_with(&format!("{f} f(300)"), "`300` is out of range for `u8`");
+    fails_with(&format!("{f} x := 300; f(x)"), "`300` is out of range for `u8`");
+    evaluates(&format!("{f} x := 300; x = 3; f(x)"), "3");
+    evaluates(&format!("{f} x := 3; {{ x := 300; }} f(x)"), "3");
+  }
+
+  #[test]
+  fn lines_that_fail_to_bind_declare_nothing() {
+    Interpreter::on_large_stack(|mut interpreter| {
+      assert!(interpreter.run("y := undefined;".into()).is_err());
+      failed_with("y", interpreter.run("y".into()), "Cannot find variable `y` in this scope");
+      assert!(interpreter.run("f: () -> i64 { undefined }".into()).is_err());
+      failed_with("f()", interpreter.run("f()".into()), "Cannot find function `f` in this scope");
+      assert_eq!(interpreter.run("y := 2; y".into()).unwrap(), "2");
+      assert!(interpreter.run("z: i64 = 0; b := 1 / z;".into()).is_err());
+      failed_with(
+        "b = 3;",
+        interpreter.run("b = 3;".into()),
+        "Cannot find variable `b` in this scope",
+      );
+      failed_with("z", interpreter.run("z".into()), "Cannot find variable `z` in this scope");
+    })
+    .expect("the thread starts");
+  }
+
+  #[test]
+  fn records_are_built_read_and_changed() {
+    let person = "V: type = { name: pub string; age: i64; }";
+    evaluates(
+      &format!("{person} name := \"a\"; v := V {{ name, age = 3 }}; v.age = v.age + 1; \"{{v.name}} {{v.age}}\""),
+      "a 4",
+    );
+    fails_with(&format!("{person} V {{ name = \"a\" }}"), "Missing field(s) `age`");
+    fails_with(
+      &format!("{person} V {{ name = \"a\", age = 1, age = 2 }}"),
+      "field `age` is given a value more than once",
+    );
+    fails_with(
+      &format!("{person} V {{ name = \"a\", age = 1, height = 2 }}"),
+      "No field `height` in type `V`",
+    );
+    fails_with(
+      &format!("{person} v := V {{ name = \"a\", age = 1 }}; v.height"),
+      "No field `height` in type `V`",
+    );
+  }
+
+  #[test]
+  fn records_of_one_field_need_no_type_where_one_is_expected() {
+    let point = "V: type = { x: i64; }";
+    evaluates(
+      &format!("{point} V: impl = {{ new: () -> Self {{ return {{ x = 2 }}; }} }} V.new().x"),
+      "2",
+    );
+    evaluates(&format!("{point} v: V = {{ x = 3 }}; v.x"), "3");
+    evaluates(&format!("{point} f: (v: V) -> i64 {{ v.x }} f({{ x = 4 }})"), "4");
+    evaluates(&format!("{point} x := 5; v: V = {{ x, }}; v.x"), "5");
+    evaluates("x := 1; { x = 2 }; x", "2");
+  }
+
+  #[test]
+  fn methods_take_self_in_every_way() {
+    let person = "V: type = { name: string; } V: impl = { \
+                    new: (name: string) -> Self { V { name } } \
+                    greet: (&self) -> string { \"hi {self.name}\" } \
+                    rename: (&mut self, name: string) { self.name = name; } \
+                  }";
+    evaluates(&format!("{person} v := V.new(\"a\"); v.rename(\"b\"); v.greet()"), "hi b");
+    fails_with(
+      &format!("{person} v := V.new(\"a\"); v.shout()"),
+      "No method `shout` on type `V`",
+    );
+    fails_with(
+      &format!("{person} V.greet()"),
+      "`greet` takes `self`, so it is called on a value of `V`",
+    );
+    fails_with(&format!("{person} v := V.new(\"a\"); v.new(\"b\")"), "`new` takes no `self`");
+    fails_with(
+      &format!("{person} v := V.new(\"a\"); r := &v; r.rename(\"c\")"),
+      "cannot call `rename`, which takes `&mut self`, through a `&` reference",
+    );
+  }
+
+  #[test]
+  fn suffixes_type_integers() {
+    evaluates("0xFF + 0b1010 + 0o755 + 1_000_000", "1000758");
+    evaluates("x: u8 = 255u8; x", "255");
+    evaluates("10i32 + 1", "11");
+    fails_with("y: i64 = 10i32;", "expected `i64` but got `i32`");
+    fails_with("x := 10i32; y: i64 = 1; x + y", "cannot apply `+` to `i32` and `i64`");
+    fails_with("x := 255u8; x + 1", "integer overflow in `Addition` for `u8`");
+    fails_with("256u8", "Integer literal `256u8` is out of range for `u8`");
+  }
+
+  #[test]
+  fn most_negative_integers_are_literals() {
+    evaluates("x := -9223372036854775808; x", "-9223372036854775808");
+    evaluates("y := -128i8; y", "-128");
+    evaluates("-0x8000000000000000", "-9223372036854775808");
+    fails_with("x := 9223372036854775808;", "is out of range for `i64`");
+    fails_with("y := 128i8;", "Integer literal `128i8` is out of range for `i8`");
+    fails_with("z := -(9223372036854775808);", "is out of range for `i64`");
+  }
+
+  #[test]
+  fn blocks_give_their_last_expression() {
+    evaluates("x := 10; { y := x; y + 1 }", "11");
+    evaluates("x := { 1; }; x", "()");
+    evaluates("entry: () -> i32 = { 0 }", "0");
+    fails_with("1 2", "Expected `;` after expression");
+  }
+
+  #[test]
+  fn loops_run_until_they_stop() {
+    evaluates(
+      "sum := 0; i := 0; while i < 6 { i += 1; if i == 2 { continue; } sum += i; }; sum",
+      "19",
+    );
+    evaluates("i := 0; x := loop { i += 1; if i == 4 { break i * 10; } }; x", "40");
+    evaluates("s := 0; for i in 0..4 { s += i; }; s", "6");
+    evaluates("n := 0; for c in \"abc\" { n += 1; }; n", "3");
+    fails_with("if 1 { 2 }", "expected `bool` but got `{integer}`");
+    fails_with("break;", "`break` outside of a loop");
+    fails_with("while false { break 3; }", "only a `loop` can be given a value by `break`");
+  }
+
+  #[test]
+  fn enums_name_their_variants() {
+    let logic = "logic: enum = { false, true } ";
+    evaluates(&format!("{logic} logic.true"), "logic.true");
+    evaluates(&format!("{logic} logic.true == logic.false"), "false");
+    evaluates(&format!("{logic} logic.true != logic.false"), "true");
+    evaluates(
+      &format!(
+        "{logic} keep_going := logic.true; y := 0; \
+         while keep_going == logic.true {{ if y == 3 {{ keep_going = logic.false; }} y += 1; }}; y"
+      ),
+      "4",
+    );
+    fails_with(
+      &format!("{logic} logic.maybe"),
+      "No variant `maybe` in enum `logic`, expected one of `false`, `true`",
+    );
+  }
+
+  #[test]
+  fn traits_dispatch_statically_and_dynamically() {
+    let speak = "Speak: trait = { name: (&self) -> string; speak: (&self) -> string { \"I am {self.name()}\" } } \
+                 Dog: type = { n: string; } Dog: impl Speak = { name: (&self) -> string { self.n } } \
+                 Cat: type = { } \
+                 Cat: impl Speak = { name: (&self) -> string { \"cat\" } speak: (&self) -> string { \"meow\" } }";
+    evaluates(&format!("{speak} d := Dog {{ n = \"rex\" }}; d.speak()"), "I am rex");
+    evaluates(
+      &format!("{speak} hi<T: Speak>: (x: &T) -> string {{ x.speak() }} c := Cat {{}}; hi(&c)"),
+      "meow",
+    );
+    evaluates(
+      &format!(
+        "{speak} hi: (x: &Speak) -> string {{ x.speak() }} c := Cat {{}}; d := Dog {{ n = \"rex\" }}; \
+         \"{{hi(&c)}} {{hi(&d)}}\""
+      ),
+      "meow I am rex",
+    );
+    fails_with(
+      &format!("{speak} Cow: type = {{}} Cow: impl Speak = {{}}"),
+      "Missing method(s) `name` of trait `Speak` in impl for `Cow`",
+    );
+    fails_with(
+      &format!("{speak} hi<T: Speak>: (x: &T) -> string {{ x.speak() }} hi(&1)"),
+      "`{integer}` does not implement trait `Speak`",
+    );
+    fails_with(
+      &format!("{speak} s: Speak = Cat {{}};"),
+      "trait `Speak` can only be used behind a reference, as `&Speak`",
+    );
+  }
+
+  #[test]
+  fn modules_import_each_other() {
+    let circle = (
+      "shapes/circle.kon",
+      "Circle: export type = { r: pub i64; secret: i64; }\n\
+       area: export (c: &Circle) -> i64 { 3 * c.r * c.r }\n\
+       unit: export () -> Circle { Circle { r = 1, secret = 7 } }\n",
+    );
+    let shapes = (
+      "shapes.kon",
+      "export import shapes.circle.{self, area};\ntwo: export () -> i64 { 2 }\n",
+    );
+    let imports = [
+      (
+        "single",
+        "import shapes.circle.area;\nimport shapes.circle.unit;\nentry: () -> i64 = { area(&unit()) }",
+      ),
+      (
+        "list",
+        "import shapes.circle.{area, unit as one};\nentry: () -> i64 = { area(&one()) }",
+      ),
+      ("glob", "import shapes.circle.*;\nentry: () -> i64 = { area(&unit()) }"),
+      (
+        "self",
+        "import shapes.circle.{self};\nentry: () -> i64 = { circle.area(&circle.unit()) }",
+      ),
+      ("alias", "import shapes.circle as c;\nentry: () -> i64 = { c.area(&c.unit()) }"),
+    ];
+    for (name, main) in imports {
+      match run_files(name, &[("main.kon", main), circle]) {
+        Ok(result) => assert_eq!(result, "3", "`{main}`"),
+        Err(error) => panic!("`{main}` failed: {error:?}"),
+      }
+    }
+
+    let main = "import shapes.{area, circle, two};\nentry: () -> i64 = { area(&circle.unit()) + two() }";
+    match run_files("reexport", &[("main.kon", main), shapes, circle]) {
+      Ok(result) => assert_eq!(result, "5", "`{main}`"),
+      Err(error) => panic!("`{main}` failed: {error:?}"),
+    }
+
+    let main = "import shapes.circle.unit;\nentry: () -> i64 = { unit().secret }";
+    failed_with(
+      main,
+      run_files("private", &[("main.kon", main), circle]),
+      "field `secret` of `Circle` is private",
+    );
+    let main = "import nowhere.thing;";
+    failed_with(
+      main,
+      run_files("missing", &[("main.kon", main)]),
+      "Cannot find module `nowhere.thing`, tried `",
+    );
+    let main = "import b.x;\ny: export () -> i64 { 1 }";
+    failed_with(
+      main,
+      run_files("cycle", &[("a.kon", main), ("b.kon", "import a.y;\nx: export () -> i64 { 1 }")]),
+      "Modules import each other in a cycle: a -> b -> a",
+    );
+  }
+
+  #[test]
+  fn integers_widen_to_floats() {
+    evaluates("3.14", "3.14");
+    evaluates("1.5e-3", "0.0015");
+    evaluates("1 + 2.5", "3.5");
+    evaluates("x: i32 = 2; x + 0.5", "2.5");
+    evaluates("7 / 2", "3");
+    evaluates("7.0 / 2", "3.5");
+    fails_with("x: i32 = 2; y: i64 = 3; x + y", "cannot apply `+` to `i32` and `i64`");
+    evaluates("2.5 < 3", "true");
+    evaluates("1.5 >= 1.5", "true");
+    evaluates("1 == 1.0", "true");
+    evaluates("2 != 2.5", "true");
+  }
+
+  #[test]
+  fn strings_keep_what_they_escape() {
+    evaluates(r#""a\tb\\c\"d\u{41}""#, "a\tb\\c\"dA");
+    evaluates(r#""http://x" // a comment"#, "http://x");
+    evaluates("\"one\ntwo\"", "one\ntwo");
+    evaluates(r#"r"a\nb""#, r"a\nb");
+    evaluates(r###"r#"say "hi""#"###, r#"say "hi""#);
+    fails_with(r#""bad \q here""#, "Invalid escape sequence `\\q`");
+  }
+
+  #[test]
+  fn chars_compare_by_codepoint() {
+    evaluates("'a'", "a");
+    evaluates(r"'\u{1F600}'", "😀");
+    evaluates("'a' < 'b'", "true");
+    evaluates("'b' <= 'a'", "false");
+    evaluates("'a' == 'a'", "true");
+    evaluates("c: char = 'x'; c != 'y'", "true");
+  }
+
+  #[test]
+  fn comments_are_skipped() {
+    evaluates("1 /* a /* b */ c */ + 2", "3");
+    evaluates("/// One.\nf: () -> i64 { 1 } // the end\nf()", "1");
+    fails_with("1 /* never closed", "Unterminated block comment");
+  }
+
+  #[test]
+  fn characters_of_strings_are_places() {
+    evaluates("s := \"héllo\"; s[1]", "é");
+    evaluates("s := \"abc\"; s[1] = 'x'; s", "axc");
+    evaluates(
+      "V: type = { name: string; } v := V { name = \"bob\" }; v.name[0] = 'r'; v.name",
+      "rob",
+    );
+    fails_with("s := \"abc\"; s[3]", "index `3` is out of bounds for a string of 3 characters");
+    fails_with("s := \"abc\"; s[-1] = 'z';", "index `-1` is out of bounds");
+    fails_with("x := 5; x[0]", "`{integer}` cannot be indexed");
+    fails_with("s := \"abc\"; s[\"a\"]", "expected an integer index but got `string`");
+    fails_with("s := \"abc\"; s[0] = 1;", "expected `char` but got `{integer}`");
+    fails_with("f: (s: &string) { s[0] = 'x'; }", "cannot change `s` through a `&` reference");
+  }
+}
@@ -3,7 +3,7 @@ use std::{fmt::Debug, io};
 use strum::EnumDiscriminants;
 use thiserror::Error;
 
-use super::grammar::token::Position;
+use super::{grammar::span::Span, util::source_map::SourceMap};
 
 pub mod error_handler;
 
@@ -11,7 +11,7 @@ pub mod error_handler;
 pub enum KonError {
   #[error("interpreter caught {} error(s)", .0.len())]
   InterpreterErrors(Vec<InterpreterError>),
-  #[error("failed to evaluate expression: `{0}`")]
+  #[error("failed to evaluate expression: {0}")]
   Evaluation(String),
   #[error("{0}")]
   IOError(#[from] io::Error),
@@ -27,20 +27,97 @@ impl Debug for KonError {
 
 #[derive(Error, Debug, EnumDiscriminants, Clone, PartialEq, Eq)]
 pub enum InterpreterError {
-  #[error("Unknown token `{token}` {position}")]
-  UnknownToken { position: Position, token: String },
-  #[error("{message} {position}")]
-  SyntaxError { position: Position, message: String },
-  #[error("Unterminated string {position}")]
-  UnterminatedString { position: Position },
-  #[error("{message} {position}")]
-  ParseError { position: Position, message: String },
-  #[error("{message} {position}")]
-  EOFError { position: Position, message: String },
-  #[error("Unmatched `{delimiter}` {position}")]
-  UnmatchedDelimiter { position: Position, delimiter: String },
-  #[error("Unknown operator `{operator}` {position}")]
-  UnknownOperator { position: Position, operator: String },
+  #[error("Unknown token `{token}`")]
+  UnknownToken { span: Span, token: String },
+  #[error("{message}")]
+  SyntaxError { span: Span, message: String },
+  #[error("Unterminated string")]
+  UnterminatedString { span: Span },
+  #[error("Integer literal `{literal}` is out of range for `{data_type}` ({range})")]
+  LiteralOutOfRange {
+    span: Span,
+    literal: String,
+    data_type: String,
+    range: String,
+  },
+  #[error("Unterminated block comment")]
+  UnterminatedBlockComment { span: Span },
+  #[error("Unterminated character literal")]
+  UnterminatedChar { span: Span },
+  #[error("Empty character literal")]
+  EmptyChar { span: Span },
+  #[error("Character literal `'{lexeme}'` must contain exactly one codepoint")]
+  MultipleCodepointChar { span: Span, lexeme: String },
+  #[error("Invalid escape sequence `{sequence}`")]
+  InvalidEscape { span: Span, sequence: String },
+  #[error("{message}")]
+  ParseError { span: Span, message: String },
+  #[error("{message}")]
+  EOFError { span: Span, message: String },
+  #[error("Unmatched `{delimiter}`")]
+  UnmatchedDelimiter { span: Span, delimiter: String },
+  #[error("Unknown operator `{operator}`")]
+  UnknownOperator { span: Span, operator: String },
+  #[error("{message}")]
+  TypeError { span: Span, message: String },
+  #[error("Cannot find variable `{name}` in this scope")]
+  UndeclaredVariable { span: Span, name: String },
+  #[error("Variable `{name}` is used before it is given a value")]
+  UninitializedVariable { span: Span, name: String },
+  #[error("No variant `{variant}` in enum `{enumeration}`, expected one of {variants}")]
+  UnknownVariant {
+    span: Span,
+    enumeration: String,
+    variant: String,
+    variants: String,
+  },
+  #[error("Cannot find function `{name}` in this scope")]
+  UndeclaredFunction { span: Span, name: String },
+  #[error("Function `{name}` takes {expected} argument(s) but {found} were given")]
+  ArgumentCount {
+    span: Span,
+    name: String,
+    expected: usize,
+    found: usize,
+  },
+  #[error("`{name}` takes {expected} generic argument(s) but {found} were given")]
+  GenericArgumentCount {
+    span: Span,
+    name: String,
+    expected: usize,
+    found: usize,
+  },
+  #[error("No field `{field}` in type `{data_type}`")]
+  UnknownField {
+    span: Span,
+    data_type: String,
+    field: String,
+  },
+  #[error("No method `{method}` on type `{data_type}`")]
+  UnknownMethod {
+    span: Span,
+    data_type: String,
+    method: String,
+  },
+  #[error("Missing field(s) {fields} in record literal of `{data_type}`")]
+  MissingFields {
+    span: Span,
+    data_type: String,
+    fields: String,
+  },
+  #[error("Missing method(s) {methods} of trait `{trait_name}` in impl for `{data_type}`")]
+  MissingMethods {
+    span: Span,
+    trait_name: String,
+    data_type: String,
+    methods: String,
+  },
+  #[error("Cannot find module `{module}`, tried {tried}")]
+  UnresolvedImport { span: Span, module: String, tried: String },
+  #[error("Modules import each other in a cycle: {cycle}")]
+  ImportCycle { span: Span, cycle: String },
+  #[error("{message}")]
+  RuntimeError { span: Span, message: String },
   #[error("{0}")]
   Other(String),
 }
@@ -50,11 +127,50 @@ impl InterpreterError {
     self.into()
   }
 
-  pub fn report(&self) {
-    println!("{}", self.report_string());
+  /// Where in the source the error happened, if anywhere.
+  pub fn span(&self) -> Option<Span> {
+    match self {
+      InterpreterError::UnknownToken { span, .. }
+      | InterpreterError::SyntaxError { span, .. }
+      | InterpreterError::UnterminatedString { span }
+      | InterpreterError::LiteralOutOfRange { span, .. }
+      | InterpreterError::UnterminatedBlockComment { span }
+      | InterpreterError::UnterminatedChar { span }
+      | InterpreterError::EmptyChar { span }
+      | InterpreterError::MultipleCodepointChar { span, .. }
+      | InterpreterError::InvalidEscape { span, .. }
+      | InterpreterError::ParseError { span, .. }
+      | InterpreterError::EOFError { span, .. }
+      | InterpreterError::UnmatchedDelimiter { span, .. }
+      | InterpreterError::UnknownOperator { span, .. }
+      | InterpreterError::TypeError { span, .. }
+      | InterpreterError::UndeclaredVariable { span, .. }
+      | InterpreterError::UninitializedVariable { span, .. }
+      | InterpreterError::UnknownVariant { span, .. }
+      | InterpreterError::UndeclaredFunction { span, .. }
+      | InterpreterError::ArgumentCount { span, .. }
+      | InterpreterError::GenericArgumentCount { span, .. }
+      | InterpreterError::UnknownField { span, .. }
+      | InterpreterError::UnknownMethod { span, .. }
+      | InterpreterError::MissingFields { span, .. }
+      | InterpreterError::MissingMethods { span, .. }
+      | InterpreterError::UnresolvedImport { span, .. }
+      | InterpreterError::ImportCycle { span, .. }
+      | InterpreterError::RuntimeError { span, .. } => Some(*span),
+      InterpreterError::Other(_) => None,
+    }
   }
 
-  pub fn report_string(&self) -> String {
-    format!("{}", self)
+  pub fn report(&self, source_map: &SourceMap) {
+    println!("{}", self.report_string(source_map));
+  }
+
+  /// Renders the error along with the file, line and column it came from and
+  /// the offending source underlined.
+  pub fn report_string(&self, source_map: &SourceMap) -> String {
+    match self.span() {
+      Some(span) => source_map.render(span, &self.to_string()),
+      None => self.to_string(),
+    }
   }
 }
@@ -1,4 +1,5 @@
 use super::{InterpreterError, KonError};
+use crate::interpreter::util::source_map::SourceMap;
 
 pub struct ErrorHandler {
   errors: Vec<InterpreterError>,
@@ -33,16 +34,16 @@ impl ErrorHandler {
     self.errors.clear();
   }
 
-  pub fn report_errors(&self) -> KonError {
+  pub fn report_errors(&self, source_map: &SourceMap) -> KonError {
     for error in &self.errors {
-      error.report();
+      error.report(source_map);
     }
     return KonError::InterpreterErrors(self.errors().to_vec());
   }
 
-  pub fn try_report_errors(&self) -> Result<(), KonError> {
+  pub fn try_report_errors(&self, source_map: &SourceMap) -> Result<(), KonError> {
     if self.had_error() {
-      Err(self.report_errors())
+      Err(self.report_errors(source_map))
     } else {
       Ok(())
     }
@@ -1,7 +1,19 @@
 pub mod binding;
+pub mod constant;
+pub mod enumeration;
 pub mod expression;
+pub mod function;
+pub mod generic;
+pub mod implementation;
+pub mod import;
+pub mod item;
 pub mod keyword;
 pub mod literal;
+pub mod record;
+pub mod span;
+pub mod statement;
 pub mod symbol;
 pub mod syntax_tree;
 pub mod token;
+pub mod trait_declaration;
+pub mod type_expression;
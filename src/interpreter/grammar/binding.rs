@@ -1,4 +1,10 @@
 pub mod binder;
 pub mod bound_expression;
+pub mod bound_function;
 pub mod bound_operator;
+pub mod bound_statement;
+pub mod bound_tree;
 pub mod data_type;
+pub mod environment;
+pub mod interrupt;
+pub mod value;
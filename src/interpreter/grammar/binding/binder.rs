@@ -1,32 +1,1404 @@
+use std::{
+  collections::{HashMap, HashSet},
+  rc::Rc,
+};
+
 use foxy_utils::types::handle::Handle;
 
 use super::{
-  bound_expression::BoundExpression,
+  bound_expression::{
+    BoundExpression,
+    BoundFieldInitializer,
+    BoundInterpolationPart,
+    BoundIterable,
+    BoundPlace,
+    BoundPlaceStep,
+  },
+  bound_function::{BoundFunction, BoundParameter},
   bound_operator::{BoundBinaryOperator, BoundUnaryOperator},
+  bound_statement::BoundStatement,
+  bound_tree::BoundTree,
+  data_type::{FieldDefinition, Primitive, Type, TypeArgument, TypeDefinition, TypeId, TypeParameter},
+  value::Value,
+};
+use crate::interpreter::{
+  error::{error_handler::ErrorHandler, InterpreterError},
+  grammar::{
+    constant::Constant,
+    enumeration::Enum,
+    expression::{Expression, InterpolationPart},
+    function::{Function, ReceiverKind},
+    generic::{GenericArgument, GenericParameter},
+    implementation::Impl,
+    import::{write_path, ImportedPath, Include},
+    item::ItemKind,
+    keyword::Keyword,
+    literal::Literal,
+    record::{FieldInitializer, Record},
+    span::Span,
+    statement::Statement,
+    symbol::Symbol,
+    syntax_tree::SyntaxTree,
+    token::{LiteralToken, SymbolToken},
+    trait_declaration::Trait,
+    type_expression::TypeExpression,
+  },
 };
-use crate::interpreter::{error::error_handler::ErrorHandler, grammar::expression::Expression};
 
 pub struct Binder {
   errors: Handle<ErrorHandler>,
+  /// The types of the variables in scope, innermost scope last. The outermost
+  /// scope holds the top-level variables of every tree bound so far.
+  scopes: Vec<HashMap<String, Type>>,
+  /// The integers known to be in the variables in scope, by the index of
+  /// their scope and their name: those given a literal and not assigned to
+  /// since, for where they go to be checked as a literal would be.
+  integers: HashMap<(usize, String), i128>,
+  /// The functions in scope by name, which are those of every tree bound so
+  /// far for the program being run.
+  functions: HashMap<String, FunctionSymbol>,
+  /// The constants in scope by name, with the values they stand for.
+  constants: HashMap<String, BoundExpression>,
+  /// The modules in scope by name, with their paths.
+  module_names: HashMap<String, String>,
+  /// The modules bound so far, by path.
+  modules: HashMap<String, Module>,
+  /// The path of the module being bound, which is empty for the program
+  /// being run.
+  module: String,
+  /// What the module being bound exports so far.
+  exports: Namespace,
+  /// The types declared by every tree bound so far, indexed by
+  /// [`TypeId::index`].
+  definitions: Vec<TypeDefinition>,
+  /// The path of the module each type is declared in, indexed by
+  /// [`TypeId::index`].
+  declared_in: Vec<String>,
+  /// The types in scope by name.
+  types: HashMap<String, TypeId>,
+  /// The methods and associated functions of each type, by name.
+  methods: HashMap<TypeId, HashMap<String, Method>>,
+  /// The methods of each trait.
+  traits: HashMap<TypeId, TraitDefinition>,
+  /// The traits each type implements.
+  implementations: HashMap<TypeId, Vec<TypeId>>,
+  /// The type parameters in scope by name, such as the `T` of a generic
+  /// function or the `Self` of a trait.
+  generics: HashMap<String, Type>,
+  /// The return type of the function being bound, if any.
+  return_type: Option<Type>,
+  /// How the method being bound takes `self`, if it does.
+  receiver: Option<ReceiverKind>,
+  /// The loops around the expression being bound, innermost last.
+  loops: Vec<Loop>,
+}
+
+struct Loop {
+  /// Whether a `break` can give the loop a value, as only `loop` can.
+  takes_value: bool,
+  /// The type of the values given by the `break`s bound so far.
+  break_type: Option<Type>,
+}
+
+/// A function in scope. `path` is the name it is called by when the program
+/// runs, which is qualified by its module if it isn't the program's own.
+#[derive(Debug, Clone)]
+struct FunctionSymbol {
+  path: String,
+  signature: Signature,
+}
+
+/// What a module makes known by name. Types, functions, constants and
+/// modules each have names of their own, so one name can be a type and a
+/// function at once.
+#[derive(Debug, Clone, Default)]
+struct Namespace {
+  types: HashMap<String, TypeId>,
+  functions: HashMap<String, FunctionSymbol>,
+  constants: HashMap<String, BoundExpression>,
+  /// Modules by name, with their paths.
+  modules: HashMap<String, String>,
+}
+
+impl Namespace {
+  fn contains(&self, name: &str) -> bool {
+    self.types.contains_key(name)
+      || self.functions.contains_key(name)
+      || self.constants.contains_key(name)
+      || self.modules.contains_key(name)
+  }
+
+  /// The namespace with only what is called `name`, under the name `alias`.
+  fn only(&self, name: &str, alias: &str) -> Namespace {
+    fn entry<T: Clone>(map: &HashMap<String, T>, name: &str, alias: &str) -> HashMap<String, T> {
+      (map.get(name))
+        .map(|entry| (alias.to_string(), entry.clone()))
+        .into_iter()
+        .collect()
+    }
+
+    Namespace {
+      types: entry(&self.types, name, alias),
+      functions: entry(&self.functions, name, alias),
+      constants: entry(&self.constants, name, alias),
+      modules: entry(&self.modules, name, alias),
+    }
+  }
+
+  fn extend(&mut self, other: Namespace) {
+    self.types.extend(other.types);
+    self.functions.extend(other.functions);
+    self.constants.extend(other.constants);
+    self.modules.extend(other.modules);
+  }
+}
+
+/// What the program being run has declared, for the binder to go back to
+/// when binding more of it fails, so that what failed isn't left declared.
+pub struct Checkpoint {
+  scopes: Vec<HashMap<String, Type>>,
+  integers: HashMap<(usize, String), i128>,
+  names: Namespace,
+  methods: HashMap<TypeId, HashMap<String, Method>>,
+  traits: HashMap<TypeId, TraitDefinition>,
+  implementations: HashMap<TypeId, Vec<TypeId>>,
+}
+
+/// A module bound so far.
+struct Module {
+  exports: Namespace,
+  /// Every name the module declares, exported or not.
+  declared: HashSet<String>,
+}
+
+/// What a call needs to know about a function, which is known before its body
+/// is bound.
+#[derive(Debug, Clone)]
+struct Signature {
+  generics: Vec<Rc<TypeParameter>>,
+  parameters: Vec<BoundParameter>,
+  return_type: Type,
+}
+
+impl Signature {
+  /// The signature with the type parameters named in `bindings` replaced by
+  /// the types they are given.
+  fn substitute(&self, bindings: &HashMap<String, TypeArgument>) -> Signature {
+    Signature {
+      generics: self.generics.clone(),
+      parameters: self
+        .parameters
+        .iter()
+        .map(|parameter| BoundParameter {
+          name: parameter.name.clone(),
+          data_type: parameter.data_type.substitute(bindings),
+        })
+        .collect(),
+      return_type: self.return_type.substitute(bindings),
+    }
+  }
+}
+
+/// A function declared for a type. A method takes `self` as its first
+/// parameter, while an associated function such as `new` takes no `self`.
+#[derive(Debug, Clone)]
+struct Method {
+  receiver: Option<ReceiverKind>,
+  signature: Signature,
+  /// Whether the method was declared `pub`, for other modules to call.
+  exported: bool,
+}
+
+impl Method {
+  /// Whether two methods can be called in the same ways.
+  fn matches(&self, other: &Method) -> bool {
+    self.receiver == other.receiver
+      && self.signature.generics.len() == other.signature.generics.len()
+      && self.signature.return_type == other.signature.return_type
+      && self.signature.parameters.len() == other.signature.parameters.len()
+      && (self.signature.parameters.iter())
+        .zip(&other.signature.parameters)
+        .all(|(parameter, other)| parameter.data_type == other.data_type)
+  }
+}
+
+impl std::fmt::Display for Method {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut parameters: Vec<_> = (self.signature.parameters.iter())
+      .skip(usize::from(self.receiver.is_some()))
+      .map(|parameter| parameter.data_type.to_string())
+      .collect();
+    if let Some(receiver) = self.receiver {
+      parameters.insert(0, receiver.to_string());
+    }
+    write!(f, "({}) -> {}", parameters.join(", "), self.signature.return_type)
+  }
+}
+
+/// What the binder knows about a trait. Its methods are declared with `Self`
+/// standing for `self_type`, a type parameter bound by the trait.
+#[derive(Debug, Clone)]
+struct TraitDefinition {
+  self_type: Type,
+  /// The methods in the order they are declared.
+  methods: Vec<TraitMethod>,
+}
+
+#[derive(Debug, Clone)]
+struct TraitMethod {
+  name: String,
+  method: Method,
+  /// Whether every `impl` has to give the method a body, as it has no default.
+  required: bool,
+}
+
+/// A method a call was resolved to, declared by `owner`. A `dispatch`ed
+/// method is looked up on the type of the receiver when called.
+struct FoundMethod {
+  owner: TypeId,
+  method: Method,
+  dispatch: bool,
 }
 
 impl Binder {
+  /// What the variables holding the values of const parameters are called
+  /// before the parameter's name, which no name in the program can start with.
+  const CONST_PREFIX: &str = "const ";
+
   pub fn new(errors: Handle<ErrorHandler>) -> Self {
-    Self { errors }
+    Self {
+      errors,
+      scopes: vec![HashMap::new()],
+      integers: HashMap::new(),
+      functions: HashMap::new(),
+      constants: HashMap::new(),
+      module_names: HashMap::new(),
+      modules: HashMap::new(),
+      module: String::new(),
+      exports: Namespace::default(),
+      definitions: Vec::new(),
+      declared_in: Vec::new(),
+      types: HashMap::new(),
+      methods: HashMap::new(),
+      traits: HashMap::new(),
+      implementations: HashMap::new(),
+      generics: HashMap::new(),
+      return_type: None,
+      receiver: None,
+      loops: Vec::new(),
+    }
   }
 
-  pub fn bind(&mut self, syntax: Expression) -> BoundExpression {
-    match syntax {
-      Expression::Literal { token } => {
-        let value = token.literal.value();
-        BoundExpression::Literal {
-          data_type: (*value).type_id(),
-          position: token.position,
-          value,
+  /// Binds the module at `path`, such as `core.array`, which the program and
+  /// other modules can import once it is bound. It has names of its own, so
+  /// only what it exports can be used elsewhere.
+  pub fn bind_module(&mut self, path: &str, tree: SyntaxTree) -> BoundTree {
+    let module = std::mem::replace(&mut self.module, path.into());
+    let scopes = std::mem::replace(&mut self.scopes, vec![HashMap::new()]);
+    let integers = std::mem::take(&mut self.integers);
+    let functions = std::mem::take(&mut self.functions);
+    let types = std::mem::take(&mut self.types);
+    let constants = std::mem::take(&mut self.constants);
+    let module_names = std::mem::take(&mut self.module_names);
+    let outer_exports = std::mem::take(&mut self.exports);
+
+    let declared = (tree.items.iter())
+      .filter_map(|item| match &item.kind {
+        ItemKind::Function(Function { name, .. })
+        | ItemKind::Enum(Enum { name, .. })
+        | ItemKind::Record(Record { name, .. })
+        | ItemKind::Trait(Trait { name, .. })
+        | ItemKind::Constant(Constant { name, .. }) => Some(name.literal.lexeme()),
+        ItemKind::Statement(_) | ItemKind::Impl(_) | ItemKind::Import(_) | ItemKind::Include(_) => None,
+      })
+      .collect();
+    let bound = self.bind(tree);
+    let exports = std::mem::replace(&mut self.exports, outer_exports);
+    self.modules.insert(path.into(), Module { exports, declared });
+
+    self.module = module;
+    self.scopes = scopes;
+    self.integers = integers;
+    self.functions = functions;
+    self.types = types;
+    self.constants = constants;
+    self.module_names = module_names;
+    bound
+  }
+
+  /// Whether the module at `path` has been bound.
+  pub fn has_module(&self, path: &str) -> bool {
+    self.modules.contains_key(path)
+  }
+
+  /// What the program has declared so far, to [`restore`](Self::restore).
+  pub fn checkpoint(&self) -> Checkpoint {
+    Checkpoint {
+      scopes: self.scopes.clone(),
+      integers: self.integers.clone(),
+      names: Namespace {
+        types: self.types.clone(),
+        functions: self.functions.clone(),
+        constants: self.constants.clone(),
+        modules: self.module_names.clone(),
+      },
+      methods: self.methods.clone(),
+      traits: self.traits.clone(),
+      implementations: self.implementations.clone(),
+    }
+  }
+
+  /// Forgets what the program declared since `checkpoint`, as is done when
+  /// what declared it doesn't bind or fails as it runs.
+  pub fn restore(&mut self, checkpoint: Checkpoint) {
+    self.scopes = checkpoint.scopes;
+    self.integers = checkpoint.integers;
+    self.types = checkpoint.names.types;
+    self.functions = checkpoint.names.functions;
+    self.constants = checkpoint.names.constants;
+    self.module_names = checkpoint.names.modules;
+    self.methods = checkpoint.methods;
+    self.traits = checkpoint.traits;
+    self.implementations = checkpoint.implementations;
+  }
+
+  /// Forgets the module at `path`, so that it is loaded again the next time
+  /// it is imported, as is done when it doesn't bind.
+  pub fn forget_module(&mut self, path: &str) {
+    self.modules.remove(path);
+  }
+
+  pub fn bind(&mut self, tree: SyntaxTree) -> BoundTree {
+    // what the tree imports is in scope before anything it declares
+    for item in &tree.items {
+      match &item.kind {
+        ItemKind::Import(import) => {
+          for path in import.tree.paths() {
+            self.import(&path, import.exported);
+          }
+        }
+        ItemKind::Include(include) => self.include(include),
+        _ => {}
+      }
+    }
+
+    // types and functions can be used above where they are declared, so they
+    // are all known before anything is bound
+    let mut declared = HashSet::new();
+    let mut records = Vec::new();
+    let mut traits = Vec::new();
+    for item in &tree.items {
+      let name = match &item.kind {
+        ItemKind::Enum(enumeration) => &enumeration.name,
+        ItemKind::Record(record) => &record.name,
+        ItemKind::Trait(declaration) => &declaration.name,
+        ItemKind::Statement(_)
+        | ItemKind::Function(_)
+        | ItemKind::Impl(_)
+        | ItemKind::Constant(_)
+        | ItemKind::Import(_)
+        | ItemKind::Include(_) => continue,
+      };
+      let lexeme = name.literal.lexeme();
+      if !declared.insert(lexeme.clone()) {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: name.span,
+          message: format!("type `{lexeme}` is declared more than once"),
+        });
+      }
+
+      match &item.kind {
+        ItemKind::Enum(enumeration) => self.declare_enum(enumeration),
+        ItemKind::Record(record) => {
+          let index = self.declare_type(lexeme.clone(), TypeDefinition::Record {
+            generics: Vec::new(),
+            fields: Vec::new(),
+          });
+          records.push((index, record));
+        }
+        ItemKind::Trait(declaration) => {
+          self.declare_type(lexeme.clone(), TypeDefinition::Trait);
+          traits.push((self.types[&lexeme].clone(), declaration));
+        }
+        ItemKind::Statement(_)
+        | ItemKind::Function(_)
+        | ItemKind::Impl(_)
+        | ItemKind::Constant(_)
+        | ItemKind::Import(_)
+        | ItemKind::Include(_) => {}
+      }
+
+      let exported = match &item.kind {
+        ItemKind::Enum(enumeration) => enumeration.exported,
+        ItemKind::Record(record) => record.exported,
+        ItemKind::Trait(declaration) => declaration.exported,
+        _ => false,
+      };
+      if exported {
+        self.exports.types.insert(lexeme.clone(), self.types[&lexeme].clone());
+      }
+    }
+    // fields and methods can use types declared below them, so they are
+    // resolved once every type has a name and its type parameters
+    for (index, record) in &records {
+      self.definitions[*index] = TypeDefinition::Record {
+        generics: self.generic_parameters(&record.generics),
+        fields: Vec::new(),
+      };
+    }
+    for (index, record) in records {
+      let TypeDefinition::Record { generics, .. } = self.definitions[index].clone() else {
+        unreachable!("the record was just declared")
+      };
+      let outer_generics = self.generics.clone();
+      self.generics.extend(Self::generics_in_scope(&generics));
+      let fields = self.record_fields(record);
+      self.generics = outer_generics;
+      self.definitions[index] = TypeDefinition::Record { generics, fields };
+    }
+    for (id, declaration) in traits {
+      self.declare_trait(id, declaration);
+    }
+
+    let mut declared = HashSet::new();
+    let mut signatures = Vec::new();
+    for item in &tree.items {
+      if let ItemKind::Function(function) = &item.kind {
+        let name = function.name.literal.lexeme();
+        if !declared.insert(name.clone()) {
+          self.errors.get_mut().push(InterpreterError::TypeError {
+            span: function.name.span,
+            message: format!("function `{name}` is declared more than once"),
+          });
+        }
+
+        let signature = self.signature(function);
+        let symbol = FunctionSymbol {
+          path: self.qualified(&name),
+          signature: signature.clone(),
+        };
+        if function.exported {
+          self.exports.functions.insert(name.clone(), symbol.clone());
+        }
+        self.functions.insert(name, symbol);
+        signatures.push(signature);
+      }
+    }
+
+    // the methods of each type and `impl`, or `None` for an `impl` of a type
+    // that doesn't exist
+    let mut method_groups = Vec::new();
+    for item in &tree.items {
+      let (target, generics, methods) = match &item.kind {
+        ItemKind::Record(record) => (&record.name, &record.generics, &record.methods),
+        ItemKind::Impl(implementation) => (&implementation.target, &implementation.generics, &implementation.methods),
+        ItemKind::Statement(_)
+        | ItemKind::Function(_)
+        | ItemKind::Enum(_)
+        | ItemKind::Trait(_)
+        | ItemKind::Constant(_)
+        | ItemKind::Import(_)
+        | ItemKind::Include(_) => continue,
+      };
+      let lexeme = target.literal.lexeme();
+      let Some(owner) = self.types.get(&lexeme).cloned() else {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: target.span,
+          message: format!("cannot find type `{lexeme}` in this scope"),
+        });
+        method_groups.push(None);
+        continue;
+      };
+      if let ItemKind::Impl(implementation) = &item.kind {
+        self.check_impl_parameters(&owner, implementation);
+      }
+      let methods = self.declare_methods(&owner, generics, methods);
+      if let ItemKind::Impl(implementation) = &item.kind {
+        self.implement(&owner, implementation, &methods);
+      }
+      method_groups.push(Some((owner, methods)));
+    }
+
+    // constants are bound before the functions that can use them, in the
+    // order they are declared
+    let (constants, items): (Vec<_>, Vec<_>) =
+      (tree.items.into_iter()).partition(|item| matches!(item.kind, ItemKind::Constant(_)));
+    let mut declared = HashSet::new();
+    for item in constants {
+      let ItemKind::Constant(constant) = item.kind else {
+        unreachable!("only constants were taken")
+      };
+      let name = constant.name.literal.lexeme();
+      if !declared.insert(name.clone()) {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: constant.name.span,
+          message: format!("constant `{name}` is declared more than once"),
+        });
+      }
+      self.bind_constant(constant);
+    }
+
+    let mut signatures = signatures.into_iter();
+    let mut method_groups = method_groups.into_iter();
+    let mut functions = Vec::new();
+    let mut methods = Vec::new();
+    let mut statements = Vec::new();
+    for item in items {
+      let declared = match item.kind {
+        ItemKind::Statement(statement) if !self.module.is_empty() => {
+          self.errors.get_mut().push(InterpreterError::TypeError {
+            span: statement.span(),
+            message: format!(
+              "module `{}` can only declare items, as only the program being run has top-level statements",
+              self.module
+            ),
+          });
+          continue;
+        }
+        ItemKind::Statement(statement) => {
+          statements.push(self.bind_statement(statement));
+          continue;
+        }
+        ItemKind::Function(function) => {
+          let signature = signatures.next().expect("every function has a signature");
+          let constants = self.const_parameters(None, "", &signature.generics);
+          let mut function = self.bind_function(function, signature, constants);
+          function.name = self.qualified(&function.name);
+          functions.push(Rc::new(function));
+          continue;
+        }
+        ItemKind::Enum(_) | ItemKind::Constant(_) | ItemKind::Import(_) | ItemKind::Include(_) => continue,
+        ItemKind::Trait(declaration) => {
+          methods.extend(self.bind_trait_methods(declaration));
+          continue;
+        }
+        ItemKind::Record(record) => (record.generics, record.methods),
+        ItemKind::Impl(implementation) => (implementation.generics, implementation.methods),
+      };
+      let (generics, declared) = declared;
+
+      let Some((owner, signatures)) = method_groups.next().expect("every type and `impl` has its methods") else {
+        continue;
+      };
+      for (function, method) in declared.into_iter().zip(signatures) {
+        let function = self.bind_method(&owner, &generics, function, method);
+        methods.push((owner.clone(), Rc::new(function)));
+      }
+    }
+
+    BoundTree {
+      functions,
+      methods,
+      statements,
+    }
+  }
+
+  /// The name a function of the module being bound is called by when the
+  /// program runs. The program's own functions keep their names.
+  fn qualified(&self, name: &str) -> String {
+    match self.module.is_empty() {
+      true => name.to_string(),
+      false => format!("{}.{name}", self.module),
+    }
+  }
+
+  /// Brings what `path` names into scope, and into what the module exports if
+  /// the import is `exported`. The module is the longest part of the path
+  /// that names one, and the rest names what is in it.
+  fn import(&mut self, path: &ImportedPath, exported: bool) {
+    let segments: Vec<_> = path.segments.iter().map(|segment| segment.literal.lexeme()).collect();
+    let Some(split) = (1..=segments.len())
+      .rev()
+      .find(|split| self.modules.contains_key(&segments[..*split].join(".")))
+    else {
+      self.errors.get_mut().push(InterpreterError::TypeError {
+        span: path.span,
+        message: format!("cannot find module `{}`", path.path()),
+      });
+      return;
+    };
+    let module = segments[..split].join(".");
+
+    let (within, name) = match segments.split_last() {
+      // the path names the module itself
+      Some((name, _)) if split == segments.len() => (
+        Namespace {
+          modules: HashMap::from([(name.clone(), module)]),
+          ..Default::default()
+        },
+        name,
+      ),
+      Some((name, inner)) => {
+        let mut within = self.modules[&module].exports.clone();
+        let mut within_path = module;
+        for segment in &inner[split..] {
+          let Some(inner) = self.enter(&within, &within_path, segment, path.span) else {
+            return;
+          };
+          within = inner;
+          within_path = format!("{within_path}.{segment}");
+        }
+        if !within.contains(name) {
+          self.missing_export(&within_path, name, path.span);
+          return;
+        }
+        (within, name)
+      }
+      None => unreachable!("an import path has at least one segment"),
+    };
+
+    let imported = match path.glob {
+      true => match self.enter(&within, "", name, path.span) {
+        Some(inner) => inner,
+        None => return,
+      },
+      false => within.only(name, &path.name().literal.lexeme()),
+    };
+    if exported {
+      self.exports.extend(imported.clone());
+    }
+    self.types.extend(imported.types);
+    self.functions.extend(imported.functions);
+    self.constants.extend(imported.constants);
+    self.module_names.extend(imported.modules);
+  }
+
+  /// What is in `name` of `within`, which is a module or an enum, whose
+  /// variants are constants.
+  fn enter(&mut self, within: &Namespace, within_path: &str, name: &str, span: Span) -> Option<Namespace> {
+    if let Some(module) = within.modules.get(name) {
+      return Some(self.modules[module].exports.clone());
+    }
+    if let Some(id) = within.types.get(name)
+      && let TypeDefinition::Enum { variants } = &self.definitions[id.index]
+    {
+      let constants = (variants.iter())
+        .map(|variant| {
+          let value = BoundExpression::Literal {
+            data_type: Type::UserDefined(id.clone()),
+            span,
+            value: Value::Enum {
+              data_type: id.clone(),
+              variant: variant.as_str().into(),
+            },
+          };
+          (variant.clone(), value)
+        })
+        .collect();
+      return Some(Namespace {
+        constants,
+        ..Default::default()
+      });
+    }
+
+    match within.contains(name) {
+      true => self.errors.get_mut().push(InterpreterError::TypeError {
+        span,
+        message: format!("`{name}` is not a module or an enum"),
+      }),
+      false => self.missing_export(within_path, name, span),
+    }
+    None
+  }
+
+  /// Reports that `name` can't be found in the module or enum at `within`.
+  fn missing_export(&mut self, within: &str, name: &str, span: Span) {
+    let message = match self.modules.get(within) {
+      Some(module) if module.declared.contains(name) => format!("`{name}` is not exported by `{within}`"),
+      _ => format!("cannot find `{name}` in `{within}`"),
+    };
+    self
+      .errors
+      .get_mut()
+      .push(InterpreterError::TypeError { span, message });
+  }
+
+  /// Makes the module `include` names usable by its name.
+  fn include(&mut self, include: &Include) {
+    let Some(name) = include.path.last() else {
+      return;
+    };
+    let path = write_path(&include.path);
+    if !self.modules.contains_key(&path) {
+      self.errors.get_mut().push(InterpreterError::TypeError {
+        span: include.span,
+        message: format!("cannot find module `{path}`"),
+      });
+      return;
+    }
+    self.module_names.insert(name.literal.lexeme(), path);
+  }
+
+  /// Binds the value of a constant, which stands in for the constant wherever
+  /// it is used. As functions can use it too, it can't use any variables.
+  fn bind_constant(&mut self, constant: Constant) {
+    let declared = (constant.data_type.as_ref()).map(|data_type| self.resolve_type(data_type));
+    let outer_scopes = std::mem::replace(&mut self.scopes, vec![HashMap::new()]);
+    let value = self.bind_expected(constant.value, declared.as_ref());
+    self.scopes = outer_scopes;
+
+    let value = match declared {
+      Some(declared) => {
+        match self.accepts(&declared, &value.data_type()) {
+          true => self.check_range(&declared, &value),
+          false => self.errors.get_mut().push(InterpreterError::TypeError {
+            span: value.span(),
+            message: format!("expected `{declared}` but got `{}`", value.data_type()),
+          }),
+        }
+        // gives the value the declared type, such as the `i32` of a `0`
+        BoundExpression::Grouping {
+          data_type: declared,
+          span: value.span(),
+          operand: Box::new(value),
         }
       }
-      Expression::Unary { operator, operand } => {
-        let operand = Box::new(self.bind(*operand));
+      None => value,
+    };
+
+    let name = constant.name.literal.lexeme();
+    if constant.exported {
+      self.exports.constants.insert(name.clone(), value.clone());
+    }
+    self.constants.insert(name, value);
+  }
+
+  /// The value of the constant `name`, unless a variable of that name hides
+  /// it.
+  fn constant(&self, name: &str) -> Option<BoundExpression> {
+    if self.scopes.iter().any(|scope| scope.contains_key(name)) {
+      return None;
+    }
+    self.constants.get(name).cloned()
+  }
+
+  /// Makes the methods of `owner` known, giving their signatures in the order
+  /// they are declared.
+  fn declare_methods(&mut self, owner: &TypeId, generics: &[GenericParameter], functions: &[Function]) -> Vec<Method> {
+    let self_type = self.self_type(owner);
+    self.with_self_type(owner, generics, |binder| {
+      let mut methods = Vec::new();
+      for function in functions {
+        let name = function.name.literal.lexeme();
+        let declared = binder.methods.entry(owner.clone()).or_default();
+        if declared.contains_key(&name) {
+          binder.errors.get_mut().push(InterpreterError::TypeError {
+            span: function.name.span,
+            message: format!("`{}` already has a method `{name}`", owner.name),
+          });
+        }
+
+        let method = binder.method(function, &self_type);
+        binder
+          .methods
+          .entry(owner.clone())
+          .or_default()
+          .insert(name, method.clone());
+        methods.push(method);
+      }
+      methods
+    })
+  }
+
+  /// The signature of a method of `self_type`, where a method taking `self`
+  /// has it as its first parameter.
+  fn method(&mut self, function: &Function, self_type: &Type) -> Method {
+    let mut signature = self.signature(function);
+    let receiver = function.receiver.as_ref().map(|receiver| {
+      if let Some(declared) = &receiver.data_type {
+        let declared = self.resolve_type(declared);
+        if !self_type.accepts(&declared) {
+          self.errors.get_mut().push(InterpreterError::TypeError {
+            span: receiver.span,
+            message: format!("expected `self` to be a `{self_type}` but got `{declared}`"),
+          });
+        }
+      }
+
+      signature.parameters.insert(0, BoundParameter {
+        name: Keyword::_Self.lexeme().into(),
+        data_type: self_type.clone(),
+      });
+      receiver.kind
+    });
+
+    Method {
+      receiver,
+      signature,
+      exported: function.exported,
+    }
+  }
+
+  fn bind_method(
+    &mut self,
+    owner: &TypeId,
+    generics: &[GenericParameter],
+    function: Function,
+    method: Method,
+  ) -> BoundFunction {
+    self.with_self_type(owner, generics, |binder| {
+      let outer_receiver = std::mem::replace(&mut binder.receiver, method.receiver);
+      let name = function.name.literal.lexeme();
+      let constants = binder.const_parameters(Some(owner), &name, &method.signature.generics);
+      let function = binder.bind_function(function, method.signature, constants);
+      binder.receiver = outer_receiver;
+      function
+    })
+  }
+
+  /// Makes the methods of a trait known. Inside the trait, `Self` is a type
+  /// parameter standing for whichever type implements it.
+  fn declare_trait(&mut self, id: TypeId, declaration: &Trait) {
+    let self_type = Type::Parameter(Rc::new(TypeParameter {
+      name: Keyword::_SelfType.lexeme().into(),
+      bounds: vec![id.clone()],
+      const_type: None,
+    }));
+
+    let outer_generics = self.generics.clone();
+    self
+      .generics
+      .insert(Keyword::_SelfType.lexeme().into(), self_type.clone());
+    let mut methods: Vec<TraitMethod> = Vec::new();
+    for function in &declaration.methods {
+      let name = function.name.literal.lexeme();
+      if methods.iter().any(|method| method.name == name) {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: function.name.span,
+          message: format!("`{}` already has a method `{name}`", id.name),
+        });
+      }
+
+      methods.push(TraitMethod {
+        name,
+        method: self.method(function, &self_type),
+        required: function.body.is_none(),
+      });
+    }
+    self.generics = outer_generics;
+
+    self.traits.insert(id, TraitDefinition { self_type, methods });
+  }
+
+  /// Binds the default methods of a trait, which are methods of the trait
+  /// itself, used by the types that don't replace them.
+  fn bind_trait_methods(&mut self, declaration: Trait) -> Vec<(TypeId, Rc<BoundFunction>)> {
+    let Some(id) = self.types.get(&declaration.name.literal.lexeme()).cloned() else {
+      return Vec::new();
+    };
+    let Some(definition) = self.traits.get(&id).cloned() else {
+      return Vec::new();
+    };
+
+    let outer_generics = self.generics.clone();
+    self
+      .generics
+      .insert(Keyword::_SelfType.lexeme().into(), definition.self_type);
+    let mut defaults = Vec::new();
+    for (function, declared) in declaration.methods.into_iter().zip(definition.methods) {
+      if function.body.is_none() {
+        continue;
+      }
+      let outer_receiver = std::mem::replace(&mut self.receiver, declared.method.receiver);
+      let constants = self.const_parameters(None, "", &declared.method.signature.generics);
+      let function = self.bind_function(function, declared.method.signature, constants);
+      self.receiver = outer_receiver;
+      defaults.push((id.clone(), Rc::new(function)));
+    }
+    self.generics = outer_generics;
+    defaults
+  }
+
+  /// Checks an `impl Trait for Type` against the trait: its methods have to
+  /// be declared by the trait, with the same signatures once `Self` is the
+  /// type, and every method without a default has to be among them.
+  fn implement(&mut self, owner: &TypeId, implementation: &Impl, methods: &[Method]) {
+    let Some(trait_name) = &implementation.trait_name else {
+      return;
+    };
+    let Some(trait_id) = self.resolve_trait(trait_name) else {
+      return;
+    };
+
+    let implemented = self.implementations.entry(owner.clone()).or_default();
+    if implemented.contains(&trait_id) {
+      self.errors.get_mut().push(InterpreterError::TypeError {
+        span: trait_name.span(),
+        message: format!("`{}` is already implemented for `{}`", trait_id.name, owner.name),
+      });
+      return;
+    }
+    implemented.push(trait_id.clone());
+
+    let declared = self
+      .traits
+      .get(&trait_id)
+      .map_or_else(Vec::new, |definition| definition.methods.clone());
+    let bindings = HashMap::from([(Keyword::_SelfType.lexeme().into(), TypeArgument::Type(self.self_type(owner)))]);
+    for (function, method) in implementation.methods.iter().zip(methods) {
+      let name = function.name.literal.lexeme();
+      let Some(declared) = declared.iter().find(|declared| declared.name == name) else {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: function.name.span,
+          message: format!("method `{name}` is not a member of trait `{}`", trait_id.name),
+        });
+        continue;
+      };
+
+      let expected = Method {
+        receiver: declared.method.receiver,
+        signature: declared.method.signature.substitute(&bindings),
+        exported: true,
+      };
+      if !expected.matches(method) {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: function.name.span,
+          message: format!(
+            "expected `{name}` to be `{expected}` as in trait `{}` but got `{method}`",
+            trait_id.name
+          ),
+        });
+      }
+    }
+
+    let missing: Vec<_> = declared
+      .iter()
+      .filter(|declared| declared.required)
+      .filter(|declared| {
+        !implementation
+          .methods
+          .iter()
+          .any(|function| function.name.literal.lexeme() == declared.name)
+      })
+      .map(|declared| format!("`{}`", declared.name))
+      .collect();
+    if !missing.is_empty() {
+      self.errors.get_mut().push(InterpreterError::MissingMethods {
+        span: implementation.target.span,
+        trait_name: trait_id.name.to_string(),
+        data_type: owner.name.to_string(),
+        methods: missing.join(", "),
+      });
+    }
+  }
+
+  /// The trait named by `name`, reporting an error if it doesn't name one.
+  fn resolve_trait(&mut self, name: &TypeExpression) -> Option<TypeId> {
+    let found = match name {
+      TypeExpression::Path {
+        segments, arguments, ..
+      } if arguments.is_empty() => {
+        let names: Vec<_> = segments.iter().map(|segment| segment.literal.lexeme()).collect();
+        match names.as_slice() {
+          [name] => self.types.get(name),
+          [modules @ .., name] => {
+            (self.module_at(modules)).and_then(|module| self.modules[&module].exports.types.get(name))
+          }
+          [] => None,
+        }
+      }
+      _ => None,
+    };
+    let message = match found {
+      Some(id) if self.is_trait(id) => return Some(id.clone()),
+      Some(_) => format!("`{name}` is not a trait"),
+      None => format!("cannot find trait `{name}` in this scope"),
+    };
+    self.errors.get_mut().push(InterpreterError::TypeError {
+      span: name.span(),
+      message,
+    });
+    None
+  }
+
+  fn is_trait(&self, id: &TypeId) -> bool {
+    matches!(self.definitions[id.index], TypeDefinition::Trait)
+  }
+
+  /// Whether values of `data_type` have the methods of a trait, as the types
+  /// implementing it, type parameters bound by it and references to either
+  /// do.
+  fn implements(&self, data_type: &Type, trait_id: &TypeId) -> bool {
+    match data_type {
+      Type::UserDefined(id) | Type::Instance { id, .. } => {
+        id == trait_id || (self.implementations.get(id)).is_some_and(|traits| traits.contains(trait_id))
+      }
+      Type::Reference(inner) => self.implements(inner, trait_id),
+      Type::Parameter(parameter) => parameter.bounds.contains(trait_id),
+      Type::Unknown | Type::Never => true,
+      Type::Primitive(_) => false,
+    }
+  }
+
+  /// Whether a value of `actual` can go where an `expected` is expected. On
+  /// top of [`Type::accepts`], a reference to a trait accepts a reference to
+  /// any type implementing it.
+  fn accepts(&self, expected: &Type, actual: &Type) -> bool {
+    match (expected, actual) {
+      _ if expected.accepts(actual) => true,
+      (Type::Reference(expected), Type::Reference(actual)) => match expected.as_ref() {
+        Type::UserDefined(id) if self.is_trait(id) => self.implements(actual, id),
+        _ => false,
+      },
+      _ => false,
+    }
+  }
+
+  /// Runs `bind` with `Self` naming `owner`, as it does inside the body of a
+  /// type or an `impl`. The type parameters of a generic `owner` are in scope
+  /// under the names in `generics`, which an `impl` gives them.
+  fn with_self_type<T>(
+    &mut self,
+    owner: &TypeId,
+    generics: &[GenericParameter],
+    bind: impl FnOnce(&mut Self) -> T,
+  ) -> T {
+    let name = Keyword::_SelfType.lexeme();
+    let outer_types = self.types.insert(name.into(), owner.clone());
+    let outer_generics = self.generics.clone();
+    self.generics.insert(name.into(), self.self_type(owner));
+    for (parameter, alias) in self.type_generics(owner).into_iter().zip(generics) {
+      self
+        .generics
+        .insert(alias.name.literal.lexeme(), Type::Parameter(parameter));
+    }
+
+    let result = bind(self);
+    match outer_types {
+      Some(outer) => self.types.insert(name.into(), outer),
+      None => self.types.remove(name),
+    };
+    self.generics = outer_generics;
+    result
+  }
+
+  /// The type parameters of the type `id`, if it is generic.
+  fn type_generics(&self, id: &TypeId) -> Vec<Rc<TypeParameter>> {
+    match &self.definitions[id.index] {
+      TypeDefinition::Record { generics, .. } => generics.clone(),
+      TypeDefinition::Enum { .. } | TypeDefinition::Trait => Vec::new(),
+    }
+  }
+
+  /// The type `Self` is in the methods of `id`, which is given its own type
+  /// parameters if it is generic.
+  fn self_type(&self, id: &TypeId) -> Type {
+    let generics = self.type_generics(id);
+    if generics.is_empty() {
+      return Type::UserDefined(id.clone());
+    }
+    Type::Instance {
+      id: id.clone(),
+      arguments: (generics.into_iter())
+        .map(|parameter| TypeArgument::Type(Type::Parameter(parameter)))
+        .collect(),
+    }
+  }
+
+  /// Checks an `impl` of a generic type names one type parameter for each of
+  /// the type's, which take their bounds from the type.
+  fn check_impl_parameters(&mut self, owner: &TypeId, implementation: &Impl) {
+    let expected = self.type_generics(owner).len();
+    if implementation.generics.len() != expected {
+      self.errors.get_mut().push(InterpreterError::GenericArgumentCount {
+        span: implementation.target.span,
+        name: owner.name.to_string(),
+        expected,
+        found: implementation.generics.len(),
+      });
+    }
+
+    for parameter in &implementation.generics {
+      if !parameter.bounds.is_empty() || parameter.const_type.is_some() {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: parameter.span,
+          message: format!(
+            "`{}` of an `impl` takes its bounds from the declaration of `{}`",
+            parameter.name.literal.lexeme(),
+            owner.name
+          ),
+        });
+      }
+    }
+  }
+
+  fn declare_enum(&mut self, enumeration: &Enum) {
+    let mut variants = Vec::new();
+    for variant in &enumeration.variants {
+      let name = variant.literal.lexeme();
+      if variants.contains(&name) {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: variant.span,
+          message: format!("variant `{name}` is declared more than once"),
+        });
+        continue;
+      }
+      variants.push(name);
+    }
+
+    self.declare_type(enumeration.name.literal.lexeme(), TypeDefinition::Enum { variants });
+  }
+
+  fn record_fields(&mut self, record: &Record) -> Vec<FieldDefinition> {
+    let mut fields: Vec<FieldDefinition> = Vec::new();
+    for field in &record.fields {
+      let name = field.name.literal.lexeme();
+      if fields.iter().any(|other| other.name == name) {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: field.name.span,
+          message: format!("field `{name}` is declared more than once"),
+        });
+        continue;
+      }
+
+      fields.push(FieldDefinition {
+        name,
+        data_type: self.resolve_type(&field.data_type),
+        public: field.public,
+      });
+    }
+    fields
+  }
+
+  /// Puts a type in scope as `name`, giving the index of its definition.
+  fn declare_type(&mut self, name: String, definition: TypeDefinition) -> usize {
+    let index = self.definitions.len();
+    let id = TypeId {
+      index,
+      name: name.as_str().into(),
+    };
+    self.definitions.push(definition);
+    self.declared_in.push(self.module.clone());
+    self.types.insert(name, id);
+    index
+  }
+
+  fn signature(&mut self, function: &Function) -> Signature {
+    let generics = self.generic_parameters(&function.generics);
+    let outer_generics = self.generics.clone();
+    self.generics.extend(Self::generics_in_scope(&generics));
+
+    let mut names = HashSet::new();
+    let parameters = function
+      .parameters
+      .iter()
+      .map(|parameter| {
+        let name = parameter.name.literal.lexeme();
+        if !names.insert(name.clone()) {
+          self.errors.get_mut().push(InterpreterError::TypeError {
+            span: parameter.name.span,
+            message: format!("parameter `{name}` is declared more than once"),
+          });
+        }
+
+        BoundParameter {
+          name,
+          data_type: self.resolve_type(&parameter.data_type),
+        }
+      })
+      .collect();
+
+    let return_type = function
+      .return_type
+      .as_ref()
+      .map_or(Type::VOID, |return_type| self.resolve_type(return_type));
+    self.generics = outer_generics;
+
+    Signature {
+      generics,
+      parameters,
+      return_type,
+    }
+  }
+
+  fn generic_parameters(&mut self, parameters: &[GenericParameter]) -> Vec<Rc<TypeParameter>> {
+    let mut names = HashSet::new();
+    parameters
+      .iter()
+      .map(|parameter| {
+        let name = parameter.name.literal.lexeme();
+        if !names.insert(name.clone()) {
+          self.errors.get_mut().push(InterpreterError::TypeError {
+            span: parameter.name.span,
+            message: format!("type parameter `{name}` is declared more than once"),
+          });
+        }
+
+        let bounds = parameter
+          .bounds
+          .iter()
+          .filter_map(|bound| self.resolve_trait(bound))
+          .collect();
+        let const_type = parameter.const_type.as_ref().map(|const_type| {
+          let data_type = self.resolve_type(const_type);
+          if !data_type.is_integer() && data_type != Type::Unknown {
+            self.errors.get_mut().push(InterpreterError::TypeError {
+              span: const_type.span(),
+              message: format!("const parameter `{name}` has to be an integer, not a `{data_type}`"),
+            });
+          }
+          data_type
+        });
+        Rc::new(TypeParameter {
+          name,
+          bounds,
+          const_type,
+        })
+      })
+      .collect()
+  }
+
+  fn generics_in_scope(generics: &[Rc<TypeParameter>]) -> impl Iterator<Item = (String, Type)> + '_ {
+    (generics.iter()).map(|parameter| (parameter.name.clone(), Type::Parameter(parameter.clone())))
+  }
+
+  /// Binds the body of a function, which sees its parameters but none of the
+  /// variables around it. It is also given the values of its `constants`,
+  /// after its parameters.
+  fn bind_function(
+    &mut self,
+    function: Function,
+    signature: Signature,
+    constants: Vec<Rc<TypeParameter>>,
+  ) -> BoundFunction {
+    let constants: Vec<_> = (constants.iter())
+      .map(|parameter| BoundParameter {
+        name: Self::const_variable(parameter),
+        data_type: parameter.const_type.clone().unwrap_or(Type::Unknown),
+      })
+      .collect();
+    let parameters = (signature.parameters.iter().chain(&constants))
+      .map(|parameter| (parameter.name.clone(), parameter.data_type.clone()))
+      .collect();
+    let outer_scopes = std::mem::replace(&mut self.scopes, vec![parameters]);
+    let outer_integers = std::mem::take(&mut self.integers);
+    let outer_return_type = self.return_type.replace(signature.return_type.clone());
+    let outer_loops = std::mem::take(&mut self.loops);
+    let outer_generics = self.generics.clone();
+    self.generics.extend(Self::generics_in_scope(&signature.generics));
+
+    let tail = match &function.body {
+      Some(Expression::Block { statements, .. }) => statements.last().map(Statement::span),
+      _ => None,
+    };
+    // only the methods of a trait can go without a body
+    let body = match function.body {
+      Some(body) => self.bind_expression(body),
+      None => {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: function.span,
+          message: format!("function `{}` has no body", function.name.literal.lexeme()),
+        });
+        Self::error_expression(function.span)
+      }
+    };
+
+    self.scopes = outer_scopes;
+    self.integers = outer_integers;
+    self.return_type = outer_return_type;
+    self.loops = outer_loops;
+    self.generics = outer_generics;
+
+    if !self.accepts(&signature.return_type, &body.data_type()) {
+      self.errors.get_mut().push(InterpreterError::TypeError {
+        span: tail.unwrap_or(body.span()),
+        message: format!("expected `{}` but got `{}`", signature.return_type, body.data_type()),
+      });
+    }
+
+    BoundFunction {
+      name: function.name.literal.lexeme(),
+      parameters: signature.parameters.into_iter().chain(constants).collect(),
+      return_type: signature.return_type,
+      body,
+      span: function.span,
+    }
+  }
+
+  fn bind_statement(&mut self, statement: Statement) -> BoundStatement {
+    match statement {
+      Statement::Expression(expression) => BoundStatement::Expression(self.bind_expression(expression)),
+      Statement::Semicolon { expression, span } => BoundStatement::Semicolon {
+        expression: self.bind_expression(expression),
+        span,
+      },
+      Statement::Declaration {
+        name,
+        data_type,
+        initializer,
+        span,
+        ..
+      } => {
+        let declared = data_type.map(|data_type| self.resolve_type(&data_type));
+        // bound before the name is declared, so `x := x + 1` reads an outer `x`
+        let initializer = initializer.map(|initializer| self.bind_expected(initializer, declared.as_ref()));
+        let data_type = match declared {
+          Some(declared) => {
+            if let Some(initializer) = &initializer {
+              match self.accepts(&declared, &initializer.data_type()) {
+                true => self.check_range(&declared, initializer),
+                false => self.errors.get_mut().push(InterpreterError::TypeError {
+                  span: initializer.span(),
+                  message: format!("expected `{declared}` but got `{}`", initializer.data_type()),
+                }),
+              }
+            }
+            declared
+          }
+          None => initializer.as_ref().map_or(Type::Unknown, BoundExpression::data_type),
+        };
+
+        let name = name.literal.lexeme();
+        self
+          .scopes
+          .last_mut()
+          .expect("there is always an outermost scope")
+          .insert(name.clone(), data_type.clone());
+        let key = (self.scopes.len() - 1, name.clone());
+        match initializer
+          .as_ref()
+          .and_then(|initializer| self.known_integer(initializer))
+        {
+          Some(integer) => self.integers.insert(key, integer),
+          None => self.integers.remove(&key),
+        };
+        BoundStatement::Declaration {
+          name,
+          data_type,
+          initializer,
+          span,
+        }
+      }
+    }
+  }
+
+  fn bind_expression(&mut self, syntax: Expression) -> BoundExpression {
+    match syntax {
+      Expression::Literal { token } => self.bind_literal(token, false),
+      Expression::Variable { name } => match self.constant(&name.literal.lexeme()) {
+        Some(constant) => constant,
+        None => match self.const_parameter(&name.literal.lexeme()) {
+          Some((variable, data_type)) => BoundExpression::Variable {
+            data_type,
+            span: name.span,
+            name: variable,
+          },
+          None => BoundExpression::Variable {
+            data_type: self.variable_type(&name),
+            span: name.span,
+            name: name.literal.lexeme(),
+          },
+        },
+      },
+      Expression::Unary {
+        operator,
+        operand,
+        span,
+      } => {
+        let operand = Box::new(match *operand {
+          Expression::Literal { token } => self.bind_literal(token, operator.symbol == Symbol::Minus),
+          operand => self.bind_expression(operand),
+        });
         let bound_operator = match BoundUnaryOperator::try_from(operator.clone()) {
           Ok(value) => value,
           Err(error) => {
@@ -35,9 +1407,10 @@ impl Binder {
           }
         };
         BoundExpression::Unary {
-          data_type: operand.data_type(),
-          position: operator.position,
+          data_type: self.unary_type(&operator, &bound_operator, operand.data_type()),
+          span,
           operator: bound_operator,
+          operator_span: operator.span,
           operand,
         }
       }
@@ -45,9 +1418,10 @@ impl Binder {
         operator,
         left_operand,
         right_operand,
+        span,
       } => {
-        let left_operand = Box::new(self.bind(*left_operand));
-        let right_operand = Box::new(self.bind(*right_operand));
+        let left_operand = Box::new(self.bind_expression(*left_operand));
+        let right_operand = Box::new(self.bind_expression(*right_operand));
         let bound_operator = match BoundBinaryOperator::try_from(operator.clone()) {
           Ok(value) => value,
           Err(error) => {
@@ -56,20 +1430,1705 @@ impl Binder {
           }
         };
         BoundExpression::Binary {
-          data_type: left_operand.data_type(),
-          position: operator.position,
+          data_type: self.binary_type(&operator, &bound_operator, left_operand.data_type(), right_operand.data_type()),
+          span,
           operator: bound_operator,
+          operator_span: operator.span,
           left_operand,
           right_operand,
         }
       }
-      Expression::Grouping { operand } => {
-        let operand = Box::new(self.bind(*operand));
+      Expression::Grouping { operand, span } => {
+        let operand = Box::new(self.bind_expression(*operand));
         BoundExpression::Grouping {
           data_type: operand.data_type(),
+          span,
           operand,
         }
       }
-    }
+      // every value has a string form, so any expression can be interpolated
+      Expression::Interpolation { parts, span } => BoundExpression::Interpolation {
+        data_type: Type::STRING,
+        span,
+        parts: parts
+          .into_iter()
+          .map(|part| match part {
+            InterpolationPart::Text(text) => BoundInterpolationPart::Text(text),
+            InterpolationPart::Expression(expression) => {
+              BoundInterpolationPart::Expression(self.bind_expression(expression))
+            }
+          })
+          .collect(),
+      },
+      Expression::Member { object, member, span } => self.bind_member(*object, member, span),
+      Expression::Index { object, index, span } => self.bind_index(*object, *index, span),
+      Expression::Call {
+        callee,
+        arguments,
+        span,
+      } => self.bind_call(*callee, arguments, span),
+      Expression::Return { value, span } => {
+        let return_type = self.return_type.clone();
+        let value = value.map(|value| Box::new(self.bind_expected(*value, return_type.as_ref())));
+        let data_type = value.as_ref().map_or(Type::VOID, |value| value.data_type());
+        match &self.return_type {
+          None => self.errors.get_mut().push(InterpreterError::TypeError {
+            span,
+            message: "`return` outside of a function".into(),
+          }),
+          Some(return_type) if !self.accepts(return_type, &data_type) => {
+            let message = format!("expected `{return_type}` but got `{data_type}`");
+            self.errors.get_mut().push(InterpreterError::TypeError {
+              span: value.as_ref().map_or(span, |value| value.span()),
+              message,
+            });
+          }
+          Some(_) => {}
+        }
+
+        BoundExpression::Return {
+          data_type: Type::Never,
+          span,
+          value,
+        }
+      }
+      Expression::If {
+        condition,
+        then_branch,
+        else_branch,
+        span,
+      } => {
+        let condition = Box::new(self.bind_condition(*condition));
+        let then_branch = Box::new(self.bind_expression(*then_branch));
+        let else_branch = else_branch.map(|branch| Box::new(self.bind_expression(*branch)));
+        let data_type = match &else_branch {
+          Some(else_branch) => Type::join(&then_branch.data_type(), &else_branch.data_type()).unwrap_or_else(|| {
+            self.errors.get_mut().push(InterpreterError::TypeError {
+              span: else_branch.span(),
+              message: format!(
+                "`if` and `else` have different types: `{}` and `{}`",
+                then_branch.data_type(),
+                else_branch.data_type()
+              ),
+            });
+            Type::Unknown
+          }),
+          None => Type::VOID,
+        };
+
+        BoundExpression::If {
+          data_type,
+          span,
+          condition,
+          then_branch,
+          else_branch,
+        }
+      }
+      Expression::While { condition, body, span } => {
+        let condition = Box::new(self.bind_condition(*condition));
+        let (body, _) = self.bind_loop_body(*body, false);
+        BoundExpression::While {
+          data_type: Type::VOID,
+          span,
+          condition,
+          body: Box::new(body),
+        }
+      }
+      Expression::Loop { body, span } => {
+        let (body, break_type) = self.bind_loop_body(*body, true);
+        BoundExpression::Loop {
+          // a `loop` that is never broken out of never finishes
+          data_type: break_type.unwrap_or(Type::Never),
+          span,
+          body: Box::new(body),
+        }
+      }
+      Expression::For {
+        variable,
+        iterable,
+        body,
+        span,
+      } => {
+        let (iterable, element_type) = self.bind_iterable(*iterable);
+        let variable = variable.literal.lexeme();
+        self.scopes.push(HashMap::from([(variable.clone(), element_type)]));
+        let (body, _) = self.bind_loop_body(*body, false);
+        self.pop_scope();
+
+        BoundExpression::For {
+          data_type: Type::VOID,
+          span,
+          variable,
+          iterable,
+          body: Box::new(body),
+        }
+      }
+      Expression::Range { span, .. } => {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span,
+          message: "a range can only be gone through by a `for` loop".into(),
+        });
+        Self::error_expression(span)
+      }
+      Expression::Break { value, span } => {
+        let value = value.map(|value| Box::new(self.bind_expression(*value)));
+        self.bind_break(value.as_deref(), span);
+        BoundExpression::Break {
+          data_type: Type::Never,
+          span,
+          value,
+        }
+      }
+      Expression::Continue { span } => {
+        if self.loops.is_empty() {
+          self.errors.get_mut().push(InterpreterError::TypeError {
+            span,
+            message: "`continue` outside of a loop".into(),
+          });
+        }
+        BoundExpression::Continue {
+          data_type: Type::Never,
+          span,
+        }
+      }
+      Expression::Record {
+        name,
+        arguments,
+        fields,
+        span,
+      } => self.bind_record(name, arguments, fields, span, None),
+      Expression::Generic { span, .. } => {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span,
+          message: "generic arguments can only be given to a function being called or to a type".into(),
+        });
+        Self::error_expression(span)
+      }
+      Expression::Assign {
+        target,
+        operator,
+        value,
+        span,
+      } => self.bind_assign(*target, operator, *value, span),
+      Expression::Block { statements, span } => {
+        self.scopes.push(HashMap::new());
+        let statements: Vec<_> = statements
+          .into_iter()
+          .map(|statement| self.bind_statement(statement))
+          .collect();
+        self.pop_scope();
+        BoundExpression::Block {
+          data_type: BoundStatement::sequence_type(&statements),
+          span,
+          statements,
+        }
+      }
+    }
+  }
+
+  /// Binds `object.member`, which is either the variant of an enum in
+  /// `Enum.Variant`, a constant of a module in `core.SUCCESS`, or the field of
+  /// a record.
+  fn bind_member(&mut self, object: Expression, member: LiteralToken, span: Span) -> BoundExpression {
+    if let Some(module) = self.module_named(&object) {
+      let name = member.literal.lexeme();
+      return match self.modules[&module].exports.constants.get(&name) {
+        Some(value) => value.clone(),
+        None => {
+          self.not_a_value(&module, &name, member.span);
+          Self::error_expression(span)
+        }
+      };
+    }
+    if let Some(id) = self.type_path(&object)
+      && let TypeDefinition::Enum { variants } = &self.definitions[id.index]
+    {
+      let variant = member.literal.lexeme();
+      if !variants.contains(&variant) {
+        let variants: Vec<_> = variants.iter().map(|variant| format!("`{variant}`")).collect();
+        self.errors.get_mut().push(InterpreterError::UnknownVariant {
+          span: member.span,
+          enumeration: id.name.to_string(),
+          variant,
+          variants: variants.join(", "),
+        });
+        return Self::error_expression(span);
+      }
+
+      return BoundExpression::Literal {
+        data_type: Type::UserDefined(id.clone()),
+        span,
+        value: Value::Enum {
+          data_type: id,
+          variant: variant.into(),
+        },
+      };
+    }
+
+    let object = self.bind_expression(object);
+    match self.field(object.data_type().dereferenced(), &member) {
+      Some((index, data_type)) => BoundExpression::Field {
+        data_type,
+        span,
+        object: Box::new(object),
+        index,
+      },
+      None => Self::error_expression(span),
+    }
+  }
+
+  /// Binds `object[index]`. Only a `string` can be indexed so far, giving the
+  /// character at `index`.
+  fn bind_index(&mut self, object: Expression, index: Expression, span: Span) -> BoundExpression {
+    let object = self.bind_expression(object);
+    let index = self.bind_expression(index);
+    let data_type = match object.data_type().dereferenced() {
+      Type::Unknown => Type::Unknown,
+      data_type if *data_type == Type::STRING => Type::CHAR,
+      data_type => {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: object.span(),
+          message: format!("`{data_type}` cannot be indexed"),
+        });
+        Type::Unknown
+      }
+    };
+    match index.data_type() {
+      Type::Unknown => {}
+      index_type if index_type.is_integer() => {}
+      index_type => self.errors.get_mut().push(InterpreterError::TypeError {
+        span: index.span(),
+        message: format!("expected an integer index but got `{index_type}`"),
+      }),
+    }
+    if data_type == Type::Unknown {
+      return Self::error_expression(span);
+    }
+
+    BoundExpression::Index {
+      data_type,
+      span,
+      object: Box::new(object),
+      index: Box::new(index),
+    }
+  }
+
+  /// The index and type of the field `member` of a `data_type`, reporting an
+  /// error if it has no such field.
+  fn field(&mut self, data_type: &Type, member: &LiteralToken) -> Option<(usize, Type)> {
+    let name = member.literal.lexeme();
+    let error = match data_type {
+      Type::Unknown => return None,
+      Type::UserDefined(id) | Type::Instance { id, .. } => match self.definitions[id.index].field(&name) {
+        Some((index, field)) => {
+          let data_type = field.data_type.substitute(&self.instance_bindings(data_type));
+          if !field.public {
+            self.check_private(id, &format!("field `{name}`"), member.span);
+          }
+          return Some((index, data_type));
+        }
+        None if matches!(self.definitions[id.index], TypeDefinition::Record { .. }) => InterpreterError::UnknownField {
+          span: member.span,
+          data_type: data_type.to_string(),
+          field: name,
+        },
+        None => InterpreterError::TypeError {
+          span: member.span,
+          message: format!("`{data_type}` has no member `{name}`"),
+        },
+      },
+      _ => InterpreterError::TypeError {
+        span: member.span,
+        message: format!("`{data_type}` has no member `{name}`"),
+      },
+    };
+    self.errors.get_mut().push(error);
+    None
+  }
+
+  /// Reports the use of what is private to `owner`, such as a field, outside
+  /// the module declaring it.
+  fn check_private(&mut self, owner: &TypeId, what: &str, span: Span) {
+    if self.declared_in[owner.index] != self.module {
+      self.errors.get_mut().push(InterpreterError::TypeError {
+        span,
+        message: format!("{what} of `{}` is private", owner.name),
+      });
+    }
+  }
+
+  /// Binds an expression whose value goes where an `expected` is expected,
+  /// which is what gives a record literal without a type name its type, and
+  /// one of a generic type its arguments.
+  fn bind_expected(&mut self, expression: Expression, expected: Option<&Type>) -> BoundExpression {
+    match expression {
+      Expression::Record {
+        name,
+        arguments,
+        fields,
+        span,
+      } => self.bind_record(name, arguments, fields, span, expected),
+      // `{ name = value }` parses as a block giving `name` a value, which
+      // would have no value to give where a record is expected
+      Expression::Block { statements, span } if self.expects_record(expected) && Self::is_one_field(&statements) => {
+        let Some(Statement::Expression(Expression::Assign {
+          target,
+          value,
+          span: field_span,
+          ..
+        })) = statements.into_iter().next()
+        else {
+          unreachable!("the block was just checked")
+        };
+        let Expression::Variable { name } = *target else {
+          unreachable!("the block was just checked")
+        };
+        let field = FieldInitializer {
+          name,
+          value: *value,
+          span: field_span,
+        };
+        self.bind_record(None, Vec::new(), vec![field], span, expected)
+      }
+      expression => self.bind_expression(expression),
+    }
+  }
+
+  /// Whether a value of `expected` is a record.
+  fn expects_record(&self, expected: Option<&Type>) -> bool {
+    let id = expected.and_then(Type::declared);
+    id.is_some_and(|id| matches!(self.definitions[id.index], TypeDefinition::Record { .. }))
+  }
+
+  /// Whether a block of `statements` is `{ name = value }`, which is also how
+  /// a record literal with one field is written.
+  fn is_one_field(statements: &[Statement]) -> bool {
+    match statements {
+      [Statement::Expression(Expression::Assign {
+        target, operator: None, ..
+      })] => matches!(**target, Expression::Variable { .. }),
+      _ => false,
+    }
+  }
+
+  /// Binds a record literal, which must give every field of its type a value
+  /// exactly once. The arguments of a generic type can be left out for the
+  /// values of the fields to give them.
+  fn bind_record(
+    &mut self,
+    name: Option<LiteralToken>,
+    arguments: Vec<GenericArgument>,
+    fields: Vec<FieldInitializer>,
+    span: Span,
+    expected: Option<&Type>,
+  ) -> BoundExpression {
+    let data_type = match (&name, expected) {
+      (Some(name), _) => {
+        let lexeme = name.literal.lexeme();
+        let found = self.type_named(&lexeme);
+        match (found, expected) {
+          (Some(id), Some(expected @ Type::Instance { id: expected_id, .. }))
+            if arguments.is_empty() && *expected_id == id =>
+          {
+            Some(expected.clone())
+          }
+          (Some(id), _) if arguments.is_empty() => Some(Type::UserDefined(id)),
+          (Some(id), _) => Some(self.instance(id, &arguments, name.span)),
+          (None, _) => {
+            self.errors.get_mut().push(InterpreterError::TypeError {
+              span: name.span,
+              message: format!("cannot find type `{lexeme}` in this scope"),
+            });
+            None
+          }
+        }
+      }
+      (None, Some(Type::Unknown)) => None,
+      (None, Some(expected)) => Some(expected.clone()),
+      (None, None) => {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span,
+          message: "cannot tell the type of this record, write it as `Type { ... }`".into(),
+        });
+        None
+      }
+    };
+
+    let definitions = match &data_type {
+      Some(Type::Unknown) | None => None,
+      Some(data_type) => match data_type.declared().map(|id| (id, &self.definitions[id.index])) {
+        Some((id, TypeDefinition::Record { generics, fields })) => {
+          Some((id.clone(), generics.clone(), fields.clone(), self.instance_bindings(data_type)))
+        }
+        _ => {
+          self.errors.get_mut().push(InterpreterError::TypeError {
+            span,
+            message: format!("expected `{data_type}` but got a record"),
+          });
+          None
+        }
+      },
+    };
+    let Some((id, generics, definitions, mut bindings)) = definitions else {
+      for field in fields {
+        self.bind_expression(field.value);
+      }
+      return Self::error_expression(span);
+    };
+    let given: HashSet<_> = bindings.keys().cloned().collect();
+
+    let mut bound: Vec<BoundFieldInitializer> = Vec::new();
+    for field in fields {
+      let name = field.name.literal.lexeme();
+      let Some(index) = definitions.iter().position(|definition| definition.name == name) else {
+        self.errors.get_mut().push(InterpreterError::UnknownField {
+          span: field.name.span,
+          data_type: id.name.to_string(),
+          field: name,
+        });
+        self.bind_expression(field.value);
+        continue;
+      };
+
+      if !definitions[index].public {
+        self.check_private(&id, &format!("field `{name}`"), field.name.span);
+      }
+      let declared = &definitions[index].data_type;
+      let value = self.bind_expected(field.value, Some(&declared.substitute(&bindings)));
+      Self::infer(declared, &value.data_type(), &mut bindings);
+      let data_type = declared.substitute(&bindings);
+      if bound.iter().any(|other| other.index == index) {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: field.name.span,
+          message: format!("field `{name}` is given a value more than once"),
+        });
+        continue;
+      }
+      if !self.accepts(&data_type, &value.data_type()) {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: value.span(),
+          message: format!("expected `{data_type}` but got `{}`", value.data_type()),
+        });
+      }
+      bound.push(BoundFieldInitializer {
+        index,
+        name,
+        data_type,
+        value,
+      });
+    }
+
+    let missing: Vec<_> = definitions
+      .iter()
+      .enumerate()
+      .filter(|(index, _)| !bound.iter().any(|field| field.index == *index))
+      .map(|(_, definition)| format!("`{}`", definition.name))
+      .collect();
+    if !missing.is_empty() {
+      self.errors.get_mut().push(InterpreterError::MissingFields {
+        span,
+        data_type: id.name.to_string(),
+        fields: missing.join(", "),
+      });
+    }
+
+    let data_type = match generics.is_empty() {
+      true => Type::UserDefined(id),
+      false => {
+        let arguments = self.inferred_arguments(&id.name, &generics, &given, &mut bindings, span);
+        Type::Instance { id, arguments }
+      }
+    };
+    BoundExpression::Record {
+      data_type,
+      span,
+      fields: bound,
+    }
+  }
+
+  /// What the type parameters of a generic type are given by `data_type`, an
+  /// instance of it.
+  fn instance_bindings(&self, data_type: &Type) -> HashMap<String, TypeArgument> {
+    let Type::Instance { id, arguments } = data_type else {
+      return HashMap::new();
+    };
+    (self.type_generics(id).iter())
+      .map(|parameter| parameter.name.clone())
+      .zip(arguments.iter().cloned())
+      .collect()
+  }
+
+  fn bind_assign(
+    &mut self,
+    target: Expression,
+    operator: Option<SymbolToken>,
+    value: Expression,
+    span: Span,
+  ) -> BoundExpression {
+    let target = self.bind_expression(target);
+    let target_type = target.data_type();
+    let Some(place) = Self::place(&target) else {
+      // a target that couldn't be bound has already been reported
+      if target_type != Type::Unknown {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: target.span(),
+          message: "cannot assign to this expression".into(),
+        });
+      }
+      self.bind_expression(value);
+      return Self::error_expression(span);
+    };
+    self.check_mutable(&place, target.span());
+
+    let value = match operator {
+      Some(operator) => self.bind_compound(target, operator, value),
+      None => self.bind_expected(value, Some(&target_type)),
+    };
+    match self.accepts(&target_type, &value.data_type()) {
+      true => self.check_range(&target_type, &value),
+      false => self.errors.get_mut().push(InterpreterError::TypeError {
+        span: value.span(),
+        message: format!("expected `{target_type}` but got `{}`", value.data_type()),
+      }),
+    }
+    if place.path.is_empty()
+      && let Some(index) = self.scope_of(&place.variable)
+    {
+      self.integers.remove(&(index, place.variable.clone()));
+    }
+
+    BoundExpression::Assign {
+      data_type: Type::VOID,
+      span,
+      target: place,
+      target_type,
+      value: Box::new(value),
+    }
+  }
+
+  /// Binds the value a compound assignment such as `target += value` gives
+  /// `target`, which is `target + value`.
+  fn bind_compound(&mut self, target: BoundExpression, operator: SymbolToken, value: Expression) -> BoundExpression {
+    let value = self.bind_expression(value);
+    let binary = SymbolToken {
+      symbol: (operator.symbol.compound_operator()).expect("the parser only gives assignment operators"),
+      ..operator.clone()
+    };
+    let Ok(bound_operator) = BoundBinaryOperator::try_from(binary) else {
+      self.errors.get_mut().push(InterpreterError::UnknownOperator {
+        span: operator.span,
+        operator: operator.symbol.lexeme().into(),
+      });
+      return Self::error_expression(value.span());
+    };
+    BoundExpression::Binary {
+      data_type: self.binary_type(&operator, &bound_operator, target.data_type(), value.data_type()),
+      span: target.span().to(value.span()),
+      operator: bound_operator,
+      operator_span: operator.span,
+      left_operand: Box::new(target),
+      right_operand: Box::new(value),
+    }
+  }
+
+  /// What changes when `target` is assigned to: a variable, or a field or
+  /// an element of one. Gives `None` if `target` isn't something that can be
+  /// assigned to.
+  fn place(target: &BoundExpression) -> Option<BoundPlace> {
+    match target {
+      BoundExpression::Variable { name, .. } => Some(BoundPlace {
+        variable: name.clone(),
+        path: Vec::new(),
+      }),
+      BoundExpression::Field { object, index, .. } => {
+        let mut place = Self::place(object)?;
+        place.path.push(BoundPlaceStep::Field(*index));
+        Some(place)
+      }
+      BoundExpression::Index { object, index, .. } => {
+        let mut place = Self::place(object)?;
+        place.path.push(BoundPlaceStep::Index((**index).clone()));
+        Some(place)
+      }
+      _ => None,
+    }
+  }
+
+  /// Reports changing `place` if it is the `self` of a method taking `&self`,
+  /// or a field of what a reference refers to.
+  fn check_mutable(&mut self, place: &BoundPlace, span: Span) {
+    let root = self.scopes.iter().rev().find_map(|scope| scope.get(&place.variable));
+    let message = if place.variable == Keyword::_Self.lexeme() && self.receiver == Some(ReceiverKind::Reference) {
+      "cannot change `self` in a method taking `&self`".to_string()
+    } else if let Some(name) = place.variable.strip_prefix(Self::CONST_PREFIX) {
+      format!("cannot change the const parameter `{name}`")
+    } else if !place.path.is_empty() && matches!(root, Some(Type::Reference(_))) {
+      format!("cannot change `{}` through a `&` reference", place.variable)
+    } else {
+      return;
+    };
+    self
+      .errors
+      .get_mut()
+      .push(InterpreterError::TypeError { span, message });
+  }
+
+  /// The variable a function is given the value of its const `parameter` in.
+  fn const_variable(parameter: &TypeParameter) -> String {
+    format!("{}{}", Self::CONST_PREFIX, parameter.name)
+  }
+
+  /// The variable holding the value of the const parameter `name` and its
+  /// type, if `name` names one whose value the function being bound is given.
+  fn const_parameter(&self, name: &str) -> Option<(String, Type)> {
+    if self.scopes.iter().any(|scope| scope.contains_key(name)) {
+      return None;
+    }
+    let Some(Type::Parameter(parameter)) = self.generics.get(name) else {
+      return None;
+    };
+    let variable = Self::const_variable(parameter);
+    (self.scopes.iter().rev()).find_map(|scope| Some((variable.clone(), scope.get(&variable)?.clone())))
+  }
+
+  fn unreadable_const(name: &str) -> String {
+    format!("const parameter `{name}` cannot be read in a method implementing a trait, as the trait doesn't know it")
+  }
+
+  /// The const parameters a function or method is given the values of when
+  /// it is called, after the arguments written in the call: those of the
+  /// type `owner` it is a method of, then those of its own `generics`. A
+  /// method called `name` implementing a trait can be called through the
+  /// trait, which doesn't know its type's, so it isn't given those.
+  fn const_parameters(
+    &self,
+    owner: Option<&TypeId>,
+    name: &str,
+    generics: &[Rc<TypeParameter>],
+  ) -> Vec<Rc<TypeParameter>> {
+    let owned = match owner {
+      Some(owner) if !self.implements_trait_method(owner, name) => self.type_generics(owner),
+      _ => Vec::new(),
+    };
+    (owned.into_iter().chain(generics.iter().cloned()))
+      .filter(|parameter| parameter.const_type.is_some())
+      .collect()
+  }
+
+  /// The values a call gives the const `parameters` of what it calls, from
+  /// what `bindings` gives them.
+  fn const_arguments(
+    &mut self,
+    parameters: &[Rc<TypeParameter>],
+    bindings: &HashMap<String, TypeArgument>,
+    span: Span,
+  ) -> Vec<BoundExpression> {
+    let mut arguments = Vec::new();
+    for parameter in parameters {
+      let data_type = parameter.const_type.clone().unwrap_or(Type::Unknown);
+      let argument = match bindings.get(&parameter.name) {
+        Some(TypeArgument::Const(value)) => BoundExpression::Literal {
+          data_type,
+          span,
+          value: Value::Int(*value),
+        },
+        // a const parameter of the caller's passed on
+        Some(TypeArgument::Type(Type::Parameter(outer))) if outer.const_type.is_some() => {
+          let name = Self::const_variable(outer);
+          match self.scopes.iter().any(|scope| scope.contains_key(&name)) {
+            true => BoundExpression::Variable { data_type, span, name },
+            false => {
+              self.errors.get_mut().push(InterpreterError::TypeError {
+                span,
+                message: Self::unreadable_const(&outer.name),
+              });
+              Self::error_expression(span)
+            }
+          }
+        }
+        _ => Self::error_expression(span),
+      };
+      arguments.push(argument);
+    }
+    arguments
+  }
+
+  /// Whether `name` is a method `owner` has for a trait it implements.
+  fn implements_trait_method(&self, owner: &TypeId, name: &str) -> bool {
+    (self.implementations.get(owner).into_iter().flatten())
+      .any(|trait_id| (self.traits[trait_id].methods.iter()).any(|declared| declared.name == name))
+  }
+
+  /// The type of the variable `name`, reporting an error if there is none.
+  fn variable_type(&mut self, name: &LiteralToken) -> Type {
+    let lexeme = name.literal.lexeme();
+    if let Some(data_type) = self.scopes.iter().rev().find_map(|scope| scope.get(&lexeme)) {
+      return data_type.clone();
+    }
+
+    let error = match (self.generics.get(&lexeme), self.types.get(&lexeme)) {
+      (Some(Type::Parameter(parameter)), _) if parameter.const_type.is_some() => InterpreterError::TypeError {
+        span: name.span,
+        message: Self::unreadable_const(&lexeme),
+      },
+      (Some(_), _) | (None, Some(_)) => InterpreterError::TypeError {
+        span: name.span,
+        message: format!("expected a value but found the type `{lexeme}`"),
+      },
+      (None, None) if self.module_names.contains_key(&lexeme) => InterpreterError::TypeError {
+        span: name.span,
+        message: format!("expected a value but found the module `{lexeme}`"),
+      },
+      (None, None) => InterpreterError::UndeclaredVariable {
+        span: name.span,
+        name: lexeme,
+      },
+    };
+    self.errors.get_mut().push(error);
+    Type::Unknown
+  }
+
+  /// The type called `name`, unless a variable of that name hides it.
+  fn type_named(&self, name: &str) -> Option<TypeId> {
+    if self.scopes.iter().any(|scope| scope.contains_key(name)) {
+      return None;
+    }
+    self.types.get(name).cloned()
+  }
+
+  /// The path of the module `expression` names, as in `core` or
+  /// `core.array`, unless a variable hides it.
+  fn module_named(&self, expression: &Expression) -> Option<String> {
+    match expression {
+      Expression::Variable { name } => {
+        let name = name.literal.lexeme();
+        if self.scopes.iter().any(|scope| scope.contains_key(&name)) {
+          return None;
+        }
+        self.module_names.get(&name).cloned()
+      }
+      Expression::Member { object, member, .. } => {
+        let module = self.module_named(object)?;
+        self.modules[&module]
+          .exports
+          .modules
+          .get(&member.literal.lexeme())
+          .cloned()
+      }
+      _ => None,
+    }
+  }
+
+  /// The path of the module reached through `names`, as in `core.array`,
+  /// whose first name is one in scope.
+  fn module_at(&self, names: &[String]) -> Option<String> {
+    let (first, rest) = names.split_first()?;
+    let mut module = self.module_names.get(first)?.clone();
+    for name in rest {
+      module = self.modules[&module].exports.modules.get(name)?.clone();
+    }
+    Some(module)
+  }
+
+  /// The type `expression` names, as in `Color` or `core.Color`.
+  fn type_path(&self, expression: &Expression) -> Option<TypeId> {
+    match expression {
+      Expression::Variable { name } => self.type_named(&name.literal.lexeme()),
+      Expression::Member { object, member, .. } => {
+        let module = self.module_named(object)?;
+        self.modules[&module]
+          .exports
+          .types
+          .get(&member.literal.lexeme())
+          .cloned()
+      }
+      _ => None,
+    }
+  }
+
+  /// Reports that `name` of the module at `module` isn't a value.
+  fn not_a_value(&mut self, module: &str, name: &str, span: Span) {
+    let exports = &self.modules[module].exports;
+    let message = if exports.types.contains_key(name) {
+      format!("expected a value but found the type `{module}.{name}`")
+    } else if exports.functions.contains_key(name) {
+      format!("`{module}.{name}` is a function, so it has to be called")
+    } else if exports.modules.contains_key(name) {
+      format!("expected a value but found the module `{module}.{name}`")
+    } else {
+      return self.missing_export(module, name, span);
+    };
+    self
+      .errors
+      .get_mut()
+      .push(InterpreterError::TypeError { span, message });
+  }
+
+  fn bind_condition(&mut self, condition: Expression) -> BoundExpression {
+    let condition = self.bind_expression(condition);
+    if !Type::BOOL.accepts(&condition.data_type()) {
+      self.errors.get_mut().push(InterpreterError::TypeError {
+        span: condition.span(),
+        message: format!("expected `bool` but got `{}`", condition.data_type()),
+      });
+    }
+    condition
+  }
+
+  /// Binds the body of a loop, giving the type of the values its `break`s
+  /// give it, if any do.
+  fn bind_loop_body(&mut self, body: Expression, takes_value: bool) -> (BoundExpression, Option<Type>) {
+    self.loops.push(Loop {
+      takes_value,
+      break_type: None,
+    });
+    let body = self.bind_expression(body);
+    let break_type = self.loops.pop().and_then(|context| context.break_type);
+    (body, break_type)
+  }
+
+  fn bind_break(&mut self, value: Option<&BoundExpression>, span: Span) {
+    let Some(context) = self.loops.last_mut() else {
+      self.errors.get_mut().push(InterpreterError::TypeError {
+        span,
+        message: "`break` outside of a loop".into(),
+      });
+      return;
+    };
+
+    if let Some(value) = value
+      && !context.takes_value
+    {
+      self.errors.get_mut().push(InterpreterError::TypeError {
+        span: value.span(),
+        message: "only a `loop` can be given a value by `break`".into(),
+      });
+      return;
+    }
+
+    let data_type = value.map_or(Type::VOID, BoundExpression::data_type);
+    match &context.break_type {
+      None => context.break_type = Some(data_type),
+      Some(previous) => match Type::join(previous, &data_type) {
+        Some(joined) => context.break_type = Some(joined),
+        None => {
+          let message = format!("expected `{previous}` but got `{data_type}`");
+          self.errors.get_mut().push(InterpreterError::TypeError {
+            span: value.map_or(span, BoundExpression::span),
+            message,
+          });
+        }
+      },
+    }
+  }
+
+  /// Binds what a `for` loop goes through, along with the type of its
+  /// elements: integer ranges and strings.
+  fn bind_iterable(&mut self, iterable: Expression) -> (BoundIterable, Type) {
+    if let Expression::Range {
+      start,
+      end,
+      inclusive,
+      span,
+    } = iterable
+    {
+      let start = Box::new(self.bind_expression(*start));
+      let end = Box::new(self.bind_expression(*end));
+      let element_type = match (start.data_type(), end.data_type()) {
+        (Type::Unknown, _) | (_, Type::Unknown) => Type::Unknown,
+        (start, end) => Type::promote(&start, &end).filter(Type::is_integer).unwrap_or_else(|| {
+          self.errors.get_mut().push(InterpreterError::TypeError {
+            span,
+            message: format!("expected a range of integers but got `{start}` and `{end}`"),
+          });
+          Type::Unknown
+        }),
+      };
+      return (BoundIterable::Range { start, end, inclusive }, element_type);
+    }
+
+    let iterable = self.bind_expression(iterable);
+    let element_type = match iterable.data_type() {
+      Type::Unknown => Type::Unknown,
+      data_type if data_type == Type::STRING => Type::CHAR,
+      data_type => {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: iterable.span(),
+          message: format!("`{data_type}` cannot be gone through by a `for` loop"),
+        });
+        Type::Unknown
+      }
+    };
+    (BoundIterable::String(Box::new(iterable)), element_type)
+  }
+
+  /// Binds a call of a function by name, checking its arguments against the
+  /// function's parameters.
+  fn bind_call(&mut self, callee: Expression, arguments: Vec<Expression>, span: Span) -> BoundExpression {
+    let (callee, explicit) = match callee {
+      Expression::Generic { target, arguments, .. } => (*target, arguments),
+      callee => (callee, Vec::new()),
+    };
+    let symbol = match callee {
+      Expression::Member { object, member, .. } => match self.module_named(&object) {
+        Some(module) => {
+          let name = member.literal.lexeme();
+          match self.modules[&module].exports.functions.get(&name).cloned() {
+            Some(symbol) => Some((format!("{module}.{name}"), symbol)),
+            None => {
+              match self.modules[&module].exports.constants.contains_key(&name) {
+                true => self.errors.get_mut().push(InterpreterError::TypeError {
+                  span: member.span,
+                  message: format!("`{module}.{name}` is a constant, not a function"),
+                }),
+                false => self.not_a_value(&module, &name, member.span),
+              }
+              None
+            }
+          }
+        }
+        None => return self.bind_method_call(*object, member, explicit, arguments, span),
+      },
+      Expression::Variable { name } => {
+        let lexeme = name.literal.lexeme();
+        match self.functions.get(&lexeme).cloned() {
+          Some(symbol) => Some((lexeme, symbol)),
+          None => {
+            let error = if self.scopes.iter().any(|scope| scope.contains_key(&lexeme)) {
+              InterpreterError::TypeError {
+                span: name.span,
+                message: format!("`{lexeme}` is a variable, not a function"),
+              }
+            } else {
+              InterpreterError::UndeclaredFunction {
+                span: name.span,
+                name: lexeme,
+              }
+            };
+            self.errors.get_mut().push(error);
+            None
+          }
+        }
+      }
+      callee => {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: callee.span(),
+          message: "expected a function to call".into(),
+        });
+        None
+      }
+    };
+    let Some((name, FunctionSymbol { path, signature })) = symbol else {
+      self.bind_arguments(arguments, &[]);
+      return Self::error_expression(span);
+    };
+
+    let bindings = self.explicit_bindings(&name, &signature.generics, &explicit, span);
+    let constants = self.const_parameters(None, "", &signature.generics);
+    let mut arguments = self.bind_arguments(arguments, &signature.substitute(&bindings).parameters);
+    let (signature, bindings) = self.instantiate(&name, &signature, bindings, &arguments, span);
+    self.check_arguments(&name, &signature.parameters, &arguments, span);
+    arguments.extend(self.const_arguments(&constants, &bindings, span));
+    BoundExpression::Call {
+      data_type: signature.return_type,
+      span,
+      name: path,
+      arguments,
+    }
+  }
+
+  /// Binds a call of `object.member`, which is either a method called on a
+  /// value, or an associated function called through its type as in
+  /// `Vtuber.new(...)`. The arguments of a generic type called through are
+  /// inferred from the call if they aren't given, as in `array.new()`.
+  fn bind_method_call(
+    &mut self,
+    object: Expression,
+    member: LiteralToken,
+    explicit: Vec<GenericArgument>,
+    arguments: Vec<Expression>,
+    span: Span,
+  ) -> BoundExpression {
+    let name = member.literal.lexeme();
+    let (object, type_arguments) = match object {
+      Expression::Generic { target, arguments, .. }
+        if matches!(*target, Expression::Variable { .. } | Expression::Member { .. }) =>
+      {
+        (*target, Some(arguments))
+      }
+      object => (object, None),
+    };
+    let type_name = self.type_path(&object);
+    let type_arguments = match (type_arguments, &type_name) {
+      (Some(_), None) => {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: object.span(),
+          message: "generic arguments can only be given to a function being called or to a type".into(),
+        });
+        None
+      }
+      (type_arguments, _) => type_arguments,
+    };
+
+    if let Some(id) = &type_name
+      && self.is_trait(id)
+    {
+      self.errors.get_mut().push(InterpreterError::TypeError {
+        span: member.span,
+        message: format!("`{name}` of trait `{}` is called through a type implementing it", id.name),
+      });
+      self.bind_arguments(arguments, &[]);
+      return Self::error_expression(span);
+    }
+
+    // methods are called the same way on a value and on a reference to one
+    let (self_type, receiver) = match (type_name, type_arguments) {
+      (Some(owner), Some(type_arguments)) => (self.instance(owner, &type_arguments, object.span()), None),
+      (Some(owner), None) => (Type::UserDefined(owner), None),
+      (None, _) => {
+        let receiver = self.bind_expression(object);
+        (receiver.data_type().dereferenced().clone(), Some(receiver))
+      }
+    };
+
+    let Some(FoundMethod {
+      owner,
+      method,
+      dispatch,
+    }) = self.find_method(&self_type, &name)
+    else {
+      if self_type != Type::Unknown {
+        self.errors.get_mut().push(InterpreterError::UnknownMethod {
+          span: member.span,
+          data_type: self_type.to_string(),
+          method: name,
+        });
+      }
+      self.bind_arguments(arguments, &[]);
+      return Self::error_expression(span);
+    };
+
+    // the methods of the traits a type implements are as public as the traits
+    let from_trait = self.implements_trait_method(&owner, &name);
+    if !method.exported && !from_trait && !self.is_trait(&owner) {
+      self.check_private(&owner, &format!("method `{name}`"), member.span);
+    }
+
+    let skipped = match (&receiver, method.receiver) {
+      (Some(_), Some(_)) => 1,
+      (None, None) => 0,
+      (Some(_), None) => {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: member.span,
+          message: format!("`{name}` takes no `self`, so it is called as `{self_type}.{name}(...)`"),
+        });
+        0
+      }
+      (None, Some(_)) => {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: member.span,
+          message: format!("`{name}` takes `self`, so it is called on a value of `{self_type}`"),
+        });
+        1
+      }
+    };
+
+    // the `Self` of a method declared by a trait is the type it is called on,
+    // which a trait object doesn't know
+    let trait_object = matches!(&self_type, Type::UserDefined(id) if self.is_trait(id));
+    let mut signature = Signature {
+      generics: method.signature.generics.clone(),
+      parameters: method.signature.parameters[skipped..].to_vec(),
+      return_type: method.signature.return_type.clone(),
+    };
+    let mut bindings = self.explicit_bindings(&format!("{self_type}.{name}"), &signature.generics, &explicit, span);
+    bindings.extend(self.instance_bindings(&self_type));
+    let mut self_type = self_type;
+    if let Type::UserDefined(id) = &self_type
+      && !self.type_generics(id).is_empty()
+    {
+      // the arguments of the type are inferred along with those of the method
+      let own = HashMap::from([(Keyword::_SelfType.lexeme().to_string(), TypeArgument::Type(self.self_type(id)))]);
+      signature = signature.substitute(&own);
+      signature.generics.splice(0..0, self.type_generics(id));
+      self_type = self.self_type(id);
+    }
+    let mut bound_self = self_type.clone();
+    if trait_object
+      && (signature.parameters.iter())
+        .map(|parameter| &parameter.data_type)
+        .chain([&signature.return_type])
+        .any(Self::mentions_self)
+    {
+      self.errors.get_mut().push(InterpreterError::TypeError {
+        span: member.span,
+        message: format!("`{name}` cannot be called on `&{self_type}`, as it uses `Self`"),
+      });
+      bound_self = Type::Unknown;
+    }
+
+    let qualified = format!("{self_type}.{name}");
+    bindings.insert(Keyword::_SelfType.lexeme().to_string(), TypeArgument::Type(bound_self));
+    let constants = self.const_parameters(Some(&owner), &name, &method.signature.generics);
+    let mut arguments = self.bind_arguments(arguments, &signature.substitute(&bindings).parameters);
+    let (signature, bindings) = self.instantiate(&qualified, &signature, bindings, &arguments, span);
+    self.check_arguments(&qualified, &signature.parameters, &arguments, span);
+    arguments.extend(self.const_arguments(&constants, &bindings, span));
+
+    // a method taking `&mut self` gives its changes back to what it was
+    // called on, if that can be assigned to
+    let place = match (&receiver, method.receiver) {
+      (Some(receiver), Some(ReceiverKind::MutableReference)) => {
+        if matches!(receiver.data_type(), Type::Reference(_)) {
+          self.errors.get_mut().push(InterpreterError::TypeError {
+            span: receiver.span(),
+            message: format!("cannot call `{name}`, which takes `&mut self`, through a `&` reference"),
+          });
+        }
+        Self::place(receiver)
+      }
+      _ => None,
+    };
+    if let (Some(place), Some(receiver)) = (&place, &receiver) {
+      self.check_mutable(place, receiver.span());
+    }
+
+    BoundExpression::MethodCall {
+      data_type: signature.return_type,
+      span,
+      owner,
+      name,
+      dispatch,
+      receiver: receiver.filter(|_| method.receiver.is_some()).map(Box::new),
+      place,
+      arguments,
+    }
+  }
+
+  /// The method `name` of `data_type`. A type's own methods come before the
+  /// defaults of the traits it implements, while the methods of trait
+  /// objects and type parameters are those of their traits.
+  fn find_method(&self, data_type: &Type, name: &str) -> Option<FoundMethod> {
+    let declared_by = |trait_id: &TypeId, dispatch: bool| {
+      let definition = self.traits.get(trait_id)?;
+      let declared = definition.methods.iter().find(|method| method.name == name)?;
+      Some(FoundMethod {
+        owner: trait_id.clone(),
+        method: declared.method.clone(),
+        dispatch,
+      })
+    };
+
+    match data_type {
+      Type::UserDefined(id) if self.is_trait(id) => declared_by(id, true),
+      Type::UserDefined(id) | Type::Instance { id, .. } => {
+        match self.methods.get(id).and_then(|methods| methods.get(name)) {
+          Some(method) => Some(FoundMethod {
+            owner: id.clone(),
+            method: method.clone(),
+            dispatch: false,
+          }),
+          None => (self.implementations.get(id)?.iter()).find_map(|trait_id| declared_by(trait_id, false)),
+        }
+      }
+      Type::Parameter(parameter) => (parameter.bounds.iter()).find_map(|trait_id| declared_by(trait_id, true)),
+      _ => None,
+    }
+  }
+
+  fn mentions_self(data_type: &Type) -> bool {
+    match data_type {
+      Type::Parameter(parameter) => parameter.name == Keyword::_SelfType.lexeme(),
+      Type::Reference(inner) => Self::mentions_self(inner),
+      _ => false,
+    }
+  }
+
+  /// Gives the type parameters of a generic call the types of the arguments
+  /// given for them, on top of the ones already in `bindings`, and checks
+  /// they implement the traits the parameters are bound by. Gives the
+  /// signature with every parameter replaced.
+  fn instantiate(
+    &mut self,
+    name: &str,
+    signature: &Signature,
+    mut bindings: HashMap<String, TypeArgument>,
+    arguments: &[BoundExpression],
+    span: Span,
+  ) -> (Signature, HashMap<String, TypeArgument>) {
+    let given: HashSet<_> = bindings.keys().cloned().collect();
+    for (parameter, argument) in signature.parameters.iter().zip(arguments) {
+      Self::infer(&parameter.data_type, &argument.data_type(), &mut bindings);
+    }
+    self.inferred_arguments(name, &signature.generics, &given, &mut bindings, span);
+    (signature.substitute(&bindings), bindings)
+  }
+
+  /// The arguments `bindings` gives `generics` once they have been inferred,
+  /// checking the ones that weren't `given` before.
+  fn inferred_arguments(
+    &mut self,
+    name: &str,
+    generics: &[Rc<TypeParameter>],
+    given: &HashSet<String>,
+    bindings: &mut HashMap<String, TypeArgument>,
+    span: Span,
+  ) -> Vec<TypeArgument> {
+    let mut arguments = Vec::new();
+    for parameter in generics {
+      let argument = match bindings.get(&parameter.name) {
+        Some(argument) => argument.clone(),
+        None => {
+          self.errors.get_mut().push(InterpreterError::TypeError {
+            span,
+            message: format!("cannot infer type `{}` of `{name}`", parameter.name),
+          });
+          let argument = TypeArgument::Type(Type::Unknown);
+          bindings.insert(parameter.name.clone(), argument.clone());
+          argument
+        }
+      };
+      if !given.contains(&parameter.name) {
+        self.check_argument(parameter, &argument, span);
+      }
+      arguments.push(argument);
+    }
+    arguments
+  }
+
+  /// Binds the type parameters in `parameter` to the parts of `argument`
+  /// they stand for.
+  fn infer(parameter: &Type, argument: &Type, bindings: &mut HashMap<String, TypeArgument>) {
+    match (parameter, argument) {
+      (Type::Parameter(parameter), argument) => {
+        bindings
+          .entry(parameter.name.clone())
+          .or_insert_with(|| TypeArgument::Type(argument.clone()));
+      }
+      (Type::Reference(parameter), Type::Reference(argument)) => Self::infer(parameter, argument, bindings),
+      (
+        Type::Instance {
+          id,
+          arguments: parameters,
+        },
+        Type::Instance {
+          id: other, arguments, ..
+        },
+      ) if id == other => {
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+          match (parameter, argument) {
+            (TypeArgument::Type(Type::Parameter(parameter)), argument) => {
+              bindings
+                .entry(parameter.name.clone())
+                .or_insert_with(|| argument.clone());
+            }
+            (TypeArgument::Type(parameter), TypeArgument::Type(argument)) => Self::infer(parameter, argument, bindings),
+            _ => {}
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+
+  /// Checks `argument` fits the type parameter it is given for: an integer in
+  /// range for a const parameter, or a type implementing the bounds of any
+  /// other. Gives whether it does.
+  fn check_argument(&mut self, parameter: &TypeParameter, argument: &TypeArgument, span: Span) -> bool {
+    let name = &parameter.name;
+    let message = match (&parameter.const_type, argument) {
+      (_, TypeArgument::Type(Type::Unknown)) => return true,
+      (Some(const_type), TypeArgument::Const(value)) => match const_type.integer_range() {
+        Some(range) if !range.contains(value) => format!("`{value}` is out of range for `{const_type}`"),
+        _ => return true,
+      },
+      (Some(const_type), TypeArgument::Type(Type::Parameter(argument))) if argument.const_type.is_some() => {
+        match &argument.const_type {
+          Some(argument_type) if argument_type != const_type => {
+            format!(
+              "expected a const `{const_type}` for `{name}` but `{}` is a const `{argument_type}`",
+              argument.name
+            )
+          }
+          _ => return true,
+        }
+      }
+      (Some(const_type), TypeArgument::Type(data_type)) => {
+        format!("expected a const `{const_type}` for `{name}` but got the type `{data_type}`")
+      }
+      (None, TypeArgument::Const(value)) => format!("expected a type for `{name}` but got `{value}`"),
+      (None, TypeArgument::Type(Type::Parameter(argument))) if argument.const_type.is_some() => {
+        format!("expected a type for `{name}` but got the const parameter `{}`", argument.name)
+      }
+      (None, TypeArgument::Type(data_type)) => {
+        let mut fits = true;
+        for bound in &parameter.bounds {
+          if !self.implements(data_type, bound) {
+            fits = false;
+            self.errors.get_mut().push(InterpreterError::TypeError {
+              span,
+              message: format!("`{data_type}` does not implement trait `{}`", bound.name),
+            });
+          }
+        }
+        return fits;
+      }
+    };
+    self
+      .errors
+      .get_mut()
+      .push(InterpreterError::TypeError { span, message });
+    false
+  }
+
+  /// What the generic arguments written out at a use of `name` give its
+  /// `parameters`. There are none if the arguments are left to be inferred.
+  fn explicit_bindings(
+    &mut self,
+    name: &str,
+    parameters: &[Rc<TypeParameter>],
+    arguments: &[GenericArgument],
+    span: Span,
+  ) -> HashMap<String, TypeArgument> {
+    if arguments.is_empty() {
+      return HashMap::new();
+    }
+    if arguments.len() != parameters.len() {
+      self.errors.get_mut().push(InterpreterError::GenericArgumentCount {
+        span,
+        name: name.to_string(),
+        expected: parameters.len(),
+        found: arguments.len(),
+      });
+      for argument in arguments {
+        self.resolve_argument(argument);
+      }
+      return HashMap::new();
+    }
+
+    let mut bindings = HashMap::new();
+    for (parameter, syntax) in parameters.iter().zip(arguments) {
+      let argument = self.resolve_argument(syntax);
+      let argument = match self.check_argument(parameter, &argument, syntax.span()) {
+        true => argument,
+        false => TypeArgument::Type(Type::Unknown),
+      };
+      bindings.insert(parameter.name.clone(), argument);
+    }
+    bindings
+  }
+
+  /// The generic type `id` given `arguments`, all of which have to be there.
+  fn instance(&mut self, id: TypeId, arguments: &[GenericArgument], span: Span) -> Type {
+    let generics = self.type_generics(&id);
+    if generics.is_empty() && arguments.is_empty() {
+      return Type::UserDefined(id);
+    }
+    if arguments.len() != generics.len() {
+      self.errors.get_mut().push(InterpreterError::GenericArgumentCount {
+        span,
+        name: id.name.to_string(),
+        expected: generics.len(),
+        found: arguments.len(),
+      });
+      return Type::Unknown;
+    }
+
+    let mut bindings = self.explicit_bindings(&id.name, &generics, arguments, span);
+    let arguments = (generics.iter())
+      .map(|parameter| {
+        bindings
+          .remove(&parameter.name)
+          .unwrap_or(TypeArgument::Type(Type::Unknown))
+      })
+      .collect();
+    Type::Instance { id, arguments }
+  }
+
+  /// Resolves a generic argument. A const parameter in scope can be passed on
+  /// as it is, though it can't be used as a type.
+  fn resolve_argument(&mut self, argument: &GenericArgument) -> TypeArgument {
+    let data_type = match argument {
+      GenericArgument::Const(LiteralToken {
+        literal: Literal::Number { lexeme, .. },
+        ..
+      }) => return TypeArgument::Const(*lexeme),
+      GenericArgument::Const(_) => return TypeArgument::Type(Type::Unknown),
+      GenericArgument::Type(data_type) => data_type,
+    };
+
+    if let TypeExpression::Path {
+      segments, arguments, ..
+    } = data_type
+      && let [name] = segments.as_slice()
+      && arguments.is_empty()
+      && let Some(Type::Parameter(parameter)) = self.generics.get(&name.literal.lexeme())
+      && parameter.const_type.is_some()
+    {
+      return TypeArgument::Type(Type::Parameter(parameter.clone()));
+    }
+    TypeArgument::Type(self.resolve_type(data_type))
+  }
+
+  /// Binds the arguments of a call, where each is expected to be of the type
+  /// of its parameter.
+  fn bind_arguments(&mut self, arguments: Vec<Expression>, parameters: &[BoundParameter]) -> Vec<BoundExpression> {
+    arguments
+      .into_iter()
+      .enumerate()
+      .map(|(index, argument)| {
+        let expected = parameters.get(index).map(|parameter| &parameter.data_type);
+        self.bind_expected(argument, expected)
+      })
+      .collect()
+  }
+
+  /// Reports `value` going where an `expected` is expected if it is an
+  /// integer known to be out of its range.
+  fn check_range(&mut self, expected: &Type, value: &BoundExpression) {
+    let (Some(range), Some(integer)) = (expected.integer_range(), self.known_integer(value)) else {
+      return;
+    };
+    if !range.contains(&integer) {
+      self.errors.get_mut().push(InterpreterError::TypeError {
+        span: value.span(),
+        message: format!("`{integer}` is out of range for `{expected}`"),
+      });
+    }
+  }
+
+  /// The integer `value` is known to be before the program runs: that of an
+  /// unsuffixed literal, negated or not, or of a variable holding one.
+  fn known_integer(&self, value: &BoundExpression) -> Option<i128> {
+    match value {
+      BoundExpression::Literal {
+        data_type: Type::Primitive(Primitive::Int),
+        value: Value::Int(integer),
+        ..
+      } => Some(*integer),
+      BoundExpression::Unary {
+        operator: BoundUnaryOperator::Negation,
+        operand,
+        ..
+      } => self.known_integer(operand).map(|integer| -integer),
+      BoundExpression::Grouping { operand, .. } => self.known_integer(operand),
+      BoundExpression::Variable { name, .. } => {
+        let index = self.scope_of(name)?;
+        self.integers.get(&(index, name.clone())).copied()
+      }
+      _ => None,
+    }
+  }
+
+  /// The index of the innermost scope declaring the variable `name`.
+  fn scope_of(&self, name: &str) -> Option<usize> {
+    self.scopes.iter().rposition(|scope| scope.contains_key(name))
+  }
+
+  /// Leaves the innermost scope, forgetting what is known of its variables.
+  fn pop_scope(&mut self) {
+    self.scopes.pop();
+    let depth = self.scopes.len();
+    self.integers.retain(|(index, _), _| *index < depth);
+  }
+
+  fn check_arguments(&mut self, name: &str, parameters: &[BoundParameter], arguments: &[BoundExpression], span: Span) {
+    if arguments.len() != parameters.len() {
+      self.errors.get_mut().push(InterpreterError::ArgumentCount {
+        span,
+        name: name.to_string(),
+        expected: parameters.len(),
+        found: arguments.len(),
+      });
+    }
+
+    for (argument, parameter) in arguments.iter().zip(parameters) {
+      match self.accepts(&parameter.data_type, &argument.data_type()) {
+        true => self.check_range(&parameter.data_type, argument),
+        false => self.errors.get_mut().push(InterpreterError::TypeError {
+          span: argument.span(),
+          message: format!("expected `{}` but got `{}`", parameter.data_type, argument.data_type()),
+        }),
+      }
+    }
+  }
+
+  /// Stands in for an expression that couldn't be bound. It is never run, as
+  /// binding errors stop the program first.
+  fn error_expression(span: Span) -> BoundExpression {
+    BoundExpression::Literal {
+      data_type: Type::Unknown,
+      span,
+      value: Value::Void,
+    }
+  }
+
+  /// The type a type expression names. The primitives are part of `core`, so
+  /// they can be named with or without the `core.` in front, while declared
+  /// types and type parameters are named on their own.
+  fn resolve_type(&mut self, data_type: &TypeExpression) -> Type {
+    self.resolve(data_type, false)
+  }
+
+  /// Resolves a type expression, which can name a trait only if it is
+  /// `behind_reference`.
+  fn resolve(&mut self, data_type: &TypeExpression, behind_reference: bool) -> Type {
+    let (segments, arguments, span) = match data_type {
+      TypeExpression::Path {
+        segments,
+        arguments,
+        span,
+      } => (segments, arguments, span),
+      TypeExpression::Reference { inner, .. } => return Type::Reference(Box::new(self.resolve(inner, true))),
+      _ => return Type::VOID,
+    };
+
+    let names: Vec<_> = segments.iter().map(|segment| segment.literal.lexeme()).collect();
+    let resolved = match names.as_slice() {
+      [name] => match (self.generics.get(name), self.types.get(name)) {
+        (Some(parameter), _) => Some(parameter.clone()),
+        (None, Some(id)) => Some(Type::UserDefined(id.clone())),
+        (None, None) => Primitive::from_name(name).map(Type::Primitive),
+      },
+      [modules @ .., name] => match self.module_at(modules) {
+        Some(module) => (self.modules[&module].exports.types.get(name)).map(|id| Type::UserDefined(id.clone())),
+        None if modules == ["core"] => Primitive::from_name(name).map(Type::Primitive),
+        None => None,
+      },
+      [] => None,
+    };
+
+    match resolved {
+      Some(Type::UserDefined(id)) if self.is_trait(&id) && !behind_reference => {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: *span,
+          message: format!("trait `{data_type}` can only be used behind a reference, as `&{data_type}`"),
+        });
+        Type::Unknown
+      }
+      Some(Type::UserDefined(id)) => self.instance(id, arguments, *span),
+      Some(Type::Parameter(parameter)) if parameter.const_type.is_some() => {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: *span,
+          message: format!("`{}` is a const parameter, not a type", parameter.name),
+        });
+        Type::Unknown
+      }
+      Some(resolved) if !arguments.is_empty() => {
+        self.errors.get_mut().push(InterpreterError::GenericArgumentCount {
+          span: *span,
+          name: resolved.to_string(),
+          expected: 0,
+          found: arguments.len(),
+        });
+        Type::Unknown
+      }
+      Some(resolved) => resolved,
+      None => {
+        self.errors.get_mut().push(InterpreterError::TypeError {
+          span: *span,
+          message: format!("cannot find type `{data_type}` in this scope"),
+        });
+        Type::Unknown
+      }
+    }
+  }
+
+  /// Binds a literal. An integer literal can be as large as the magnitude of
+  /// the most negative value of its type, which only fits once `negated`.
+  fn bind_literal(&mut self, token: LiteralToken, negated: bool) -> BoundExpression {
+    if let Literal::Number { lexeme, suffix } = &token.literal {
+      let data_type = suffix.unwrap_or(Primitive::I64);
+      let range = data_type.integer_range().expect("integer types have a range");
+      if !negated && lexeme > range.end() {
+        self.errors.get_mut().push(InterpreterError::LiteralOutOfRange {
+          span: token.span,
+          literal: token.literal.lexeme(),
+          data_type: data_type.to_string(),
+          range: format!("{}..={}", range.start(), range.end()),
+        });
+      }
+    }
+    BoundExpression::Literal {
+      data_type: Self::literal_type(&token.literal),
+      span: token.span,
+      value: token.literal.value(),
+    }
+  }
+
+  fn literal_type(literal: &Literal) -> Type {
+    match literal {
+      Literal::Identifier { .. } => Type::Unknown,
+      Literal::String { .. } | Literal::InterpolatedString { .. } => Type::STRING,
+      Literal::Number { suffix, .. } => suffix.map_or(Type::INT, Type::Primitive),
+      Literal::Float { .. } => Type::FLOAT,
+      Literal::Char { .. } => Type::CHAR,
+      Literal::Bool { .. } => Type::BOOL,
+      Literal::Void => Type::VOID,
+    }
+  }
+
+  fn unary_type(&mut self, operator: &SymbolToken, bound_operator: &BoundUnaryOperator, operand: Type) -> Type {
+    let data_type = match (bound_operator, &operand) {
+      (_, Type::Unknown) => return Type::Unknown,
+      (BoundUnaryOperator::Negation, Type::Primitive(primitive)) if primitive.is_signed() => Some(operand.clone()),
+      (BoundUnaryOperator::Negation, Type::Primitive(Primitive::Float)) => Some(operand.clone()),
+      (BoundUnaryOperator::Not, Type::Primitive(primitive)) if primitive.is_integer() => Some(operand.clone()),
+      (BoundUnaryOperator::Not, Type::Primitive(Primitive::Bool)) => Some(operand.clone()),
+      (BoundUnaryOperator::Reference, _) => Some(Type::Reference(Box::new(operand.clone()))),
+      _ => None,
+    };
+
+    data_type.unwrap_or_else(|| {
+      self.errors.get_mut().push(InterpreterError::TypeError {
+        span: operator.span,
+        message: format!("cannot apply `{}` to `{operand}`", operator.symbol.lexeme()),
+      });
+      Type::Unknown
+    })
+  }
+
+  /// Arithmetic and comparison operators follow the numeric promotion rule of
+  /// [`Type::promote`]: integers mixed with `float` are evaluated as `float`,
+  /// and unsuffixed integers take the width of the other operand.
+  fn binary_type(
+    &mut self,
+    operator: &SymbolToken,
+    bound_operator: &BoundBinaryOperator,
+    left: Type,
+    right: Type,
+  ) -> Type {
+    if left == Type::Unknown || right == Type::Unknown {
+      return Type::Unknown;
+    }
+
+    let data_type = match bound_operator {
+      BoundBinaryOperator::Addition if left == Type::STRING && right == Type::STRING => Some(Type::STRING),
+      BoundBinaryOperator::Addition
+      | BoundBinaryOperator::Subtraction
+      | BoundBinaryOperator::Multiplication
+      | BoundBinaryOperator::Division
+      | BoundBinaryOperator::Exponentiation => Type::promote(&left, &right),
+      BoundBinaryOperator::LessThan
+      | BoundBinaryOperator::GreaterThan
+      | BoundBinaryOperator::LessThanEquals
+      | BoundBinaryOperator::GreaterThanEquals => {
+        (left == Type::CHAR && right == Type::CHAR || Type::promote(&left, &right).is_some()).then_some(Type::BOOL)
+      }
+      BoundBinaryOperator::Equals | BoundBinaryOperator::NotEquals => {
+        (left == right || Type::promote(&left, &right).is_some()).then_some(Type::BOOL)
+      }
+      BoundBinaryOperator::LogicalAnd | BoundBinaryOperator::LogicalOr => {
+        (Type::BOOL.accepts(&left) && Type::BOOL.accepts(&right)).then_some(Type::BOOL)
+      }
+    };
+
+    data_type.unwrap_or_else(|| {
+      self.errors.get_mut().push(InterpreterError::TypeError {
+        span: operator.span,
+        message: format!("cannot apply `{}` to `{left}` and `{right}`", operator.symbol.lexeme()),
+      });
+      Type::Unknown
+    })
   }
 }
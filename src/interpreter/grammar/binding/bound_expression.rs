@@ -1,154 +1,725 @@
-use std::any::{Any, TypeId};
+use super::{
+  bound_operator::{BoundBinaryOperator, BoundUnaryOperator},
+  bound_statement::BoundStatement,
+  data_type::{Type, TypeId},
+  environment::Environment,
+  interrupt::Interrupt,
+  value::Value,
+};
+use crate::interpreter::{error::InterpreterError, grammar::span::Span};
 
-use super::bound_operator::{BoundBinaryOperator, BoundUnaryOperator};
-use crate::interpreter::{error::InterpreterError, grammar::token::Position};
-
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum BoundExpression {
   Literal {
-    data_type: TypeId,
-    position: Position,
-    value: Box<dyn Any>,
+    data_type: Type,
+    span: Span,
+    value: Value,
+  },
+  Variable {
+    data_type: Type,
+    span: Span,
+    name: String,
   },
   Unary {
-    data_type: TypeId,
-    position: Position,
+    data_type: Type,
+    span: Span,
     operator: BoundUnaryOperator,
+    operator_span: Span,
     operand: Box<BoundExpression>,
   },
   Binary {
-    data_type: TypeId,
-    position: Position,
+    data_type: Type,
+    span: Span,
     operator: BoundBinaryOperator,
+    operator_span: Span,
     left_operand: Box<BoundExpression>,
     right_operand: Box<BoundExpression>,
   },
   Grouping {
-    data_type: TypeId,
+    data_type: Type,
+    span: Span,
     operand: Box<BoundExpression>,
   },
+  Interpolation {
+    data_type: Type,
+    span: Span,
+    parts: Vec<BoundInterpolationPart>,
+  },
+  Block {
+    data_type: Type,
+    span: Span,
+    statements: Vec<BoundStatement>,
+  },
+  Call {
+    data_type: Type,
+    span: Span,
+    name: String,
+    arguments: Vec<BoundExpression>,
+  },
+  Return {
+    data_type: Type,
+    span: Span,
+    value: Option<Box<BoundExpression>>,
+  },
+  If {
+    data_type: Type,
+    span: Span,
+    condition: Box<BoundExpression>,
+    then_branch: Box<BoundExpression>,
+    else_branch: Option<Box<BoundExpression>>,
+  },
+  While {
+    data_type: Type,
+    span: Span,
+    condition: Box<BoundExpression>,
+    body: Box<BoundExpression>,
+  },
+  Loop {
+    data_type: Type,
+    span: Span,
+    body: Box<BoundExpression>,
+  },
+  For {
+    data_type: Type,
+    span: Span,
+    variable: String,
+    iterable: BoundIterable,
+    body: Box<BoundExpression>,
+  },
+  Break {
+    data_type: Type,
+    span: Span,
+    value: Option<Box<BoundExpression>>,
+  },
+  Continue {
+    data_type: Type,
+    span: Span,
+  },
+  /// The field at `index` of a record.
+  Field {
+    data_type: Type,
+    span: Span,
+    object: Box<BoundExpression>,
+    index: usize,
+  },
+  /// The character of a string at `index`.
+  Index {
+    data_type: Type,
+    span: Span,
+    object: Box<BoundExpression>,
+    index: Box<BoundExpression>,
+  },
+  Record {
+    data_type: Type,
+    span: Span,
+    fields: Vec<BoundFieldInitializer>,
+  },
+  Assign {
+    data_type: Type,
+    span: Span,
+    target: BoundPlace,
+    target_type: Type,
+    value: Box<BoundExpression>,
+  },
+  /// A call of a method or associated function of the type `owner`. The
+  /// `receiver` comes first in the arguments of a method, and is given back
+  /// to `place` after a method taking `&mut self` has changed it.
+  ///
+  /// A method called through a trait, on a trait object or a value of a type
+  /// parameter, is `dispatch`ed: it is looked up on the type of the receiver
+  /// when called, and `owner` is the trait, whose default is used if the type
+  /// doesn't have its own.
+  MethodCall {
+    data_type: Type,
+    span: Span,
+    owner: TypeId,
+    name: String,
+    dispatch: bool,
+    receiver: Option<Box<BoundExpression>>,
+    place: Option<BoundPlace>,
+    arguments: Vec<BoundExpression>,
+  },
+}
+
+/// Where an assignment puts its value: a variable, or a field or an element
+/// of one, reached through each step of `path`.
+#[derive(Debug, Clone)]
+pub struct BoundPlace {
+  pub variable: String,
+  pub path: Vec<BoundPlaceStep>,
+}
+
+/// A step from a value to a part of it in a [`BoundPlace`].
+#[derive(Debug, Clone)]
+pub enum BoundPlaceStep {
+  /// The field of a record with this index.
+  Field(usize),
+  /// The character of a string at the index this evaluates to.
+  Index(BoundExpression),
+}
+
+/// A field given a value in a record literal, in the order the literal gives
+/// them.
+#[derive(Debug, Clone)]
+pub struct BoundFieldInitializer {
+  pub index: usize,
+  pub name: String,
+  pub data_type: Type,
+  pub value: BoundExpression,
+}
+
+/// What a `for` loop can go through.
+#[derive(Debug, Clone)]
+pub enum BoundIterable {
+  /// The integers from `start` up to `end`, including `end` if `inclusive`.
+  Range {
+    start: Box<BoundExpression>,
+    end: Box<BoundExpression>,
+    inclusive: bool,
+  },
+  /// The characters of a string.
+  String(Box<BoundExpression>),
+}
+
+#[derive(Debug, Clone)]
+pub enum BoundInterpolationPart {
+  Text(String),
+  Expression(BoundExpression),
 }
 
 impl BoundExpression {
-  pub fn data_type(&self) -> TypeId {
+  pub fn data_type(&self) -> Type {
     match self {
-      BoundExpression::Literal { data_type, .. } => *data_type,
-      BoundExpression::Unary { data_type, .. } => *data_type,
-      BoundExpression::Binary { data_type, .. } => *data_type,
-      BoundExpression::Grouping { data_type, .. } => *data_type,
+      BoundExpression::Literal { data_type, .. } => data_type.clone(),
+      BoundExpression::Variable { data_type, .. } => data_type.clone(),
+      BoundExpression::Unary { data_type, .. } => data_type.clone(),
+      BoundExpression::Binary { data_type, .. } => data_type.clone(),
+      BoundExpression::Grouping { data_type, .. } => data_type.clone(),
+      BoundExpression::Interpolation { data_type, .. } => data_type.clone(),
+      BoundExpression::Block { data_type, .. } => data_type.clone(),
+      BoundExpression::Call { data_type, .. } => data_type.clone(),
+      BoundExpression::Return { data_type, .. } => data_type.clone(),
+      BoundExpression::If { data_type, .. } => data_type.clone(),
+      BoundExpression::While { data_type, .. } => data_type.clone(),
+      BoundExpression::Loop { data_type, .. } => data_type.clone(),
+      BoundExpression::For { data_type, .. } => data_type.clone(),
+      BoundExpression::Break { data_type, .. } => data_type.clone(),
+      BoundExpression::Continue { data_type, .. } => data_type.clone(),
+      BoundExpression::Field { data_type, .. } => data_type.clone(),
+      BoundExpression::Index { data_type, .. } => data_type.clone(),
+      BoundExpression::Record { data_type, .. } => data_type.clone(),
+      BoundExpression::Assign { data_type, .. } => data_type.clone(),
+      BoundExpression::MethodCall { data_type, .. } => data_type.clone(),
     }
   }
 
-  pub fn evaluate(&self) -> Result<Box<dyn Any>, InterpreterError> {
+  pub fn span(&self) -> Span {
     match self {
-      BoundExpression::Literal {
-        data_type,
-        position,
-        value,
-      } => {
-        if let Some(&integer) = value.downcast_ref::<i64>() {
-          return Ok(Box::new(integer));
+      BoundExpression::Literal { span, .. } => *span,
+      BoundExpression::Variable { span, .. } => *span,
+      BoundExpression::Unary { span, .. } => *span,
+      BoundExpression::Binary { span, .. } => *span,
+      BoundExpression::Grouping { span, .. } => *span,
+      BoundExpression::Interpolation { span, .. } => *span,
+      BoundExpression::Block { span, .. } => *span,
+      BoundExpression::Call { span, .. } => *span,
+      BoundExpression::Return { span, .. } => *span,
+      BoundExpression::If { span, .. } => *span,
+      BoundExpression::While { span, .. } => *span,
+      BoundExpression::Loop { span, .. } => *span,
+      BoundExpression::For { span, .. } => *span,
+      BoundExpression::Break { span, .. } => *span,
+      BoundExpression::Continue { span, .. } => *span,
+      BoundExpression::Field { span, .. } => *span,
+      BoundExpression::Index { span, .. } => *span,
+      BoundExpression::Record { span, .. } => *span,
+      BoundExpression::Assign { span, .. } => *span,
+      BoundExpression::MethodCall { span, .. } => *span,
+    }
+  }
+
+  pub fn evaluate(&self, environment: &mut Environment) -> Result<Value, Interrupt> {
+    match self {
+      BoundExpression::Literal { data_type, span, value } => {
+        if *data_type == Type::Unknown {
+          return Err(
+            InterpreterError::SyntaxError {
+              span: *span,
+              message: "literals of this value not supported".to_string(),
+            }
+            .into(),
+          );
         }
 
-        Err(InterpreterError::SyntaxError {
-          position: position.to_owned(),
-          message: "literals of this value not supported".to_string(),
-        })
+        Ok(value.clone())
       }
+      BoundExpression::Variable { span, name, .. } => match environment.get(name) {
+        Some(value) => Ok(value.clone()),
+        None => Err(
+          InterpreterError::UninitializedVariable {
+            span: *span,
+            name: name.clone(),
+          }
+          .into(),
+        ),
+      },
       BoundExpression::Unary {
         data_type,
-        position,
         operator,
+        operator_span,
         operand,
+        ..
       } => {
-        let value = operand.evaluate()?;
-
-        match operator {
-          BoundUnaryOperator::Negation => {
-            if *data_type == TypeId::of::<i64>() {
-              let Some(&value) = value.downcast_ref::<i64>() else {
-                unreachable!()
-              };
-
-              return Ok(Box::new(-value));
-            }
-
-            Err(InterpreterError::SyntaxError {
-              position: position.to_owned(),
-              message: format!("cannot perform `{:?}` on value", operator),
-            })
-          }
-          BoundUnaryOperator::Not => {
-            if *data_type == TypeId::of::<i64>() {
-              let Some(&value) = value.downcast_ref::<i64>() else {
-                unreachable!()
-              };
-
-              return Ok(Box::new(!value));
-            }
-
-            Err(InterpreterError::SyntaxError {
-              position: position.to_owned(),
-              message: format!("cannot perform `{:?}` on value", operator),
-            })
-          }
-        }
+        let value = operand.evaluate(environment)?;
+        Ok(Self::evaluate_unary(data_type, operator, *operator_span, value)?)
       }
       BoundExpression::Binary {
         data_type,
-        position,
         operator,
+        operator_span,
         left_operand,
         right_operand,
+        ..
       } => {
-        let left_value = left_operand.evaluate()?;
-        let right_value = right_operand.evaluate()?;
+        let left_value = left_operand.evaluate(environment)?;
+        // the right operand of `&&` and `||` only runs if the left one doesn't
+        // already give the result
+        match (operator, &left_value) {
+          (BoundBinaryOperator::LogicalAnd, Value::Bool(false))
+          | (BoundBinaryOperator::LogicalOr, Value::Bool(true)) => {
+            return Ok(left_value);
+          }
+          _ => {}
+        }
+        let right_value = right_operand.evaluate(environment)?;
 
-        match operator {
-          BoundBinaryOperator::Addition => {
-            if let (Some(left), Some(right)) = (left_value.downcast_ref::<i64>(), right_value.downcast_ref::<i64>()) {
-              return Ok(Box::new(left + right));
+        Ok(Self::evaluate_binary(
+          data_type,
+          operator,
+          *operator_span,
+          left_value,
+          right_value,
+        )?)
+      }
+      BoundExpression::Grouping { operand, .. } => operand.evaluate(environment),
+      BoundExpression::Interpolation { parts, .. } => {
+        let mut string = String::new();
+        for part in parts {
+          match part {
+            BoundInterpolationPart::Text(text) => string.push_str(text),
+            BoundInterpolationPart::Expression(expression) => {
+              string.push_str(&expression.evaluate(environment)?.to_string())
             }
-
-            Err(InterpreterError::SyntaxError {
-              position: position.to_owned(),
-              message: format!("cannot perform `{:?}` on value", operator),
-            })
           }
-          BoundBinaryOperator::Subtraction => {
-            if let (Some(left), Some(right)) = (left_value.downcast_ref::<i64>(), right_value.downcast_ref::<i64>()) {
-              return Ok(Box::new(left - right));
-            }
+        }
+
+        Ok(Value::String(string))
+      }
+      BoundExpression::Block { statements, .. } => {
+        environment.push_scope();
+        let value = BoundStatement::evaluate_sequence(statements, environment);
+        environment.pop_scope();
+        value
+      }
+      BoundExpression::Call {
+        span, name, arguments, ..
+      } => {
+        let function = environment
+          .function(name)
+          .ok_or_else(|| InterpreterError::UndeclaredFunction {
+            span: *span,
+            name: name.clone(),
+          })?;
 
-            Err(InterpreterError::SyntaxError {
-              position: position.to_owned(),
-              message: format!("cannot perform `{:?}` on value", operator),
-            })
+        let mut values = Vec::with_capacity(arguments.len());
+        for (argument, parameter) in arguments.iter().zip(&function.parameters) {
+          values.push(
+            argument
+              .evaluate(environment)?
+              .fit(&parameter.data_type, argument.span())?,
+          );
+        }
+
+        Ok(function.call(values, *span, environment)?)
+      }
+      BoundExpression::Return { value, .. } => {
+        let value = match value {
+          Some(value) => value.evaluate(environment)?,
+          None => Value::Void,
+        };
+        Err(Interrupt::Return(value))
+      }
+      BoundExpression::If {
+        condition,
+        then_branch,
+        else_branch,
+        ..
+      } => {
+        match (Self::condition(condition, environment)?, else_branch) {
+          (true, Some(_)) => then_branch.evaluate(environment),
+          // an `if` without an `else` has no value, whichever branch runs
+          (true, None) => then_branch.evaluate(environment).map(|_| Value::Void),
+          (false, Some(else_branch)) => else_branch.evaluate(environment),
+          (false, None) => Ok(Value::Void),
+        }
+      }
+      BoundExpression::While { condition, body, .. } => {
+        while Self::condition(condition, environment)? {
+          if Self::iterate(body, environment)?.is_some() {
+            break;
           }
-          BoundBinaryOperator::Multiplication => {
-            if let (Some(left), Some(right)) = (left_value.downcast_ref::<i64>(), right_value.downcast_ref::<i64>()) {
-              return Ok(Box::new(left * right));
+        }
+        Ok(Value::Void)
+      }
+      BoundExpression::Loop { body, .. } => loop {
+        if let Some(value) = Self::iterate(body, environment)? {
+          return Ok(value);
+        }
+      },
+      BoundExpression::For {
+        variable,
+        iterable,
+        body,
+        ..
+      } => {
+        let values: Box<dyn Iterator<Item = Value>> = match iterable {
+          BoundIterable::Range { start, end, inclusive } => {
+            let (Value::Int(start), Value::Int(end)) = (start.evaluate(environment)?, end.evaluate(environment)?)
+            else {
+              unreachable!("the binder only allows integer ranges")
+            };
+            match inclusive {
+              true => Box::new((start..=end).map(Value::Int)),
+              false => Box::new((start..end).map(Value::Int)),
             }
+          }
+          BoundIterable::String(string) => {
+            let Value::String(string) = string.evaluate(environment)? else {
+              unreachable!("the binder only allows iterating strings")
+            };
+            Box::new(string.chars().collect::<Vec<_>>().into_iter().map(Value::Char))
+          }
+        };
 
-            Err(InterpreterError::SyntaxError {
-              position: position.to_owned(),
-              message: format!("cannot perform `{:?}` on value", operator),
-            })
+        for value in values {
+          environment.push_scope();
+          environment.declare(variable, Some(value));
+          let broke = Self::iterate(body, environment);
+          environment.pop_scope();
+          if broke?.is_some() {
+            break;
           }
-          BoundBinaryOperator::Division => {
-            if let (Some(left), Some(right)) = (left_value.downcast_ref::<i64>(), right_value.downcast_ref::<i64>()) {
-              return Ok(Box::new(left / right));
-            }
+        }
+        Ok(Value::Void)
+      }
+      BoundExpression::Break { value, .. } => {
+        let value = match value {
+          Some(value) => value.evaluate(environment)?,
+          None => Value::Void,
+        };
+        Err(Interrupt::Break(value))
+      }
+      BoundExpression::Continue { .. } => Err(Interrupt::Continue),
+      BoundExpression::Field { object, index, .. } => match object.evaluate(environment)? {
+        Value::Record { mut fields, .. } => Ok(fields.swap_remove(*index).1),
+        _ => unreachable!("the binder only allows fields of records"),
+      },
+      BoundExpression::Index { object, index, .. } => {
+        let Value::String(string) = object.evaluate(environment)? else {
+          unreachable!("the binder only allows indexing strings")
+        };
+        let Value::Int(position) = index.evaluate(environment)? else {
+          unreachable!("the binder only allows integer indices")
+        };
+        let position = Self::position(&string, position, index.span())?;
+        Ok(Value::Char(
+          string.chars().nth(position).expect("the position is in the string"),
+        ))
+      }
+      BoundExpression::Record { data_type, fields, .. } => {
+        let Some(id) = data_type.declared() else {
+          unreachable!("record literals are bound to their record type")
+        };
+
+        let mut values = Vec::with_capacity(fields.len());
+        for field in fields {
+          let value = field
+            .value
+            .evaluate(environment)?
+            .fit(&field.data_type, field.value.span())?;
+          values.push((field.index, (field.name.as_str().into(), value)));
+        }
+        values.sort_by_key(|(index, _)| *index);
 
-            Err(InterpreterError::SyntaxError {
-              position: position.to_owned(),
-              message: format!("cannot perform `{:?}` on value", operator),
-            })
+        Ok(Value::Record {
+          data_type: id.clone(),
+          fields: values.into_iter().map(|(_, field)| field).collect(),
+        })
+      }
+      BoundExpression::Assign {
+        span,
+        target,
+        target_type,
+        value,
+        ..
+      } => {
+        let value = value.evaluate(environment)?.fit(target_type, value.span())?;
+        target.assign(value, *span, environment)?;
+        Ok(Value::Void)
+      }
+      BoundExpression::MethodCall {
+        span,
+        owner,
+        name,
+        dispatch,
+        receiver,
+        place,
+        arguments,
+        ..
+      } => {
+        let mut values = Vec::with_capacity(arguments.len() + 1);
+        if let Some(receiver) = receiver {
+          values.push(receiver.evaluate(environment)?);
+        }
+
+        let implementation = match values.first().and_then(Value::type_id) {
+          Some(data_type) if *dispatch => environment.method(data_type, name),
+          _ => None,
+        };
+        let method = implementation
+          .or_else(|| environment.method(owner, name))
+          .ok_or_else(|| InterpreterError::UndeclaredFunction {
+            span: *span,
+            name: format!("{}.{name}", owner.name),
+          })?;
+        for (argument, parameter) in arguments.iter().zip(&method.parameters[values.len()..]) {
+          values.push(
+            argument
+              .evaluate(environment)?
+              .fit(&parameter.data_type, argument.span())?,
+          );
+        }
+
+        let (value, receiver) = method.call_with_receiver(values, *span, environment)?;
+        if let (Some(place), Some(receiver)) = (place, receiver) {
+          place.assign(receiver, *span, environment)?;
+        }
+        Ok(value)
+      }
+    }
+  }
+
+  fn condition(condition: &BoundExpression, environment: &mut Environment) -> Result<bool, Interrupt> {
+    match condition.evaluate(environment)? {
+      Value::Bool(value) => Ok(value),
+      value => Err(
+        InterpreterError::RuntimeError {
+          span: condition.span(),
+          message: format!("expected `bool` but got `{value}`"),
+        }
+        .into(),
+      ),
+    }
+  }
+
+  /// Runs the body of a loop once, giving the value of the `break` it hit, if
+  /// any.
+  fn iterate(body: &BoundExpression, environment: &mut Environment) -> Result<Option<Value>, Interrupt> {
+    match body.evaluate(environment) {
+      Ok(_) | Err(Interrupt::Continue) => Ok(None),
+      Err(Interrupt::Break(value)) => Ok(Some(value)),
+      Err(interrupt) => Err(interrupt),
+    }
+  }
+
+  fn evaluate_unary(
+    data_type: &Type,
+    operator: &BoundUnaryOperator,
+    operator_span: Span,
+    value: Value,
+  ) -> Result<Value, InterpreterError> {
+    let range = data_type.integer_range();
+
+    match (operator, value, range) {
+      (BoundUnaryOperator::Negation, Value::Int(value), Some(range)) => value
+        .checked_neg()
+        .filter(|value| range.contains(value))
+        .map(Value::Int)
+        .ok_or_else(|| InterpreterError::RuntimeError {
+          span: operator_span,
+          message: format!("integer overflow in `{:?}` for `{data_type}`", operator),
+        }),
+      (BoundUnaryOperator::Negation, Value::Float(value), _) => Ok(Value::Float(-value)),
+      // flips the bits within the width of the type, so `!0u8` is `255`
+      (BoundUnaryOperator::Not, Value::Int(value), Some(range)) if *range.start() == 0 => {
+        Ok(Value::Int(range.end() ^ value))
+      }
+      (BoundUnaryOperator::Not, Value::Int(value), Some(_)) => Ok(Value::Int(!value)),
+      (BoundUnaryOperator::Not, Value::Bool(value), _) => Ok(Value::Bool(!value)),
+      // references can't be used to change what they refer to, so the value
+      // itself can stand in for one
+      (BoundUnaryOperator::Reference, value, _) => Ok(value),
+      _ => Err(InterpreterError::SyntaxError {
+        span: operator_span,
+        message: format!("cannot perform `{:?}` on value", operator),
+      }),
+    }
+  }
+
+  fn evaluate_binary(
+    data_type: &Type,
+    operator: &BoundBinaryOperator,
+    operator_span: Span,
+    left: Value,
+    right: Value,
+  ) -> Result<Value, InterpreterError> {
+    // mixed int/float operands are promoted to float, see `Type::promote`
+    let (left, right) = match (&left, &right) {
+      (Value::Int(_), Value::Float(_)) | (Value::Float(_), Value::Int(_)) => (left.promoted(), right.promoted()),
+      _ => (left, right),
+    };
+
+    // integer results must stay within the range of the bound type
+    let integer = |value: Option<i128>| {
+      value
+        .filter(|value| data_type.integer_range().is_some_and(|range| range.contains(value)))
+        .map(Value::Int)
+        .ok_or_else(|| InterpreterError::RuntimeError {
+          span: operator_span,
+          message: format!("integer overflow in `{:?}` for `{data_type}`", operator),
+        })
+    };
+
+    match (operator, left, right) {
+      (BoundBinaryOperator::Addition, Value::Int(left), Value::Int(right)) => integer(left.checked_add(right)),
+      (BoundBinaryOperator::Subtraction, Value::Int(left), Value::Int(right)) => integer(left.checked_sub(right)),
+      (BoundBinaryOperator::Multiplication, Value::Int(left), Value::Int(right)) => integer(left.checked_mul(right)),
+      (BoundBinaryOperator::Division, Value::Int(_), Value::Int(0)) => Err(InterpreterError::RuntimeError {
+        span: operator_span,
+        message: "attempted to divide by zero".to_string(),
+      }),
+      (BoundBinaryOperator::Division, Value::Int(left), Value::Int(right)) => integer(left.checked_div(right)),
+      (BoundBinaryOperator::Exponentiation, Value::Int(_), Value::Int(right)) if right < 0 => {
+        Err(InterpreterError::RuntimeError {
+          span: operator_span,
+          message: format!("cannot raise an integer to the negative power `{right}`"),
+        })
+      }
+      (BoundBinaryOperator::Exponentiation, Value::Int(left), Value::Int(right)) => {
+        integer(u32::try_from(right).ok().and_then(|right| left.checked_pow(right)))
+      }
+      (BoundBinaryOperator::Addition, Value::Float(left), Value::Float(right)) => Ok(Value::Float(left + right)),
+      (BoundBinaryOperator::Subtraction, Value::Float(left), Value::Float(right)) => Ok(Value::Float(left - right)),
+      (BoundBinaryOperator::Multiplication, Value::Float(left), Value::Float(right)) => Ok(Value::Float(left * right)),
+      (BoundBinaryOperator::Division, Value::Float(left), Value::Float(right)) => Ok(Value::Float(left / right)),
+      (BoundBinaryOperator::Exponentiation, Value::Float(left), Value::Float(right)) => {
+        Ok(Value::Float(left.powf(right)))
+      }
+      (BoundBinaryOperator::Addition, Value::String(left), Value::String(right)) => {
+        Ok(Value::String(format!("{left}{right}")))
+      }
+      (BoundBinaryOperator::LessThan, Value::Int(left), Value::Int(right)) => Ok(Value::Bool(left < right)),
+      (BoundBinaryOperator::GreaterThan, Value::Int(left), Value::Int(right)) => Ok(Value::Bool(left > right)),
+      (BoundBinaryOperator::LessThanEquals, Value::Int(left), Value::Int(right)) => Ok(Value::Bool(left <= right)),
+      (BoundBinaryOperator::GreaterThanEquals, Value::Int(left), Value::Int(right)) => Ok(Value::Bool(left >= right)),
+      (BoundBinaryOperator::LessThan, Value::Float(left), Value::Float(right)) => Ok(Value::Bool(left < right)),
+      (BoundBinaryOperator::GreaterThan, Value::Float(left), Value::Float(right)) => Ok(Value::Bool(left > right)),
+      (BoundBinaryOperator::LessThanEquals, Value::Float(left), Value::Float(right)) => Ok(Value::Bool(left <= right)),
+      (BoundBinaryOperator::GreaterThanEquals, Value::Float(left), Value::Float(right)) => {
+        Ok(Value::Bool(left >= right))
+      }
+      (BoundBinaryOperator::LessThan, Value::Char(left), Value::Char(right)) => Ok(Value::Bool(left < right)),
+      (BoundBinaryOperator::GreaterThan, Value::Char(left), Value::Char(right)) => Ok(Value::Bool(left > right)),
+      (BoundBinaryOperator::LessThanEquals, Value::Char(left), Value::Char(right)) => Ok(Value::Bool(left <= right)),
+      (BoundBinaryOperator::GreaterThanEquals, Value::Char(left), Value::Char(right)) => Ok(Value::Bool(left >= right)),
+      (BoundBinaryOperator::Equals, left, right) => Ok(Value::Bool(left == right)),
+      (BoundBinaryOperator::NotEquals, left, right) => Ok(Value::Bool(left != right)),
+      (BoundBinaryOperator::LogicalAnd | BoundBinaryOperator::LogicalOr, Value::Bool(_), right @ Value::Bool(_)) => {
+        Ok(right)
+      }
+      _ => Err(InterpreterError::SyntaxError {
+        span: operator_span,
+        message: format!("cannot perform `{:?}` on value", operator),
+      }),
+    }
+  }
+}
+
+impl BoundExpression {
+  /// Checks that `position`, the index at `span`, is that of a character of
+  /// `string`.
+  fn position(string: &str, position: i128, span: Span) -> Result<usize, InterpreterError> {
+    let length = string.chars().count();
+    match usize::try_from(position) {
+      Ok(position) if position < length => Ok(position),
+      _ => Err(InterpreterError::RuntimeError {
+        span,
+        message: format!("index `{position}` is out of bounds for a string of {length} characters"),
+      }),
+    }
+  }
+}
+
+impl BoundPlace {
+  /// Stores `value` in the place. `span` is the assignment, for the error if
+  /// the variable holding the field hasn't been declared or given a value yet.
+  pub fn assign(&self, value: Value, span: Span, environment: &mut Environment) -> Result<(), Interrupt> {
+    // the indices are worked out before the variable is borrowed to change it
+    let mut positions = Vec::new();
+    for step in &self.path {
+      if let BoundPlaceStep::Index(index) = step {
+        let Value::Int(position) = index.evaluate(environment)? else {
+          unreachable!("the binder only allows integer indices")
+        };
+        positions.push((position, index.span()));
+      }
+    }
+    let mut positions = positions.into_iter();
+
+    let Some(slot) = environment.get_mut(&self.variable) else {
+      return Err(
+        InterpreterError::UndeclaredVariable {
+          span,
+          name: self.variable.clone(),
+        }
+        .into(),
+      );
+    };
+    let mut target = match (slot, self.path.is_empty()) {
+      (slot, true) => {
+        *slot = Some(value);
+        return Ok(());
+      }
+      (Some(target), false) => target,
+      (None, false) => {
+        return Err(
+          InterpreterError::UninitializedVariable {
+            span,
+            name: self.variable.clone(),
           }
-          _ => Err(InterpreterError::Other("expected binary expression".to_string())),
+          .into(),
+        );
+      }
+    };
+
+    for step in &self.path {
+      match (step, target) {
+        (BoundPlaceStep::Field(index), Value::Record { fields, .. }) => target = &mut fields[*index].1,
+        (BoundPlaceStep::Index(_), Value::String(string)) => {
+          let (position, span) = positions.next().expect("every index was worked out");
+          let position = BoundExpression::position(string, position, span)?;
+          let Value::Char(replacement) = value else {
+            unreachable!("the binder only puts characters in strings")
+          };
+          *string = (string.chars().enumerate())
+            .map(|(index, character)| if index == position { replacement } else { character })
+            .collect();
+          return Ok(());
         }
+        _ => unreachable!("the binder only allows fields of records and characters of strings"),
       }
-      BoundExpression::Grouping { operand, .. } => operand.evaluate(),
     }
+    *target = value;
+    Ok(())
   }
 }
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use super::{
+  bound_expression::BoundExpression,
+  data_type::Type,
+  environment::Environment,
+  interrupt::Interrupt,
+  value::Value,
+};
+use crate::interpreter::{error::InterpreterError, grammar::span::Span};
+
+#[derive(Debug)]
+pub struct BoundFunction {
+  pub name: String,
+  pub parameters: Vec<BoundParameter>,
+  pub return_type: Type,
+  pub body: BoundExpression,
+  pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct BoundParameter {
+  pub name: String,
+  pub data_type: Type,
+}
+
+impl BoundFunction {
+  /// Runs the body with the parameters bound to `arguments`, which the binder
+  /// has already checked against them. `span` is the call, which errors about
+  /// the call as a whole point at.
+  pub fn call(
+    &self,
+    arguments: Vec<Value>,
+    span: Span,
+    environment: &mut Environment,
+  ) -> Result<Value, InterpreterError> {
+    self
+      .call_with_receiver(arguments, span, environment)
+      .map(|(value, _)| value)
+  }
+
+  /// Like [`call`](Self::call), also giving back the value `self` was left
+  /// with, for a method taking `&mut self` to change what it was called on.
+  pub fn call_with_receiver(
+    &self,
+    arguments: Vec<Value>,
+    span: Span,
+    environment: &mut Environment,
+  ) -> Result<(Value, Option<Value>), InterpreterError> {
+    let variables: HashMap<_, _> = self
+      .parameters
+      .iter()
+      .zip(arguments)
+      .map(|(parameter, argument)| (parameter.name.clone(), Some(argument)))
+      .collect();
+
+    let (value, receiver) = environment
+      .call(variables, |environment| {
+        let value = self.body.evaluate(environment);
+        (value, environment.get("self").cloned())
+      })
+      .ok_or_else(|| InterpreterError::RuntimeError {
+        span,
+        message: format!("stack overflow while calling `{}`", self.name),
+      })?;
+
+    match value {
+      Ok(value) | Err(Interrupt::Return(value)) => Ok((value.fit(&self.return_type, span)?, receiver)),
+      Err(Interrupt::Error(error)) => Err(error),
+      Err(Interrupt::Break(_) | Interrupt::Continue) => {
+        unreachable!("the binder rejects `break` and `continue` outside of a loop")
+      }
+    }
+  }
+}
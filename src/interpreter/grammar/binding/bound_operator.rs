@@ -3,10 +3,12 @@ use crate::interpreter::{
   grammar::{symbol::Symbol, token::SymbolToken},
 };
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BoundUnaryOperator {
   Negation,
   Not,
+  /// `&`, which refers to a value without copying it.
+  Reference,
 }
 
 impl TryFrom<SymbolToken> for BoundUnaryOperator {
@@ -16,26 +18,33 @@ impl TryFrom<SymbolToken> for BoundUnaryOperator {
     match value.symbol {
       Symbol::Minus => Ok(Self::Negation),
       Symbol::ExclamationPoint => Ok(Self::Not),
+      Symbol::Ampersand => Ok(Self::Reference),
       _ => Err(InterpreterError::UnknownOperator {
-        position: value.position,
-        operator: value.symbol.lexeme(),
+        span: value.span,
+        operator: value.symbol.lexeme().into(),
       }),
     }
   }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BoundBinaryOperator {
   Addition,
   Subtraction,
   Multiplication,
   Division,
+  /// `^`, which raises the left operand to the power of the right one.
+  Exponentiation,
   LessThan,
   GreaterThan,
   LessThanEquals,
   GreaterThanEquals,
   Equals,
   NotEquals,
+  /// `&&`, whose right operand only runs if the left one is `true`.
+  LogicalAnd,
+  /// `||`, whose right operand only runs if the left one is `false`.
+  LogicalOr,
 }
 
 impl TryFrom<SymbolToken> for BoundBinaryOperator {
@@ -47,15 +56,18 @@ impl TryFrom<SymbolToken> for BoundBinaryOperator {
       Symbol::Minus => Ok(Self::Subtraction),
       Symbol::Asterisk => Ok(Self::Multiplication),
       Symbol::ForwardSlash => Ok(Self::Division),
+      Symbol::Caret => Ok(Self::Exponentiation),
       Symbol::LeftAngledBracket => Ok(Self::LessThan),
       Symbol::RightAngledBracket => Ok(Self::GreaterThan),
       Symbol::LeftAngledBracketEquals => Ok(Self::LessThanEquals),
       Symbol::RightAngledBracketEquals => Ok(Self::GreaterThanEquals),
       Symbol::DoubleEquals => Ok(Self::Equals),
       Symbol::ExclamationPointEquals => Ok(Self::NotEquals),
+      Symbol::DoubleAmpersand => Ok(Self::LogicalAnd),
+      Symbol::DoublePipe => Ok(Self::LogicalOr),
       _ => Err(InterpreterError::UnknownOperator {
-        position: value.position,
-        operator: value.symbol.lexeme(),
+        span: value.span,
+        operator: value.symbol.lexeme().into(),
       }),
     }
   }
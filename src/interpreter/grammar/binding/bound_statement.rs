@@ -0,0 +1,74 @@
+use super::{
+  bound_expression::BoundExpression,
+  data_type::Type,
+  environment::Environment,
+  interrupt::Interrupt,
+  value::Value,
+};
+use crate::interpreter::grammar::span::Span;
+
+#[derive(Debug, Clone)]
+pub enum BoundStatement {
+  Expression(BoundExpression),
+  Semicolon {
+    expression: BoundExpression,
+    span: Span,
+  },
+  Declaration {
+    name: String,
+    data_type: Type,
+    initializer: Option<BoundExpression>,
+    span: Span,
+  },
+}
+
+impl BoundStatement {
+  pub fn span(&self) -> Span {
+    match self {
+      BoundStatement::Expression(expression) => expression.span(),
+      BoundStatement::Semicolon { span, .. } => *span,
+      BoundStatement::Declaration { span, .. } => *span,
+    }
+  }
+
+  /// The type of a sequence of statements: the type of the last one if it has
+  /// no `;`, and `()` otherwise, unless the last one never finishes, as in
+  /// `{ return x; }`.
+  pub fn sequence_type(statements: &[BoundStatement]) -> Type {
+    match statements.last() {
+      Some(BoundStatement::Expression(expression)) => expression.data_type(),
+      Some(BoundStatement::Semicolon { expression, .. }) if expression.data_type() == Type::Never => Type::Never,
+      _ => Type::VOID,
+    }
+  }
+
+  /// Runs `statements` in order, giving the value of the last one if it has no
+  /// `;`, and `()` otherwise.
+  pub fn evaluate_sequence(statements: &[BoundStatement], environment: &mut Environment) -> Result<Value, Interrupt> {
+    let mut value = Value::Void;
+    for statement in statements {
+      value = match statement {
+        BoundStatement::Expression(expression) => expression.evaluate(environment)?,
+        BoundStatement::Semicolon { expression, .. } => {
+          expression.evaluate(environment)?;
+          Value::Void
+        }
+        BoundStatement::Declaration {
+          name,
+          data_type,
+          initializer,
+          ..
+        } => {
+          let value = match initializer {
+            Some(initializer) => Some(initializer.evaluate(environment)?.fit(data_type, initializer.span())?),
+            None => None,
+          };
+          environment.declare(name, value);
+          Value::Void
+        }
+      };
+    }
+
+    Ok(value)
+  }
+}
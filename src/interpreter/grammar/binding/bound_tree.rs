@@ -0,0 +1,61 @@
+use std::rc::Rc;
+
+use super::{
+  bound_function::BoundFunction,
+  bound_statement::BoundStatement,
+  data_type::TypeId,
+  environment::Environment,
+  interrupt::Interrupt,
+  value::Value,
+};
+use crate::interpreter::error::InterpreterError;
+
+/// A type checked program, ready to run.
+#[derive(Debug)]
+pub struct BoundTree {
+  pub functions: Vec<Rc<BoundFunction>>,
+  /// The methods and associated functions declared for each type.
+  pub methods: Vec<(TypeId, Rc<BoundFunction>)>,
+  pub statements: Vec<BoundStatement>,
+}
+
+impl BoundTree {
+  /// The function a program starts in once its top-level statements have run.
+  pub const ENTRY: &'static str = "entry";
+
+  /// Runs the program with its top-level variables and functions declared in
+  /// `environment`, where they stay for any program run after it. If the
+  /// program declares an `entry` function taking no arguments, that is called
+  /// last and gives the program its value.
+  pub fn evaluate(&self, environment: &mut Environment) -> Result<Value, InterpreterError> {
+    self.define(environment);
+
+    let value = match BoundStatement::evaluate_sequence(&self.statements, environment) {
+      Ok(value) => value,
+      Err(Interrupt::Error(error)) => return Err(error),
+      Err(Interrupt::Return(_) | Interrupt::Break(_) | Interrupt::Continue) => {
+        unreachable!("the binder rejects `return`, `break` and `continue` outside of a function or loop")
+      }
+    };
+
+    match self
+      .functions
+      .iter()
+      .find(|function| function.name == Self::ENTRY && function.parameters.is_empty())
+    {
+      Some(entry) => entry.call(Vec::new(), entry.span, environment),
+      None => Ok(value),
+    }
+  }
+
+  /// Declares the functions and methods of the program in `environment`
+  /// without running it, as is done for the modules a program imports.
+  pub fn define(&self, environment: &mut Environment) {
+    for function in &self.functions {
+      environment.define_function(function.clone());
+    }
+    for (owner, method) in &self.methods {
+      environment.define_method(owner.clone(), method.clone());
+    }
+  }
+}
@@ -1,13 +1,330 @@
-use std::any::TypeId;
+use std::{collections::HashMap, fmt::Display, ops::RangeInclusive, rc::Rc};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Type {
   Primitive(Primitive),
   UserDefined(TypeId),
+  /// A generic type given its arguments, such as `Pair<i64, string>`.
+  Instance {
+    id: TypeId,
+    arguments: Vec<TypeArgument>,
+  },
+  /// `&T`, a reference to a `T`. A reference to a trait, such as `&Speak`,
+  /// refers to a value of any type implementing it.
+  Reference(Box<Type>),
+  /// A type parameter, standing for whichever type a use of a generic
+  /// function or type gives it.
+  Parameter(Rc<TypeParameter>),
+  /// The type of expressions such as `return` that never produce a value, so
+  /// they fit wherever a value is expected.
+  Never,
+  Unknown,
 }
 
-#[derive(Debug, PartialEq)]
+/// Identifies a type declared in the program, such as an enum. Types are told
+/// apart by `index`, as a type may be declared again under the same name; the
+/// name is only kept for messages.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeId {
+  pub index: usize,
+  pub name: Rc<str>,
+}
+
+/// A type parameter such as the `T` of `show<T: Speak>`, or the `Self` of a
+/// trait, which stands for the type implementing the trait.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeParameter {
+  pub name: String,
+  /// The traits the types given to the parameter have to implement.
+  pub bounds: Vec<TypeId>,
+  /// The type of a const parameter, such as the `u64` of `N: const u64`,
+  /// which is given an integer rather than a type.
+  pub const_type: Option<Type>,
+}
+
+/// What a generic parameter is given: a type, or the integer of a const
+/// parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeArgument {
+  Type(Type),
+  Const(i128),
+}
+
+/// What the binder knows about a type declared in the program.
+#[derive(Debug, Clone)]
+pub enum TypeDefinition {
+  Enum {
+    variants: Vec<String>,
+  },
+  Record {
+    /// The type parameters of a generic record, which its fields can use.
+    generics: Vec<Rc<TypeParameter>>,
+    fields: Vec<FieldDefinition>,
+  },
+  /// A trait, which can only be used as a type behind a reference.
+  Trait,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldDefinition {
+  pub name: String,
+  pub data_type: Type,
+  /// Whether modules other than the one declaring the type can use the field.
+  pub public: bool,
+}
+
+impl TypeDefinition {
+  /// The index and definition of the field called `name`, if this is a record
+  /// with such a field.
+  pub fn field(&self, name: &str) -> Option<(usize, &FieldDefinition)> {
+    match self {
+      TypeDefinition::Record { fields, .. } => fields.iter().enumerate().find(|(_, field)| field.name == name),
+      TypeDefinition::Enum { .. } | TypeDefinition::Trait => None,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Primitive {
   Float,
+  /// An integer without a suffix. It takes on the width of any sized integer it
+  /// is combined with, and behaves like an `i64` on its own.
   Int,
+  I8,
+  I16,
+  I32,
+  I64,
+  U8,
+  U16,
+  U32,
+  U64,
+  Bool,
+  Char,
+  String,
+  Void,
+}
+
+impl Type {
+  pub const BOOL: Type = Type::Primitive(Primitive::Bool);
+  pub const CHAR: Type = Type::Primitive(Primitive::Char);
+  pub const FLOAT: Type = Type::Primitive(Primitive::Float);
+  pub const INT: Type = Type::Primitive(Primitive::Int);
+  pub const STRING: Type = Type::Primitive(Primitive::String);
+  pub const VOID: Type = Type::Primitive(Primitive::Void);
+
+  pub fn is_numeric(&self) -> bool {
+    matches!(self, Type::Primitive(primitive) if primitive.is_integer() || *primitive == Primitive::Float)
+  }
+
+  pub fn is_integer(&self) -> bool {
+    matches!(self, Type::Primitive(primitive) if primitive.is_integer())
+  }
+
+  pub fn integer_range(&self) -> Option<RangeInclusive<i128>> {
+    match self {
+      Type::Primitive(primitive) => primitive.integer_range(),
+      _ => None,
+    }
+  }
+
+  /// The type a reference refers to, or the type itself if it isn't one.
+  pub fn dereferenced(&self) -> &Type {
+    match self {
+      Type::Reference(inner) => inner.dereferenced(),
+      data_type => data_type,
+    }
+  }
+
+  /// The declared type this is, or is an instance of.
+  pub fn declared(&self) -> Option<&TypeId> {
+    match self {
+      Type::UserDefined(id) | Type::Instance { id, .. } => Some(id),
+      _ => None,
+    }
+  }
+
+  /// Replaces the type parameters named in `bindings` with what they are
+  /// given.
+  pub fn substitute(&self, bindings: &HashMap<String, TypeArgument>) -> Type {
+    match self {
+      Type::Parameter(parameter) => match bindings.get(&parameter.name) {
+        Some(TypeArgument::Type(data_type)) => data_type.clone(),
+        _ => self.clone(),
+      },
+      Type::Reference(inner) => Type::Reference(Box::new(inner.substitute(bindings))),
+      Type::Instance { id, arguments } => Type::Instance {
+        id: id.clone(),
+        arguments: arguments.iter().map(|argument| argument.substitute(bindings)).collect(),
+      },
+      _ => self.clone(),
+    }
+  }
+
+  /// Whether a value of type `value` can be stored where a `self` is
+  /// expected. An unsuffixed `int` fits any sized integer, which is checked
+  /// against the range of the integer when the value is stored.
+  pub fn accepts(&self, value: &Type) -> bool {
+    self == value
+      || *self == Type::Unknown
+      || matches!(value, Type::Unknown | Type::Never)
+      || (*value == Type::INT && self.is_integer())
+      || match (self, value) {
+        (Type::Reference(expected), Type::Reference(value)) => expected.accepts(value),
+        (
+          Type::Instance { id, arguments },
+          Type::Instance {
+            id: value_id,
+            arguments: value_arguments,
+          },
+        ) => {
+          id == value_id
+            && arguments.len() == value_arguments.len()
+            && arguments.iter().zip(value_arguments).all(|arguments| match arguments {
+              (TypeArgument::Type(expected), TypeArgument::Type(value)) => expected.accepts(value),
+              (expected, value) => expected == value,
+            })
+        }
+        _ => false,
+      }
+  }
+
+  /// The type of a value that is either an `a` or a `b`, such as the value of
+  /// an `if` with an `else`. Gives `None` if neither fits where the other is
+  /// expected.
+  pub fn join(a: &Type, b: &Type) -> Option<Type> {
+    match (a, b) {
+      (Type::Never, other) | (other, Type::Never) => Some(other.clone()),
+      _ if a.accepts(b) => Some(a.clone()),
+      _ if b.accepts(a) => Some(b.clone()),
+      _ => None,
+    }
+  }
+
+  /// Numeric promotion for mixed operands:
+  /// - an unsuffixed `int` adopts the width of a sized integer on the other
+  ///   side,
+  /// - two sized integers must have the same width,
+  /// - if either side is `float`, the other side is widened to `float` as well.
+  ///
+  /// Returns `None` when either operand is not numeric or the widths disagree.
+  pub fn promote(left: &Type, right: &Type) -> Option<Type> {
+    match (left, right) {
+      (Type::Primitive(Primitive::Int), right) if right.is_integer() => Some(right.clone()),
+      (left, Type::Primitive(Primitive::Int)) if left.is_integer() => Some(left.clone()),
+      (left, right) if left.is_integer() && right.is_integer() => (left == right).then(|| left.clone()),
+      (left, right) if left.is_numeric() && right.is_numeric() => Some(Type::FLOAT),
+      _ => None,
+    }
+  }
+}
+
+impl TypeArgument {
+  /// Replaces the type parameters named in `bindings` with what they are
+  /// given, which is how a const parameter is given its integer.
+  pub fn substitute(&self, bindings: &HashMap<String, TypeArgument>) -> TypeArgument {
+    match self {
+      TypeArgument::Type(Type::Parameter(parameter)) => {
+        bindings.get(&parameter.name).cloned().unwrap_or_else(|| self.clone())
+      }
+      TypeArgument::Type(data_type) => TypeArgument::Type(data_type.substitute(bindings)),
+      TypeArgument::Const(_) => self.clone(),
+    }
+  }
+}
+
+impl Display for TypeArgument {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      TypeArgument::Type(data_type) => write!(f, "{data_type}"),
+      TypeArgument::Const(value) => write!(f, "{value}"),
+    }
+  }
+}
+
+impl Primitive {
+  pub fn from_suffix(suffix: &str) -> Option<Primitive> {
+    match suffix {
+      "i8" => Some(Primitive::I8),
+      "i16" => Some(Primitive::I16),
+      "i32" => Some(Primitive::I32),
+      "i64" => Some(Primitive::I64),
+      "u8" => Some(Primitive::U8),
+      "u16" => Some(Primitive::U16),
+      "u32" => Some(Primitive::U32),
+      "u64" => Some(Primitive::U64),
+      _ => None,
+    }
+  }
+
+  /// The primitive a type name such as `i32` or `string` refers to.
+  pub fn from_name(name: &str) -> Option<Primitive> {
+    match name {
+      "float" => Some(Primitive::Float),
+      "bool" => Some(Primitive::Bool),
+      "char" => Some(Primitive::Char),
+      "string" => Some(Primitive::String),
+      _ => Self::from_suffix(name),
+    }
+  }
+
+  pub fn is_integer(&self) -> bool {
+    self.integer_range().is_some()
+  }
+
+  pub fn is_signed(&self) -> bool {
+    self.integer_range().is_some_and(|range| *range.start() < 0)
+  }
+
+  /// The values an integer of this type can hold, or `None` for non-integers.
+  pub fn integer_range(&self) -> Option<RangeInclusive<i128>> {
+    match self {
+      Primitive::Int | Primitive::I64 => Some(i64::MIN as i128..=i64::MAX as i128),
+      Primitive::I8 => Some(i8::MIN as i128..=i8::MAX as i128),
+      Primitive::I16 => Some(i16::MIN as i128..=i16::MAX as i128),
+      Primitive::I32 => Some(i32::MIN as i128..=i32::MAX as i128),
+      Primitive::U8 => Some(0..=u8::MAX as i128),
+      Primitive::U16 => Some(0..=u16::MAX as i128),
+      Primitive::U32 => Some(0..=u32::MAX as i128),
+      Primitive::U64 => Some(0..=u64::MAX as i128),
+      _ => None,
+    }
+  }
+}
+
+impl Display for Type {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Type::Primitive(primitive) => write!(f, "{primitive}"),
+      Type::UserDefined(id) => write!(f, "{}", id.name),
+      Type::Instance { id, arguments } => {
+        let arguments: Vec<_> = arguments.iter().map(ToString::to_string).collect();
+        write!(f, "{}<{}>", id.name, arguments.join(", "))
+      }
+      Type::Reference(inner) => write!(f, "&{inner}"),
+      Type::Parameter(parameter) => write!(f, "{}", parameter.name),
+      Type::Never => write!(f, "!"),
+      Type::Unknown => write!(f, "{{unknown}}"),
+    }
+  }
+}
+
+impl Display for Primitive {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Primitive::Float => write!(f, "float"),
+      Primitive::Int => write!(f, "{{integer}}"),
+      Primitive::I8 => write!(f, "i8"),
+      Primitive::I16 => write!(f, "i16"),
+      Primitive::I32 => write!(f, "i32"),
+      Primitive::I64 => write!(f, "i64"),
+      Primitive::U8 => write!(f, "u8"),
+      Primitive::U16 => write!(f, "u16"),
+      Primitive::U32 => write!(f, "u32"),
+      Primitive::U64 => write!(f, "u64"),
+      Primitive::Bool => write!(f, "bool"),
+      Primitive::Char => write!(f, "char"),
+      Primitive::String => write!(f, "string"),
+      Primitive::Void => write!(f, "()"),
+    }
+  }
 }
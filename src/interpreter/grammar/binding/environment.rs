@@ -0,0 +1,133 @@
+use std::{collections::HashMap, rc::Rc};
+
+use super::{bound_function::BoundFunction, data_type::TypeId, value::Value};
+
+/// The variables of a running program, innermost scope last. A variable that
+/// was declared without a value maps to `None` until it is given one.
+#[derive(Debug)]
+pub struct Environment {
+  scopes: Vec<HashMap<String, Option<Value>>>,
+  functions: HashMap<String, Rc<BoundFunction>>,
+  /// The methods and associated functions of each type, by type and name.
+  methods: HashMap<(TypeId, String), Rc<BoundFunction>>,
+  /// How many function calls are running.
+  depth: usize,
+  /// Where the stack of the thread running the program starts, and how much
+  /// of it calls can take, if known.
+  stack: Option<(usize, usize)>,
+}
+
+impl Default for Environment {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Environment {
+  /// How deep calls can nest before the interpreter gives up on the program,
+  /// rather than overflowing its own stack. The thread running the program
+  /// needs [`Interpreter::STACK_SIZE`](crate::interpreter::Interpreter::STACK_SIZE)
+  /// of stack for that many calls.
+  pub const MAX_CALL_DEPTH: usize = 4096;
+
+  pub fn new() -> Self {
+    Self {
+      scopes: vec![HashMap::new()],
+      functions: HashMap::new(),
+      methods: HashMap::new(),
+      depth: 0,
+      stack: None,
+    }
+  }
+
+  /// Lets calls take no more than `size` of the stack from where this is
+  /// called, which should be near the start of the thread running programs.
+  /// How much a call takes depends on what it runs, so
+  /// [`MAX_CALL_DEPTH`](Self::MAX_CALL_DEPTH) alone can't keep a program from
+  /// overflowing the stack.
+  pub fn limit_stack(&mut self, size: usize) {
+    self.stack = Some((Self::stack_address(), size));
+  }
+
+  /// An address on the stack of the calling thread, to tell how much of the
+  /// stack is in use.
+  #[inline(never)]
+  fn stack_address() -> usize {
+    let marker = 0u8;
+    std::hint::black_box(&marker) as *const u8 as usize
+  }
+
+  pub fn push_scope(&mut self) {
+    self.scopes.push(HashMap::new());
+  }
+
+  /// Drops the innermost scope and every variable declared in it. The
+  /// outermost scope lives as long as the environment.
+  pub fn pop_scope(&mut self) {
+    if self.scopes.len() > 1 {
+      self.scopes.pop();
+    }
+  }
+
+  /// Declares `name` in the innermost scope, shadowing any variable of the
+  /// same name.
+  pub fn declare(&mut self, name: impl Into<String>, value: Option<Value>) {
+    self
+      .scopes
+      .last_mut()
+      .expect("there is always an outermost scope")
+      .insert(name.into(), value);
+  }
+
+  /// The value of the innermost variable called `name`, or `None` if it
+  /// hasn't been given one.
+  pub fn get(&self, name: &str) -> Option<&Value> {
+    self
+      .scopes
+      .iter()
+      .rev()
+      .find_map(|scope| scope.get(name))
+      .and_then(Option::as_ref)
+  }
+
+  /// Where the innermost variable called `name` keeps its value, for giving
+  /// it a new one.
+  pub fn get_mut(&mut self, name: &str) -> Option<&mut Option<Value>> {
+    self.scopes.iter_mut().rev().find_map(|scope| scope.get_mut(name))
+  }
+
+  pub fn define_function(&mut self, function: Rc<BoundFunction>) {
+    self.functions.insert(function.name.clone(), function);
+  }
+
+  pub fn function(&self, name: &str) -> Option<Rc<BoundFunction>> {
+    self.functions.get(name).cloned()
+  }
+
+  pub fn define_method(&mut self, owner: TypeId, function: Rc<BoundFunction>) {
+    self.methods.insert((owner, function.name.clone()), function);
+  }
+
+  pub fn method(&self, owner: &TypeId, name: &str) -> Option<Rc<BoundFunction>> {
+    self.methods.get(&(owner.clone(), name.to_string())).cloned()
+  }
+
+  /// Runs `body` with only `variables` in scope, so that a function can't see
+  /// the variables of whoever called it. Gives `None` without running `body`
+  /// if [`MAX_CALL_DEPTH`](Self::MAX_CALL_DEPTH) calls are already running, or
+  /// if they have taken the stack they are allowed.
+  pub fn call<T>(&mut self, variables: HashMap<String, Option<Value>>, body: impl FnOnce(&mut Self) -> T) -> Option<T> {
+    let out_of_stack = (self.stack).is_some_and(|(start, size)| start.abs_diff(Self::stack_address()) > size);
+    if self.depth >= Self::MAX_CALL_DEPTH || out_of_stack {
+      return None;
+    }
+
+    let caller = std::mem::replace(&mut self.scopes, vec![variables]);
+    self.depth += 1;
+    let result = body(self);
+    self.depth -= 1;
+    self.scopes = caller;
+
+    Some(result)
+  }
+}
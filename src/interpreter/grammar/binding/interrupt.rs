@@ -0,0 +1,19 @@
+use super::value::Value;
+use crate::interpreter::error::InterpreterError;
+
+/// Why evaluation left an expression before finishing it: an error, or a
+/// `return`, `break` or `continue` on its way out to the function or loop it
+/// leaves.
+#[derive(Debug)]
+pub enum Interrupt {
+  Error(InterpreterError),
+  Return(Value),
+  Break(Value),
+  Continue,
+}
+
+impl From<InterpreterError> for Interrupt {
+  fn from(error: InterpreterError) -> Self {
+    Interrupt::Error(error)
+  }
+}
@@ -0,0 +1,83 @@
+use std::{fmt::Display, rc::Rc};
+
+use super::data_type::{Type, TypeId};
+use crate::interpreter::{error::InterpreterError, grammar::span::Span};
+
+/// A runtime value produced by evaluating a
+/// [`BoundExpression`](super::bound_expression::BoundExpression).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+  Int(i128),
+  Float(f64),
+  Bool(bool),
+  Char(char),
+  String(String),
+  /// A variant of an enum, such as `logic.true`.
+  Enum {
+    data_type: TypeId,
+    variant: Rc<str>,
+  },
+  /// An instance of a record type, with its fields in the order the type
+  /// declares them. Records are values, so assigning one copies it.
+  Record {
+    data_type: TypeId,
+    fields: Vec<(Rc<str>, Value)>,
+  },
+  Void,
+}
+
+impl Value {
+  /// The declared type of an enum or record value, which is what a method
+  /// called through a trait is looked up on.
+  pub fn type_id(&self) -> Option<&TypeId> {
+    match self {
+      Value::Enum { data_type, .. } | Value::Record { data_type, .. } => Some(data_type),
+      _ => None,
+    }
+  }
+
+  /// Widens an `int` to a `float`, leaving every other value untouched.
+  /// This is the runtime half of
+  /// [`Type::promote`](super::data_type::Type::promote).
+  pub fn promoted(self) -> Value {
+    match self {
+      Value::Int(value) => Value::Float(value as f64),
+      value => value,
+    }
+  }
+
+  /// Checks that an integer stored where a `data_type` is expected fits in
+  /// it, as an unsuffixed `int` is only known to fit in an `i64`. `span` is
+  /// where the value came from.
+  pub fn fit(self, data_type: &Type, span: Span) -> Result<Value, InterpreterError> {
+    match (&self, data_type.integer_range()) {
+      (Value::Int(integer), Some(range)) if !range.contains(integer) => Err(InterpreterError::RuntimeError {
+        span,
+        message: format!("`{integer}` is out of range for `{data_type}`"),
+      }),
+      _ => Ok(self),
+    }
+  }
+}
+
+impl Display for Value {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Value::Int(value) => write!(f, "{value}"),
+      Value::Float(value) => write!(f, "{value:?}"),
+      Value::Bool(value) => write!(f, "{value}"),
+      Value::Char(value) => write!(f, "{value}"),
+      Value::String(value) => write!(f, "{value}"),
+      Value::Enum { data_type, variant } => write!(f, "{}.{variant}", data_type.name),
+      Value::Record { data_type, fields } => {
+        write!(f, "{} {{", data_type.name)?;
+        for (index, (name, value)) in fields.iter().enumerate() {
+          let separator = if index == 0 { " " } else { ", " };
+          write!(f, "{separator}{name} = {value}")?;
+        }
+        write!(f, "{}}}", if fields.is_empty() { "" } else { " " })
+      }
+      Value::Void => write!(f, "()"),
+    }
+  }
+}
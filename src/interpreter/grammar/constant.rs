@@ -0,0 +1,24 @@
+use super::{expression::Expression, span::Span, token::LiteralToken, type_expression::TypeExpression};
+
+/// A constant declared as `SUCCESS: export const i32 = 0;`. Its value is
+/// worked out wherever it is used, so functions can use it as well.
+#[derive(Debug, PartialEq)]
+pub struct Constant {
+  pub name: LiteralToken,
+  /// Whether the constant was declared `export`, for other modules to use.
+  pub exported: bool,
+  /// The type before the `=`, if there is one.
+  pub data_type: Option<TypeExpression>,
+  pub value: Expression,
+  pub span: Span,
+}
+
+impl Constant {
+  pub fn pretty_print(&self, indent: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match &self.data_type {
+      Some(data_type) => writeln!(f, "{:indent$}Constant: {} : {data_type}", "", self.name.literal.lexeme())?,
+      None => writeln!(f, "{:indent$}Constant: {}", "", self.name.literal.lexeme())?,
+    }
+    self.value.pretty_print(indent + 2, f)
+  }
+}
@@ -0,0 +1,24 @@
+use super::{span::Span, token::LiteralToken};
+
+/// A C-like enum declared as `logic: enum = { false, true }`.
+#[derive(Debug, PartialEq)]
+pub struct Enum {
+  pub name: LiteralToken,
+  /// Whether the enum was declared `export`, for other modules to use.
+  pub exported: bool,
+  pub variants: Vec<LiteralToken>,
+  pub span: Span,
+}
+
+impl Enum {
+  pub fn pretty_print(&self, indent: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let variants: Vec<_> = self.variants.iter().map(|variant| variant.literal.lexeme()).collect();
+    writeln!(
+      f,
+      "{:indent$}Enum: {} {{ {} }}",
+      "",
+      self.name.literal.lexeme(),
+      variants.join(", ")
+    )
+  }
+}
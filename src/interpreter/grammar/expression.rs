@@ -1,27 +1,177 @@
 use std::fmt::Display;
 
-use super::token::{LiteralToken, SymbolToken};
+use super::{
+  generic::GenericArgument,
+  record::FieldInitializer,
+  span::Span,
+  statement::Statement,
+  token::{LiteralToken, SymbolToken},
+};
 
 #[derive(Debug, PartialEq)]
 pub enum Expression {
   Literal {
     token: LiteralToken,
   },
+  /// A name referring to a variable in scope.
+  Variable {
+    name: LiteralToken,
+  },
   Unary {
     operator: SymbolToken,
     operand: Box<Expression>,
+    span: Span,
   },
   Binary {
     operator: SymbolToken,
     left_operand: Box<Expression>,
     right_operand: Box<Expression>,
+    span: Span,
   },
   Grouping {
     operand: Box<Expression>,
+    span: Span,
+  },
+  Interpolation {
+    parts: Vec<InterpolationPart>,
+    span: Span,
+  },
+  Block {
+    statements: Vec<Statement>,
+    span: Span,
+  },
+  /// `target = value`, or a compound assignment such as `target += value`,
+  /// whose `operator` is the `+=`.
+  Assign {
+    target: Box<Expression>,
+    operator: Option<SymbolToken>,
+    value: Box<Expression>,
+    span: Span,
+  },
+  /// `VTuber { name = value, real_name }`, or `{ name = value, ... }` where
+  /// the type is known from where the record goes.
+  Record {
+    name: Option<LiteralToken>,
+    /// The arguments of a generic type, as in `Pair::<i32, string> { ... }`.
+    arguments: Vec<GenericArgument>,
+    fields: Vec<FieldInitializer>,
+    span: Span,
+  },
+  /// A generic function or type given its arguments, such as the
+  /// `array::<i32>` of `array::<i32>.new()`.
+  Generic {
+    target: Box<Expression>,
+    arguments: Vec<GenericArgument>,
+    span: Span,
+  },
+  /// `object.member`, such as the variant of an enum in `logic.true`.
+  Member {
+    object: Box<Expression>,
+    member: LiteralToken,
+    span: Span,
+  },
+  /// `object[index]`, an element of what `object` holds.
+  Index {
+    object: Box<Expression>,
+    index: Box<Expression>,
+    span: Span,
+  },
+  Call {
+    callee: Box<Expression>,
+    arguments: Vec<Expression>,
+    span: Span,
+  },
+  /// `return`, with or without a value, leaving the enclosing function.
+  Return {
+    value: Option<Box<Expression>>,
+    span: Span,
+  },
+  /// `if condition { ... }`, where an `else if` is an `If` in `else_branch`.
+  If {
+    condition: Box<Expression>,
+    then_branch: Box<Expression>,
+    else_branch: Option<Box<Expression>>,
+    span: Span,
+  },
+  While {
+    condition: Box<Expression>,
+    body: Box<Expression>,
+    span: Span,
+  },
+  Loop {
+    body: Box<Expression>,
+    span: Span,
   },
+  /// `for variable in iterable { ... }`.
+  For {
+    variable: LiteralToken,
+    iterable: Box<Expression>,
+    body: Box<Expression>,
+    span: Span,
+  },
+  /// `start..end`, or `start..=end` when `inclusive`.
+  Range {
+    start: Box<Expression>,
+    end: Box<Expression>,
+    inclusive: bool,
+    span: Span,
+  },
+  /// `break`, with a value for the `loop` it leaves or without one.
+  Break {
+    value: Option<Box<Expression>>,
+    span: Span,
+  },
+  Continue {
+    span: Span,
+  },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InterpolationPart {
+  Text(String),
+  Expression(Expression),
 }
 
 impl Expression {
+  pub fn span(&self) -> Span {
+    match self {
+      Expression::Literal { token } => token.span,
+      Expression::Variable { name } => name.span,
+      Expression::Unary { span, .. } => *span,
+      Expression::Binary { span, .. } => *span,
+      Expression::Grouping { span, .. } => *span,
+      Expression::Interpolation { span, .. } => *span,
+      Expression::Block { span, .. } => *span,
+      Expression::Assign { span, .. } => *span,
+      Expression::Record { span, .. } => *span,
+      Expression::Generic { span, .. } => *span,
+      Expression::Member { span, .. } => *span,
+      Expression::Index { span, .. } => *span,
+      Expression::Call { span, .. } => *span,
+      Expression::Return { span, .. } => *span,
+      Expression::If { span, .. } => *span,
+      Expression::While { span, .. } => *span,
+      Expression::Loop { span, .. } => *span,
+      Expression::For { span, .. } => *span,
+      Expression::Range { span, .. } => *span,
+      Expression::Break { span, .. } => *span,
+      Expression::Continue { span } => *span,
+    }
+  }
+
+  /// Block-like expressions end in a `}`, and can stand as statements without
+  /// a `;` after them.
+  pub fn is_block_like(&self) -> bool {
+    matches!(
+      self,
+      Expression::Block { .. }
+        | Expression::If { .. }
+        | Expression::While { .. }
+        | Expression::Loop { .. }
+        | Expression::For { .. }
+    )
+  }
+
   // pub fn evaluate(&self) -> Result<Box<dyn Any>, InterpreterError> {
   //   match self {
   //     Expression::Literal { token } => match token.literal.clone() {
@@ -103,7 +253,10 @@ impl Expression {
       Expression::Literal { token } => {
         writeln!(f, "Literal: {token}")?;
       }
-      Expression::Unary { operator, operand } => {
+      Expression::Variable { name } => {
+        writeln!(f, "Variable: {}", name.literal.lexeme())?;
+      }
+      Expression::Unary { operator, operand, .. } => {
         writeln!(f, "Unary: {operator}")?;
         operand.pretty_print(indent + INCREMENT, f)?;
       }
@@ -111,15 +264,145 @@ impl Expression {
         operator,
         left_operand,
         right_operand,
+        ..
       } => {
         writeln!(f, "Binary: {operator}")?;
         left_operand.pretty_print(indent + INCREMENT, f)?;
         right_operand.pretty_print(indent + INCREMENT, f)?;
       }
-      Expression::Grouping { operand } => {
+      Expression::Grouping { operand, .. } => {
         writeln!(f, "Grouping")?;
         operand.pretty_print(indent + INCREMENT, f)?;
       }
+      Expression::Interpolation { parts, .. } => {
+        writeln!(f, "Interpolation")?;
+        for part in parts {
+          match part {
+            InterpolationPart::Text(text) => writeln!(f, "{:indent$}Text: {text:?}", "", indent = indent + INCREMENT)?,
+            InterpolationPart::Expression(expression) => expression.pretty_print(indent + INCREMENT, f)?,
+          }
+        }
+      }
+      Expression::Block { statements, .. } => {
+        writeln!(f, "Block")?;
+        for statement in statements {
+          statement.pretty_print(indent + INCREMENT, f)?;
+        }
+      }
+      Expression::Assign {
+        target,
+        operator,
+        value,
+        ..
+      } => {
+        match operator {
+          Some(operator) => writeln!(f, "Assign {}", operator.symbol.lexeme())?,
+          None => writeln!(f, "Assign")?,
+        }
+        target.pretty_print(indent + INCREMENT, f)?;
+        value.pretty_print(indent + INCREMENT, f)?;
+      }
+      Expression::Record {
+        name,
+        arguments,
+        fields,
+        ..
+      } => {
+        match name {
+          Some(name) => {
+            write!(f, "Record: {}", name.literal.lexeme())?;
+            GenericArgument::write_list(arguments, f)?;
+            writeln!(f)?;
+          }
+          None => writeln!(f, "Record")?,
+        }
+        for field in fields {
+          writeln!(
+            f,
+            "{:indent$}{} =",
+            "",
+            field.name.literal.lexeme(),
+            indent = indent + INCREMENT
+          )?;
+          field.value.pretty_print(indent + INCREMENT * 2, f)?;
+        }
+      }
+      Expression::Generic { target, arguments, .. } => {
+        write!(f, "Generic: ")?;
+        GenericArgument::write_list(arguments, f)?;
+        writeln!(f)?;
+        target.pretty_print(indent + INCREMENT, f)?;
+      }
+      Expression::Member { object, member, .. } => {
+        writeln!(f, "Member: {}", member.literal.lexeme())?;
+        object.pretty_print(indent + INCREMENT, f)?;
+      }
+      Expression::Index { object, index, .. } => {
+        writeln!(f, "Index")?;
+        object.pretty_print(indent + INCREMENT, f)?;
+        index.pretty_print(indent + INCREMENT, f)?;
+      }
+      Expression::Call { callee, arguments, .. } => {
+        writeln!(f, "Call")?;
+        callee.pretty_print(indent + INCREMENT, f)?;
+        for argument in arguments {
+          argument.pretty_print(indent + INCREMENT, f)?;
+        }
+      }
+      Expression::Return { value, .. } => {
+        writeln!(f, "Return")?;
+        if let Some(value) = value {
+          value.pretty_print(indent + INCREMENT, f)?;
+        }
+      }
+      Expression::If {
+        condition,
+        then_branch,
+        else_branch,
+        ..
+      } => {
+        writeln!(f, "If")?;
+        condition.pretty_print(indent + INCREMENT, f)?;
+        then_branch.pretty_print(indent + INCREMENT, f)?;
+        if let Some(else_branch) = else_branch {
+          else_branch.pretty_print(indent + INCREMENT, f)?;
+        }
+      }
+      Expression::While { condition, body, .. } => {
+        writeln!(f, "While")?;
+        condition.pretty_print(indent + INCREMENT, f)?;
+        body.pretty_print(indent + INCREMENT, f)?;
+      }
+      Expression::Loop { body, .. } => {
+        writeln!(f, "Loop")?;
+        body.pretty_print(indent + INCREMENT, f)?;
+      }
+      Expression::For {
+        variable,
+        iterable,
+        body,
+        ..
+      } => {
+        writeln!(f, "For: {}", variable.literal.lexeme())?;
+        iterable.pretty_print(indent + INCREMENT, f)?;
+        body.pretty_print(indent + INCREMENT, f)?;
+      }
+      Expression::Range {
+        start, end, inclusive, ..
+      } => {
+        writeln!(f, "Range: {}", if *inclusive { "..=" } else { ".." })?;
+        start.pretty_print(indent + INCREMENT, f)?;
+        end.pretty_print(indent + INCREMENT, f)?;
+      }
+      Expression::Break { value, .. } => {
+        writeln!(f, "Break")?;
+        if let Some(value) = value {
+          value.pretty_print(indent + INCREMENT, f)?;
+        }
+      }
+      Expression::Continue { .. } => {
+        writeln!(f, "Continue")?;
+      }
     }
 
     Ok(())
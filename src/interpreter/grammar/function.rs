@@ -0,0 +1,95 @@
+use std::fmt::Display;
+
+use super::{
+  expression::Expression,
+  generic::GenericParameter,
+  span::Span,
+  token::{DocCommentToken, LiteralToken},
+  type_expression::TypeExpression,
+};
+
+/// A function declared as `foo: (a: i32) -> core.string = { ... }`, or a
+/// method of a type when declared in its body or an `impl`.
+#[derive(Debug, PartialEq)]
+pub struct Function {
+  /// The `///` doc comments written above a method. Those of a function
+  /// declared at the top level are on its [`Item`](super::item::Item).
+  pub docs: Vec<DocCommentToken>,
+  pub name: LiteralToken,
+  pub generics: Vec<GenericParameter>,
+  /// Whether the function was declared `export`, `pub` or `public`, for other
+  /// modules to use.
+  pub exported: bool,
+  /// The `self` of a method, which then has to be called on a value of its
+  /// type.
+  pub receiver: Option<Receiver>,
+  pub parameters: Vec<Parameter>,
+  /// The type after the `->`, if there is one. Functions without one return
+  /// `()`.
+  pub return_type: Option<TypeExpression>,
+  /// The body, which only the required methods of a trait leave out.
+  pub body: Option<Expression>,
+  pub span: Span,
+}
+
+/// The first parameter of a method: `self`, `&self` or `&mut self`, which
+/// may also be given a type as in `self: &Vtuber`.
+#[derive(Debug, PartialEq)]
+pub struct Receiver {
+  pub kind: ReceiverKind,
+  pub data_type: Option<TypeExpression>,
+  pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiverKind {
+  /// `self`, a copy of the value the method is called on.
+  Value,
+  /// `&self`, which can read the value but not change it.
+  Reference,
+  /// `&mut self`, whose changes are kept by the value the method is called on.
+  MutableReference,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Parameter {
+  pub name: LiteralToken,
+  pub data_type: TypeExpression,
+  pub span: Span,
+}
+
+impl Function {
+  pub fn pretty_print(&self, indent: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let receiver = self.receiver.as_ref().map(|receiver| receiver.kind.to_string());
+    let parameters: Vec<_> = receiver
+      .into_iter()
+      .chain(
+        self
+          .parameters
+          .iter()
+          .map(|parameter| format!("{}: {}", parameter.name.literal.lexeme(), parameter.data_type)),
+      )
+      .collect();
+    write!(f, "{:indent$}Function: {}", "", self.name.literal.lexeme())?;
+    GenericParameter::write_list(&self.generics, f)?;
+    write!(f, " ({})", parameters.join(", "))?;
+    match &self.return_type {
+      Some(return_type) => writeln!(f, " -> {return_type}")?,
+      None => writeln!(f)?,
+    }
+    match &self.body {
+      Some(body) => body.pretty_print(indent + 2, f),
+      None => Ok(()),
+    }
+  }
+}
+
+impl Display for ReceiverKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ReceiverKind::Value => write!(f, "self"),
+      ReceiverKind::Reference => write!(f, "&self"),
+      ReceiverKind::MutableReference => write!(f, "&mut self"),
+    }
+  }
+}
@@ -0,0 +1,75 @@
+use std::fmt::Display;
+
+use super::{span::Span, token::LiteralToken, type_expression::TypeExpression};
+
+/// A type parameter declared after a name, such as the `T` of
+/// `show<T: Speak>: (value: &T) -> string`, along with the traits the types it
+/// stands for have to implement.
+#[derive(Debug, PartialEq)]
+pub struct GenericParameter {
+  pub name: LiteralToken,
+  pub bounds: Vec<TypeExpression>,
+  /// The type of a const parameter, such as the `usize` of `N: const usize`,
+  /// which stands for an integer rather than a type.
+  pub const_type: Option<TypeExpression>,
+  pub span: Span,
+}
+
+/// An argument given to a generic type or function, such as the `i32` and
+/// the `5` of `fixed_array<i32, 5>`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenericArgument {
+  Type(TypeExpression),
+  Const(LiteralToken),
+}
+
+impl GenericParameter {
+  /// Writes `<T: A + B, U>`, or nothing if there are no parameters.
+  pub fn write_list(parameters: &[GenericParameter], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if parameters.is_empty() {
+      return Ok(());
+    }
+
+    let parameters: Vec<_> = parameters
+      .iter()
+      .map(|parameter| {
+        let name = parameter.name.literal.lexeme();
+        let bounds: Vec<_> = parameter.bounds.iter().map(ToString::to_string).collect();
+        match (&parameter.const_type, bounds.is_empty()) {
+          (Some(const_type), _) => format!("{name}: const {const_type}"),
+          (None, true) => name,
+          (None, false) => format!("{name}: {}", bounds.join(" + ")),
+        }
+      })
+      .collect();
+    write!(f, "<{}>", parameters.join(", "))
+  }
+}
+
+impl GenericArgument {
+  pub fn span(&self) -> Span {
+    match self {
+      GenericArgument::Type(data_type) => data_type.span(),
+      GenericArgument::Const(value) => value.span,
+    }
+  }
+
+  /// Writes `<A, B>`, or nothing if there are no arguments.
+  pub fn write_list(arguments: &[GenericArgument], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if arguments.is_empty() {
+      return Ok(());
+    }
+
+    let arguments: Vec<_> = arguments.iter().map(ToString::to_string).collect();
+    write!(f, "<{}>", arguments.join(", "))
+  }
+}
+
+impl Display for GenericArgument {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      GenericArgument::Type(data_type) => write!(f, "{data_type}"),
+      GenericArgument::Const(value) => write!(f, "{}", value.literal.lexeme()),
+    }
+  }
+}
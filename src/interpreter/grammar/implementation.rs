@@ -0,0 +1,37 @@
+use super::{
+  function::Function,
+  generic::GenericParameter,
+  span::Span,
+  token::LiteralToken,
+  type_expression::TypeExpression,
+};
+
+/// Methods added to a type declared elsewhere, as `Vtuber: impl = { ... }`,
+/// or the methods of a trait as `Vtuber: impl Speak = { ... }`.
+#[derive(Debug, PartialEq)]
+pub struct Impl {
+  /// The type the methods belong to.
+  pub target: LiteralToken,
+  /// The type parameters of a generic type, in the order it declares them,
+  /// as in `Pair<T, U>: impl = { ... }`.
+  pub generics: Vec<GenericParameter>,
+  /// The trait implemented for the type, if any.
+  pub trait_name: Option<TypeExpression>,
+  pub methods: Vec<Function>,
+  pub span: Span,
+}
+
+impl Impl {
+  pub fn pretty_print(&self, indent: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match &self.trait_name {
+      Some(trait_name) => write!(f, "{:indent$}Impl: {trait_name} for {}", "", self.target.literal.lexeme())?,
+      None => write!(f, "{:indent$}Impl: {}", "", self.target.literal.lexeme())?,
+    }
+    GenericParameter::write_list(&self.generics, f)?;
+    writeln!(f)?;
+    for method in &self.methods {
+      method.pretty_print(indent + 2, f)?;
+    }
+    Ok(())
+  }
+}
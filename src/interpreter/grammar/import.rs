@@ -0,0 +1,162 @@
+use std::fmt::Display;
+
+use super::{keyword::Keyword, span::Span, token::LiteralToken};
+
+/// `import core.*;`, which brings what a module exports into scope. An
+/// `export import` exports what it imports as well, as
+/// `export import array::{array, fixed_array};` does for `core`.
+#[derive(Debug, PartialEq)]
+pub struct Import {
+  pub exported: bool,
+  pub tree: ImportTree,
+  pub span: Span,
+}
+
+/// `include core;`, which makes the module `core` usable by its name, as in
+/// `core.SUCCESS`.
+#[derive(Debug, PartialEq)]
+pub struct Include {
+  pub path: Vec<LiteralToken>,
+  pub span: Span,
+}
+
+/// What an import names. The parts of a path are separated by `.` or `::`.
+#[derive(Debug, PartialEq)]
+pub enum ImportTree {
+  /// `a.b`, or `a.b as c`. A `self` in a list stands for the path before the
+  /// list, as in `bool::bool::{self, false, true}`.
+  Path {
+    segments: Vec<LiteralToken>,
+    alias: Option<LiteralToken>,
+  },
+  /// `a.b.{c, d as e}`.
+  List {
+    prefix: Vec<LiteralToken>,
+    items: Vec<ImportTree>,
+  },
+  /// `a.b.*`, everything `a.b` exports.
+  Glob { prefix: Vec<LiteralToken> },
+}
+
+/// A single path an import brings into scope, with the lists written out.
+#[derive(Debug, Clone)]
+pub struct ImportedPath {
+  pub segments: Vec<LiteralToken>,
+  pub alias: Option<LiteralToken>,
+  /// Whether everything the path names is brought in, rather than the path
+  /// itself.
+  pub glob: bool,
+  pub span: Span,
+}
+
+impl ImportTree {
+  /// Every path the tree imports, in the order they are written.
+  pub fn paths(&self) -> Vec<ImportedPath> {
+    let mut paths = Vec::new();
+    self.collect_paths(&[], &mut paths);
+    paths
+  }
+
+  fn collect_paths(&self, prefix: &[LiteralToken], paths: &mut Vec<ImportedPath>) {
+    let within = |segments: &[LiteralToken]| [prefix, segments].concat();
+    match self {
+      ImportTree::Path { segments, alias } => {
+        let mut segments = within(segments);
+        // `a.{self}` imports `a`
+        if segments.len() > 1
+          && segments
+            .last()
+            .is_some_and(|segment| segment.literal.lexeme() == Keyword::_Self.lexeme())
+        {
+          segments.pop();
+        }
+        paths.push(ImportedPath {
+          span: Self::span_of(&segments),
+          segments,
+          alias: alias.clone(),
+          glob: false,
+        });
+      }
+      ImportTree::List { prefix: inner, items } => {
+        let prefix = within(inner);
+        for item in items {
+          item.collect_paths(&prefix, paths);
+        }
+      }
+      ImportTree::Glob { prefix: inner } => {
+        let segments = within(inner);
+        paths.push(ImportedPath {
+          span: Self::span_of(&segments),
+          segments,
+          alias: None,
+          glob: true,
+        });
+      }
+    }
+  }
+
+  fn span_of(segments: &[LiteralToken]) -> Span {
+    match (segments.first(), segments.last()) {
+      (Some(first), Some(last)) => first.span.to(last.span),
+      _ => unreachable!("an import path has at least one segment"),
+    }
+  }
+}
+
+impl ImportedPath {
+  /// The name the path is known by once imported.
+  pub fn name(&self) -> &LiteralToken {
+    match &self.alias {
+      Some(alias) => alias,
+      None => self.segments.last().expect("an import path has at least one segment"),
+    }
+  }
+
+  /// The path as written, as in `core.array`.
+  pub fn path(&self) -> String {
+    write_path(&self.segments)
+  }
+}
+
+/// Writes the segments of a path joined by `.`.
+pub fn write_path(segments: &[LiteralToken]) -> String {
+  let segments: Vec<_> = segments.iter().map(|segment| segment.literal.lexeme()).collect();
+  segments.join(".")
+}
+
+impl Import {
+  pub fn pretty_print(&self, indent: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self.exported {
+      true => writeln!(f, "{:indent$}Export Import: {}", "", self.tree),
+      false => writeln!(f, "{:indent$}Import: {}", "", self.tree),
+    }
+  }
+}
+
+impl Include {
+  pub fn pretty_print(&self, indent: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    writeln!(f, "{:indent$}Include: {}", "", write_path(&self.path))
+  }
+}
+
+impl Display for ImportTree {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ImportTree::Path { segments, alias } => {
+        write!(f, "{}", write_path(segments))?;
+        match alias {
+          Some(alias) => write!(f, " as {}", alias.literal.lexeme()),
+          None => Ok(()),
+        }
+      }
+      ImportTree::List { prefix, items } => {
+        let items: Vec<_> = items.iter().map(ToString::to_string).collect();
+        match prefix.is_empty() {
+          true => write!(f, "{{{}}}", items.join(", ")),
+          false => write!(f, "{}.{{{}}}", write_path(prefix), items.join(", ")),
+        }
+      }
+      ImportTree::Glob { prefix } => write!(f, "{}.*", write_path(prefix)),
+    }
+  }
+}
@@ -0,0 +1,48 @@
+use super::{
+  constant::Constant,
+  enumeration::Enum,
+  function::Function,
+  implementation::Impl,
+  import::{Import, Include},
+  record::Record,
+  statement::Statement,
+  token::DocCommentToken,
+  trait_declaration::Trait,
+};
+
+/// A top-level part of a program, along with the `///` doc comments written
+/// above it.
+#[derive(Debug, PartialEq)]
+pub struct Item {
+  pub docs: Vec<DocCommentToken>,
+  pub kind: ItemKind,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ItemKind {
+  Statement(Statement),
+  Function(Function),
+  Enum(Enum),
+  Record(Record),
+  Impl(Impl),
+  Trait(Trait),
+  Constant(Constant),
+  Import(Import),
+  Include(Include),
+}
+
+impl Item {
+  pub fn pretty_print(&self, indent: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match &self.kind {
+      ItemKind::Statement(statement) => statement.pretty_print(indent, f),
+      ItemKind::Function(function) => function.pretty_print(indent, f),
+      ItemKind::Enum(enumeration) => enumeration.pretty_print(indent, f),
+      ItemKind::Record(record) => record.pretty_print(indent, f),
+      ItemKind::Impl(implementation) => implementation.pretty_print(indent, f),
+      ItemKind::Trait(declaration) => declaration.pretty_print(indent, f),
+      ItemKind::Constant(constant) => constant.pretty_print(indent, f),
+      ItemKind::Import(import) => import.pretty_print(indent, f),
+      ItemKind::Include(include) => include.pretty_print(indent, f),
+    }
+  }
+}
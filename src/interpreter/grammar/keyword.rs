@@ -1,59 +1,68 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Keyword {
-  If,
-  Else,
-  For,
-  While,
-  Loop,
-  Return,
-  _Self,
-  _SelfType,
-  Super,
-  Export,
-  Import,
-  Public,
-  Type,
-  Impl,
-  As,
-  Trait,
-}
+/// Declares [`Keyword`] together with its spellings, so that the enum, its
+/// lexemes and the lookup the lexer uses can't drift apart. The first
+/// spelling is the canonical one; any after it are aliases.
+macro_rules! keywords {
+  ($($keyword:ident => $lexeme:literal $(| $alias:literal)*,)*) => {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Keyword {
+      $($keyword,)*
+    }
 
-impl Keyword {
-  pub const AS: &'static str = "as";
-  pub const ELSE: &'static str = "else";
-  pub const EXPORT: &'static str = "export";
-  pub const FOR: &'static str = "for";
-  pub const IF: &'static str = "if";
-  pub const IMPL: &'static str = "impl";
-  pub const IMPORT: &'static str = "import";
-  pub const LOOP: &'static str = "loop";
-  pub const PUBLIC: &'static str = "pub";
-  pub const RETURN: &'static str = "return";
-  pub const SELF: &'static str = "self";
-  pub const SELF_TYPE: &'static str = "Self";
-  pub const SUPER: &'static str = "super";
-  pub const TRAIT: &'static str = "trait";
-  pub const TYPE: &'static str = "type";
-  pub const WHILE: &'static str = "while";
+    impl Keyword {
+      pub const ALL: &'static [Keyword] = &[$(Keyword::$keyword,)*];
 
-  pub fn lexeme(&self) -> String {
-    match self {
-      Keyword::If => Keyword::IF.into(),
-      Keyword::Else => Keyword::ELSE.into(),
-      Keyword::For => Keyword::FOR.into(),
-      Keyword::While => Keyword::WHILE.into(),
-      Keyword::Loop => Keyword::LOOP.into(),
-      Keyword::Return => Keyword::RETURN.into(),
-      Keyword::_Self => Keyword::SELF.into(),
-      Keyword::_SelfType => Keyword::SELF_TYPE.into(),
-      Keyword::Super => Keyword::SUPER.into(),
-      Keyword::Export => Keyword::EXPORT.into(),
-      Keyword::Import => Keyword::IMPORT.into(),
-      Keyword::Public => Keyword::PUBLIC.into(),
-      Keyword::Type => Keyword::TYPE.into(),
-      Keyword::Impl => Keyword::IMPL.into(),
-      Keyword::As => Keyword::AS.into(),
-      Keyword::Trait => Keyword::TRAIT.into(),
+      pub fn lexeme(&self) -> &'static str {
+        match self {
+          $(Keyword::$keyword => $lexeme,)*
+        }
+      }
+
+      /// The keyword spelled `lexeme`, if any.
+      pub fn from_lexeme(lexeme: &str) -> Option<Keyword> {
+        match lexeme {
+          $($lexeme $(| $alias)* => Some(Keyword::$keyword),)*
+          _ => None,
+        }
+      }
     }
+  };
+}
+
+keywords! {
+  If => "if",
+  Else => "else",
+  For => "for",
+  In => "in",
+  While => "while",
+  Loop => "loop",
+  Break => "break",
+  Continue => "continue",
+  Match => "match",
+  Return => "return",
+  _Self => "self",
+  _SelfType => "Self",
+  Super => "super",
+  Export => "export",
+  Import => "import",
+  Include => "include",
+  Public => "pub" | "public",
+  Const => "const",
+  Mut => "mut",
+  Type => "type",
+  Enum => "enum",
+  Impl => "impl",
+  As => "as",
+  Trait => "trait",
+  True => "true",
+  False => "false",
+  Void => "void",
+}
+
+impl Keyword {
+  /// Contextual keywords only mean something in particular places, such as
+  /// `in` after the pattern of a `for` loop, and can be used as names
+  /// everywhere else, such as an enum with `false` and `true` variants.
+  pub fn is_contextual(&self) -> bool {
+    matches!(self, Keyword::In | Keyword::True | Keyword::False | Keyword::Void)
   }
 }
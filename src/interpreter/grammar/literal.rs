@@ -1,11 +1,35 @@
-use std::any::Any;
+use super::{
+  binding::{data_type::Primitive, value::Value},
+  span::Span,
+  token::Token,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Literal {
   // Literals
-  Identifier { lexeme: String },
-  String { lexeme: String },
-  Number { lexeme: i64 },
+  Identifier {
+    lexeme: String,
+  },
+  String {
+    lexeme: String,
+  },
+  /// A string with `{expression}`s embedded in it, split into its parts.
+  InterpolatedString {
+    parts: Vec<StringPart>,
+  },
+  Number {
+    lexeme: i128,
+    suffix: Option<Primitive>,
+  },
+  Float {
+    lexeme: f64,
+  },
+  Char {
+    lexeme: char,
+  },
+  Bool {
+    lexeme: bool,
+  },
   Void,
 }
 
@@ -16,17 +40,60 @@ impl Literal {
     match self {
       Literal::Identifier { lexeme, .. } => lexeme.clone(),
       Literal::String { lexeme, .. } => lexeme.clone(),
-      Literal::Number { lexeme, .. } => lexeme.to_string(),
+      Literal::InterpolatedString { parts } => parts.iter().map(StringPart::lexeme).collect(),
+      Literal::Number { lexeme, suffix } => match suffix {
+        Some(suffix) => format!("{lexeme}{suffix}"),
+        None => lexeme.to_string(),
+      },
+      Literal::Float { lexeme, .. } => format!("{lexeme:?}"),
+      Literal::Char { lexeme, .. } => lexeme.to_string(),
+      Literal::Bool { lexeme } => lexeme.to_string(),
       Literal::Void => Self::VOID.into(),
     }
   }
 
-  pub fn value(&self) -> Box<dyn Any> {
+  pub fn value(&self) -> Value {
     match self.clone() {
-      Literal::Identifier { lexeme } => Box::new(lexeme),
-      Literal::String { lexeme } => Box::new(lexeme),
-      Literal::Number { lexeme } => Box::new(lexeme),
-      Literal::Void => Box::new(()),
+      Literal::Identifier { lexeme } => Value::String(lexeme),
+      Literal::String { lexeme } => Value::String(lexeme),
+      Literal::InterpolatedString { .. } => Value::String(self.lexeme()),
+      Literal::Number { lexeme, .. } => Value::Int(lexeme),
+      Literal::Float { lexeme } => Value::Float(lexeme),
+      Literal::Char { lexeme } => Value::Char(lexeme),
+      Literal::Bool { lexeme } => Value::Bool(lexeme),
+      Literal::Void => Value::Void,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StringPart {
+  /// Text between interpolations, with escapes already applied.
+  Text { text: String, span: Span },
+  /// The tokens between a `{` and its `}`, ending in an end of file token in
+  /// place of the `}`. Their spans point into the original source.
+  Expression { tokens: Vec<Token>, span: Span },
+}
+
+impl StringPart {
+  pub fn span(&self) -> Span {
+    match self {
+      StringPart::Text { span, .. } => *span,
+      StringPart::Expression { span, .. } => *span,
+    }
+  }
+
+  pub fn lexeme(&self) -> String {
+    match self {
+      StringPart::Text { text, .. } => text.clone(),
+      StringPart::Expression { tokens, .. } => {
+        let tokens: Vec<_> = tokens
+          .iter()
+          .filter(|token| !matches!(token, Token::EndOfFile { .. }))
+          .map(Token::lexeme)
+          .collect();
+        format!("{{{}}}", tokens.join(" "))
+      }
     }
   }
 }
@@ -0,0 +1,67 @@
+use super::{
+  expression::Expression,
+  function::Function,
+  generic::GenericParameter,
+  span::Span,
+  token::{DocCommentToken, LiteralToken},
+  type_expression::TypeExpression,
+};
+
+/// A record type declared as `VTuber: type = { name: public core.string; }`.
+#[derive(Debug, PartialEq)]
+pub struct Record {
+  pub name: LiteralToken,
+  /// The type parameters of a generic type, as in `Pair<T, U>: type = ...`.
+  pub generics: Vec<GenericParameter>,
+  /// Whether the type was declared `export`, for other modules to use.
+  pub exported: bool,
+  pub fields: Vec<Field>,
+  /// The methods declared in the body of the type, as they could be in an
+  /// [`Impl`](super::implementation::Impl).
+  pub methods: Vec<Function>,
+  pub span: Span,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Field {
+  /// The `///` doc comments written above the field.
+  pub docs: Vec<DocCommentToken>,
+  pub name: LiteralToken,
+  /// Whether the field was declared `pub` or `public`. Private fields can
+  /// only be used by the module declaring the type.
+  pub public: bool,
+  pub data_type: TypeExpression,
+  pub span: Span,
+}
+
+/// A field given a value in a record literal, as `name = value`, or as just
+/// `name` for a variable of the same name.
+#[derive(Debug, PartialEq)]
+pub struct FieldInitializer {
+  pub name: LiteralToken,
+  pub value: Expression,
+  pub span: Span,
+}
+
+impl Record {
+  pub fn pretty_print(&self, indent: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{:indent$}Record: {}", "", self.name.literal.lexeme())?;
+    GenericParameter::write_list(&self.generics, f)?;
+    writeln!(f)?;
+    for field in &self.fields {
+      let visibility = if field.public { "pub " } else { "" };
+      writeln!(
+        f,
+        "{:indent$}{visibility}{}: {}",
+        "",
+        field.name.literal.lexeme(),
+        field.data_type,
+        indent = indent + 2
+      )?;
+    }
+    for method in &self.methods {
+      method.pretty_print(indent + 2, f)?;
+    }
+    Ok(())
+  }
+}
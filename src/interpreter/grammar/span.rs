@@ -0,0 +1,53 @@
+use std::fmt::{Display, Formatter};
+
+/// Identifies a source file registered with a
+/// [`SourceMap`](crate::interpreter::util::source_map::SourceMap).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileId(pub u32);
+
+/// A range of bytes in a source file. `start` is inclusive and `end` is
+/// exclusive, so an empty span marks a point between two characters.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+  pub file_id: FileId,
+  pub start: usize,
+  pub end: usize,
+}
+
+impl Span {
+  pub fn new(file_id: FileId, start: usize, end: usize) -> Self {
+    Self { file_id, start, end }
+  }
+
+  /// The smallest span covering both `self` and `other`.
+  pub fn to(&self, other: Span) -> Span {
+    Span {
+      file_id: self.file_id,
+      start: self.start.min(other.start),
+      end: self.end.max(other.end),
+    }
+  }
+
+  /// An empty span just past the end of this one.
+  pub fn after(&self) -> Span {
+    Span {
+      file_id: self.file_id,
+      start: self.end,
+      end: self.end,
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.end - self.start
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.start == self.end
+  }
+}
+
+impl Display for Span {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}..{}", self.start, self.end)
+  }
+}
@@ -0,0 +1,63 @@
+use super::{
+  expression::Expression,
+  span::Span,
+  token::{DocCommentToken, LiteralToken},
+  type_expression::TypeExpression,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum Statement {
+  /// An expression with no `;` after it. The last statement of a block gives
+  /// the block its value this way; anywhere else, only block-like expressions
+  /// such as `{ ... }` may leave out the `;`.
+  Expression(Expression),
+  /// An expression whose value is thrown away by the `;` after it.
+  Semicolon { expression: Expression, span: Span },
+  /// A variable, either typed as `y: i32 = a;` or inferred as `x := 10;`. A
+  /// typed variable may leave out its value, but must be given one before it
+  /// is used.
+  Declaration {
+    /// The `///` doc comments written above a declaration in a block. Those
+    /// of one at the top level are on its [`Item`](super::item::Item).
+    docs: Vec<DocCommentToken>,
+    name: LiteralToken,
+    data_type: Option<TypeExpression>,
+    initializer: Option<Expression>,
+    span: Span,
+  },
+}
+
+impl Statement {
+  pub fn span(&self) -> Span {
+    match self {
+      Statement::Expression(expression) => expression.span(),
+      Statement::Semicolon { span, .. } => *span,
+      Statement::Declaration { span, .. } => *span,
+    }
+  }
+
+  pub fn pretty_print(&self, indent: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Statement::Expression(expression) => expression.pretty_print(indent, f),
+      Statement::Semicolon { expression, .. } => {
+        writeln!(f, "{:indent$}Semicolon", "")?;
+        expression.pretty_print(indent + 2, f)
+      }
+      Statement::Declaration {
+        name,
+        data_type,
+        initializer,
+        ..
+      } => {
+        match data_type {
+          Some(data_type) => writeln!(f, "{:indent$}Declaration: {} : {data_type}", "", name.literal.lexeme())?,
+          None => writeln!(f, "{:indent$}Declaration: {}", "", name.literal.lexeme())?,
+        }
+        match initializer {
+          Some(initializer) => initializer.pretty_print(indent + 2, f),
+          None => Ok(()),
+        }
+      }
+    }
+  }
+}
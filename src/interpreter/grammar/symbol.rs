@@ -1,3 +1,5 @@
+use enumflags2::BitFlags;
+
 #[enumflags2::bitflags]
 #[repr(u64)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -47,54 +49,85 @@ pub enum Symbol {
   TildeEquals,
   Percent,
   PercentEquals,
+  ColonEquals,
+  DoubleColon,
+  DoublePeriod,
+  DoublePeriodEquals,
 }
 
 impl Symbol {
-  pub fn lexeme(&self) -> String {
+  /// Every symbol the lexer produces as an operator or punctuation. Quotation
+  /// marks and apostrophes start literals instead.
+  pub fn operators() -> impl Iterator<Item = Symbol> {
+    BitFlags::<Symbol>::all()
+      .iter()
+      .filter(|symbol| !matches!(symbol, Symbol::QuotationMark | Symbol::Apostrophe))
+  }
+
+  /// The operator a compound assignment such as `+=` applies to what it
+  /// assigns to and its value. `a &= b` is `a = a && b`, as `&` on its own
+  /// takes a reference.
+  pub fn compound_operator(&self) -> Option<Symbol> {
+    match self {
+      Symbol::PlusEquals => Some(Symbol::Plus),
+      Symbol::MinusEquals => Some(Symbol::Minus),
+      Symbol::AsteriskEquals => Some(Symbol::Asterisk),
+      Symbol::ForwardSlashEquals => Some(Symbol::ForwardSlash),
+      Symbol::CaretEquals => Some(Symbol::Caret),
+      Symbol::AmpersandEquals => Some(Symbol::DoubleAmpersand),
+      _ => None,
+    }
+  }
+
+  pub fn lexeme(&self) -> &'static str {
     match self {
-      Symbol::LeftParenthesis { .. } => "(".into(),
-      Symbol::RightParenthesis { .. } => ")".into(),
-      Symbol::LeftAngledBracket { .. } => "<".into(),
-      Symbol::RightAngledBracket { .. } => ">".into(),
-      Symbol::LeftSquareBracket { .. } => "[".into(),
-      Symbol::RightSquareBracket { .. } => "]".into(),
-      Symbol::LeftCurlyBracket { .. } => "{".into(),
-      Symbol::RightCurlyBracket { .. } => "}".into(),
-      Symbol::Hashtag { .. } => "#".into(),
-      Symbol::Comma { .. } => ",".into(),
-      Symbol::Period { .. } => ".".into(),
-      Symbol::Colon { .. } => ":".into(),
-      Symbol::Semicolon { .. } => ";".into(),
-      Symbol::QuotationMark { .. } => "\"".into(),
-      Symbol::Apostrophe { .. } => "\'".into(),
+      Symbol::LeftParenthesis => "(",
+      Symbol::RightParenthesis => ")",
+      Symbol::LeftAngledBracket => "<",
+      Symbol::RightAngledBracket => ">",
+      Symbol::LeftSquareBracket => "[",
+      Symbol::RightSquareBracket => "]",
+      Symbol::LeftCurlyBracket => "{",
+      Symbol::RightCurlyBracket => "}",
+      Symbol::Hashtag => "#",
+      Symbol::Comma => ",",
+      Symbol::Period => ".",
+      Symbol::Colon => ":",
+      Symbol::Semicolon => ";",
+      Symbol::QuotationMark => "\"",
+      Symbol::Apostrophe => "\'",
 
-      Symbol::Plus { .. } => "+".into(),
-      Symbol::Minus { .. } => "-".into(),
-      Symbol::Asterisk { .. } => "*".into(),
-      Symbol::ForwardSlash { .. } => "/".into(),
-      Symbol::ExclamationPoint { .. } => "!".into(),
-      Symbol::Equals { .. } => "=".into(),
-      Symbol::DoubleEquals { .. } => "==".into(),
-      Symbol::PlusEquals { .. } => "+=".into(),
-      Symbol::MinusEquals { .. } => "-=".into(),
-      Symbol::AsteriskEquals { .. } => "*=".into(),
-      Symbol::ForwardSlashEquals { .. } => "/=".into(),
-      Symbol::ExclamationPointEquals { .. } => "!=".into(),
-      Symbol::LeftAngledBracketEquals { .. } => "<=".into(),
-      Symbol::RightAngledBracketEquals { .. } => ">=".into(),
-      Symbol::RightArrow { .. } => "->".into(),
-      Symbol::Ampersand { .. } => "&".into(),
-      Symbol::DoubleAmpersand { .. } => "&&".into(),
-      Symbol::AmpersandEquals { .. } => "&=".into(),
-      Symbol::Pipe { .. } => "|".into(),
-      Symbol::DoublePipe { .. } => "||".into(),
-      Symbol::PipeEquals { .. } => "|=".into(),
-      Symbol::Caret { .. } => "^".into(),
-      Symbol::CaretEquals { .. } => "^=".into(),
-      Symbol::Tilde { .. } => "~".into(),
-      Symbol::TildeEquals { .. } => "~=".into(),
-      Symbol::Percent { .. } => "%".into(),
-      Symbol::PercentEquals { .. } => "%=".into(),
+      Symbol::Plus => "+",
+      Symbol::Minus => "-",
+      Symbol::Asterisk => "*",
+      Symbol::ForwardSlash => "/",
+      Symbol::ExclamationPoint => "!",
+      Symbol::Equals => "=",
+      Symbol::DoubleEquals => "==",
+      Symbol::PlusEquals => "+=",
+      Symbol::MinusEquals => "-=",
+      Symbol::AsteriskEquals => "*=",
+      Symbol::ForwardSlashEquals => "/=",
+      Symbol::ExclamationPointEquals => "!=",
+      Symbol::LeftAngledBracketEquals => "<=",
+      Symbol::RightAngledBracketEquals => ">=",
+      Symbol::RightArrow => "->",
+      Symbol::Ampersand => "&",
+      Symbol::DoubleAmpersand => "&&",
+      Symbol::AmpersandEquals => "&=",
+      Symbol::Pipe => "|",
+      Symbol::DoublePipe => "||",
+      Symbol::PipeEquals => "|=",
+      Symbol::Caret => "^",
+      Symbol::CaretEquals => "^=",
+      Symbol::Tilde => "~",
+      Symbol::TildeEquals => "~=",
+      Symbol::Percent => "%",
+      Symbol::PercentEquals => "%=",
+      Symbol::ColonEquals => ":=",
+      Symbol::DoubleColon => "::",
+      Symbol::DoublePeriod => "..",
+      Symbol::DoublePeriodEquals => "..=",
     }
   }
 }
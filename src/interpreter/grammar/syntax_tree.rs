@@ -1,15 +1,25 @@
 use std::fmt::Display;
 
-use super::{expression::Expression, token::Token};
+use super::{
+  item::Item,
+  token::{DocCommentToken, Token},
+};
 
 #[derive(Debug, PartialEq)]
 pub struct SyntaxTree {
-  pub root: Expression,
+  pub items: Vec<Item>,
+  /// Doc comments that weren't attached to an item, such as the `//!`
+  /// comments describing the file.
+  pub docs: Vec<DocCommentToken>,
   pub eof: Token,
 }
 
 impl Display for SyntaxTree {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    self.root.pretty_print(0, f)
+    for item in &self.items {
+      item.pretty_print(0, f)?;
+    }
+
+    Ok(())
   }
 }
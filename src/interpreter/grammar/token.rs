@@ -2,7 +2,7 @@ use std::fmt::{Display, Formatter};
 
 use strum::EnumDiscriminants;
 
-use super::{keyword::Keyword, literal::Literal, symbol::Symbol};
+use super::{keyword::Keyword, literal::Literal, span::Span, symbol::Symbol};
 use crate::interpreter::error::InterpreterError;
 
 #[derive(Debug, Clone, PartialEq, EnumDiscriminants)]
@@ -12,11 +12,14 @@ pub enum Token {
   Symbol(SymbolToken),
   Keyword(KeywordToken),
   Literal(LiteralToken),
+  DocComment(DocCommentToken),
   EndOfFile {
     position: Position,
+    span: Span,
   },
   Invalid {
     position: Position,
+    span: Span,
     error: InterpreterError,
   },
 }
@@ -42,6 +45,7 @@ impl Display for Position {
 #[derive(Debug, Clone, PartialEq)]
 pub struct SymbolToken {
   pub position: Position,
+  pub span: Span,
   pub symbol: Symbol,
 }
 
@@ -54,7 +58,24 @@ impl Display for SymbolToken {
 #[derive(Debug, Clone, PartialEq)]
 pub struct KeywordToken {
   pub position: Position,
+  pub span: Span,
   pub keyword: Keyword,
+  /// The keyword as written, which may be an alias of its canonical spelling.
+  pub text: String,
+}
+
+impl KeywordToken {
+  /// The keyword as an identifier, for keywords such as `self` that stand for
+  /// a name.
+  pub fn as_name(&self) -> LiteralToken {
+    LiteralToken {
+      position: self.position.clone(),
+      span: self.span,
+      literal: Literal::Identifier {
+        lexeme: self.keyword.lexeme().into(),
+      },
+    }
+  }
 }
 
 impl Display for KeywordToken {
@@ -66,6 +87,7 @@ impl Display for KeywordToken {
 #[derive(Debug, Clone, PartialEq)]
 pub struct LiteralToken {
   pub position: Position,
+  pub span: Span,
   pub literal: Literal,
 }
 
@@ -75,90 +97,197 @@ impl Display for LiteralToken {
   }
 }
 
+/// Whether a doc comment documents the declaration after it (`///`) or the
+/// enclosing file (`//!`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocCommentKind {
+  Outer,
+  Inner,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocCommentToken {
+  pub position: Position,
+  pub span: Span,
+  pub kind: DocCommentKind,
+  pub text: String,
+}
+
+impl DocCommentToken {
+  pub fn lexeme(&self) -> String {
+    match self.kind {
+      DocCommentKind::Outer => format!("///{}", self.text),
+      DocCommentKind::Inner => format!("//!{}", self.text),
+    }
+  }
+}
+
+impl Display for DocCommentToken {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.lexeme())
+  }
+}
+
+/// Source text between tokens that has no meaning to the parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Trivia {
+  Whitespace(String),
+  Newline(String),
+  LineComment(String),
+  BlockComment(String),
+}
+
+impl Trivia {
+  pub fn text(&self) -> &str {
+    match self {
+      Trivia::Whitespace(text) => text,
+      Trivia::Newline(text) => text,
+      Trivia::LineComment(text) => text,
+      Trivia::BlockComment(text) => text,
+    }
+  }
+
+  /// Splits the text between two tokens into its pieces. `text` must only hold
+  /// whitespace and (non-doc) comments, as skipped by the lexer.
+  pub fn split(text: &str) -> Vec<Trivia> {
+    let mut pieces = Vec::default();
+    let mut rest = text;
+    while !rest.is_empty() {
+      let length = if rest.starts_with("\r\n") {
+        2
+      } else if rest.starts_with('\n') {
+        1
+      } else if rest.starts_with("//") {
+        // the comment stops before the line break, `\r\n` as well as `\n`
+        let end = rest.find('\n').unwrap_or(rest.len());
+        rest[..end].strip_suffix('\r').map_or(end, str::len)
+      } else if rest.starts_with("/*") {
+        Self::block_comment_length(rest)
+      } else {
+        rest
+          .find(|c: char| !c.is_whitespace() || c == '\n' || c == '\r')
+          .unwrap_or(rest.len())
+          .max(1)
+      };
+
+      let (piece, remaining) = rest.split_at(length);
+      pieces.push(match piece {
+        "\n" | "\r\n" => Trivia::Newline(piece.into()),
+        _ if piece.starts_with("//") => Trivia::LineComment(piece.into()),
+        _ if piece.starts_with("/*") => Trivia::BlockComment(piece.into()),
+        _ => Trivia::Whitespace(piece.into()),
+      });
+      rest = remaining;
+    }
+
+    pieces
+  }
+
+  /// The length of the (possibly nested and unterminated) block comment at the
+  /// start of `text`.
+  fn block_comment_length(text: &str) -> usize {
+    let mut depth = 0;
+    let mut index = 0;
+    while index < text.len() {
+      let rest = &text[index..];
+      if rest.starts_with("/*") {
+        depth += 1;
+        index += 2;
+      } else if rest.starts_with("*/") {
+        depth -= 1;
+        index += 2;
+        if depth == 0 {
+          return index;
+        }
+      } else {
+        index += rest.chars().next().map_or(1, char::len_utf8);
+      }
+    }
+
+    text.len()
+  }
+}
+
+/// A token together with the exact source text it came from and the trivia
+/// around it. See
+/// [`Lexer::lex_lossless`](crate::interpreter::lexer::Lexer::lex_lossless).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LosslessToken {
+  pub leading: Vec<Trivia>,
+  pub token: Token,
+  pub text: String,
+  pub trailing: Vec<Trivia>,
+}
+
+impl Display for LosslessToken {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    for trivia in &self.leading {
+      write!(f, "{}", trivia.text())?;
+    }
+    write!(f, "{}", self.text)?;
+    for trivia in &self.trailing {
+      write!(f, "{}", trivia.text())?;
+    }
+    Ok(())
+  }
+}
+
 impl Token {
   pub fn position(&self) -> &Position {
     match self {
       Token::Symbol(SymbolToken { position, .. }) => position,
       Token::Keyword(KeywordToken { position, .. }) => position,
       Token::Literal(LiteralToken { position, .. }) => position,
+      Token::DocComment(DocCommentToken { position, .. }) => position,
       Token::EndOfFile { position, .. } => position,
       Token::Invalid { position, .. } => position,
     }
   }
 
+  pub fn span(&self) -> Span {
+    match self {
+      Token::Symbol(SymbolToken { span, .. }) => *span,
+      Token::Keyword(KeywordToken { span, .. }) => *span,
+      Token::Literal(LiteralToken { span, .. }) => *span,
+      Token::DocComment(DocCommentToken { span, .. }) => *span,
+      Token::EndOfFile { span, .. } => *span,
+      Token::Invalid { span, .. } => *span,
+    }
+  }
+
   pub fn lexeme(&self) -> String {
     match self {
-      Token::Symbol(SymbolToken { symbol, .. }) => symbol.lexeme(),
-      Token::Keyword(KeywordToken { keyword, .. }) => keyword.lexeme(),
+      Token::Symbol(SymbolToken { symbol, .. }) => symbol.lexeme().into(),
+      Token::Keyword(KeywordToken { keyword, .. }) => keyword.lexeme().into(),
       Token::Literal(LiteralToken { literal, .. }) => literal.lexeme(),
+      Token::DocComment(doc) => doc.lexeme(),
       Token::EndOfFile { .. } => "[EOF]".into(),
       Token::Invalid { .. } => "[INV]".into(),
     }
   }
 
-  pub fn reserved_word(value: &str, position: Position) -> Option<Self> {
-    match value {
-      // Keywords
-      Keyword::IF => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::If,
-      })),
-      Keyword::ELSE => Some(Token::Keyword(KeywordToken {
+  pub fn reserved_word(value: &str, position: Position, span: Span) -> Option<Self> {
+    Keyword::from_lexeme(value).map(|keyword| {
+      Token::Keyword(KeywordToken {
         position,
-        keyword: Keyword::Else,
-      })),
-      Keyword::FOR => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::For,
-      })),
-      Keyword::WHILE => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::While,
-      })),
-      Keyword::LOOP => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::Loop,
-      })),
-      Keyword::RETURN => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::Return,
-      })),
-      Keyword::SELF => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::_Self,
-      })),
-      Keyword::SUPER => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::Super,
-      })),
-      Keyword::IMPORT => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::Import,
-      })),
-      Keyword::EXPORT => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::Export,
-      })),
-      Keyword::PUBLIC => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::Public,
-      })),
-      Keyword::TYPE => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::Type,
-      })),
-      Keyword::IMPL => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::Impl,
-      })),
-      Keyword::AS => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::As,
-      })),
-      Keyword::TRAIT => Some(Token::Keyword(KeywordToken {
-        position,
-        keyword: Keyword::Trait,
-      })),
+        span,
+        keyword,
+        text: value.into(),
+      })
+    })
+  }
+
+  /// The token as a name: identifiers, and contextual keywords, which can
+  /// double as names wherever the grammar expects one.
+  pub fn as_identifier(&self) -> Option<LiteralToken> {
+    match self {
+      Token::Literal(
+        token @ LiteralToken {
+          literal: Literal::Identifier { .. },
+          ..
+        },
+      ) => Some(token.clone()),
+      Token::Keyword(keyword) if keyword.keyword.is_contextual() => Some(keyword.as_name()),
       _ => None,
     }
   }
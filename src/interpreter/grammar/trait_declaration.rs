@@ -0,0 +1,23 @@
+use super::{function::Function, span::Span, token::LiteralToken};
+
+/// A trait declared as `Speak: trait = { ... }`. Its methods without a body
+/// have to be given one by every `impl` of the trait, while the others are
+/// defaults that an `impl` may replace.
+#[derive(Debug, PartialEq)]
+pub struct Trait {
+  pub name: LiteralToken,
+  /// Whether the trait was declared `export`, for other modules to use.
+  pub exported: bool,
+  pub methods: Vec<Function>,
+  pub span: Span,
+}
+
+impl Trait {
+  pub fn pretty_print(&self, indent: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    writeln!(f, "{:indent$}Trait: {}", "", self.name.literal.lexeme())?;
+    for method in &self.methods {
+      method.pretty_print(indent + 2, f)?;
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,46 @@
+use std::fmt::Display;
+
+use super::{generic::GenericArgument, span::Span, token::LiteralToken};
+
+/// A type as written in the source, such as the `i32` in `y: i32 = a;`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeExpression {
+  /// A named type, possibly reached through modules, such as `core.string`,
+  /// along with the arguments of a generic type, as in `fixed_array<i32, 5>`.
+  Path {
+    segments: Vec<LiteralToken>,
+    arguments: Vec<GenericArgument>,
+    span: Span,
+  },
+  /// `&Type`, a reference to a value of the type. A reference to a trait,
+  /// such as `&Speak`, refers to a value of any type implementing it.
+  Reference { inner: Box<TypeExpression>, span: Span },
+  /// `()` or `void`.
+  Void { span: Span },
+}
+
+impl TypeExpression {
+  pub fn span(&self) -> Span {
+    match self {
+      TypeExpression::Path { span, .. } => *span,
+      TypeExpression::Reference { span, .. } => *span,
+      TypeExpression::Void { span } => *span,
+    }
+  }
+}
+
+impl Display for TypeExpression {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      TypeExpression::Path {
+        segments, arguments, ..
+      } => {
+        let segments: Vec<_> = segments.iter().map(|segment| segment.literal.lexeme()).collect();
+        write!(f, "{}", segments.join("."))?;
+        GenericArgument::write_list(arguments, f)
+      }
+      TypeExpression::Reference { inner, .. } => write!(f, "&{inner}"),
+      TypeExpression::Void { .. } => write!(f, "()"),
+    }
+  }
+}
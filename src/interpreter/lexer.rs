@@ -1,56 +1,103 @@
+use std::io::{BufRead, BufReader, Read};
+
 use foxy_utils::types::handle::Handle;
+use unicode_normalization::UnicodeNormalization;
+use unicode_xid::UnicodeXID;
 
 use super::{
   error::{error_handler::ErrorHandler, InterpreterError},
   grammar::{
-    literal::Literal,
+    binding::data_type::Primitive,
+    literal::{Literal, StringPart},
+    span::{FileId, Span},
     symbol::Symbol,
-    token::{LiteralToken, Position, SymbolToken, Token},
+    token::{DocCommentKind, DocCommentToken, LiteralToken, LosslessToken, Position, SymbolToken, Token, Trivia},
   },
   util::character_provider::CharacterProvider,
 };
 
-pub struct Lexer {
+/// Turns source text into tokens one at a time, reading from the source only
+/// as far as the token being built. Lex errors go to the error handler as the
+/// tokens they belong to are produced, and the last token is always
+/// [`Token::EndOfFile`].
+pub struct Lexer<'a> {
+  characters: CharacterProvider<'a>,
   error_handler: Handle<ErrorHandler>,
+  finished: bool,
 }
 
-impl Lexer {
-  pub fn new(error_handler: Handle<ErrorHandler>) -> Self {
-    Self { error_handler }
+impl<'a> Lexer<'a> {
+  pub fn new(source: impl BufRead + 'a, file_id: FileId, error_handler: Handle<ErrorHandler>) -> Self {
+    Self {
+      characters: CharacterProvider::new(source, file_id),
+      error_handler,
+      finished: false,
+    }
   }
 
-  pub fn lex(&mut self, source: &str) -> Vec<Token> {
-    let mut characters = CharacterProvider::new(source);
+  /// Lexes an unbuffered source such as a file or a pipe.
+  pub fn from_reader(source: impl Read + 'a, file_id: FileId, error_handler: Handle<ErrorHandler>) -> Self {
+    Self::new(BufReader::new(source), file_id, error_handler)
+  }
 
-    let mut tokens = Vec::default();
-    while let Some(token) = self.build_token(&mut characters) {
-      if let Token::Invalid { error, .. } = &token {
-        self.error_handler.get_mut().push(error.clone());
+  /// Lexes without losing anything: every token carries the whitespace and
+  /// comments around it, so writing the tokens back out in order reproduces
+  /// `source` byte for byte.
+  ///
+  /// Trivia on the same line after a token, up to the line break, trails that
+  /// token. Everything from the line break up to the next token leads it.
+  pub fn lex_lossless(source: &str, file_id: FileId, error_handler: Handle<ErrorHandler>) -> Vec<LosslessToken> {
+    let mut tokens: Vec<LosslessToken> = Vec::default();
+    let mut previous_end = 0;
+    for token in Lexer::new(source.as_bytes(), file_id, error_handler) {
+      let span = token.span();
+      let mut trivia = Trivia::split(&source[previous_end..span.start]).into_iter().peekable();
+      if let Some(previous) = tokens.last_mut() {
+        while let Some(piece) = trivia.next_if(|piece| !matches!(piece, Trivia::Newline(_))) {
+          previous.trailing.push(piece);
+        }
       }
-      tokens.push(token);
-    }
 
-    tokens.push(Token::EndOfFile {
-      position: characters.current_position(),
-    });
+      tokens.push(LosslessToken {
+        leading: trivia.collect(),
+        text: source[span.start..span.end].into(),
+        token,
+        trailing: Vec::default(),
+      });
+      previous_end = span.end;
+    }
 
     tokens
   }
 
-  fn build_token(&mut self, characters: &mut CharacterProvider) -> Option<Token> {
+  /// Builds the next token. Trivia before the token is skipped and not part
+  /// of its span.
+  fn build_token(characters: &mut CharacterProvider) -> Option<Token> {
     let next_character = characters.next()?;
+    let start = characters.last_offset();
+    Self::match_token(characters, next_character, start)
+  }
+
+  fn match_token(characters: &mut CharacterProvider, next_character: char, start: usize) -> Option<Token> {
     let mut lexeme = String::new();
     match next_character {
-      letter if letter.is_ascii_alphabetic() || letter == '_' => {
+      'r' if Self::is_raw_string_start(characters) => {
+        let position = characters.current_position();
+        return Some(Self::lex_raw_string(characters, position, start));
+      }
+      letter if Self::is_identifier_start(letter) => {
         let start_of_lexeme = characters.current_column();
 
-        // identifiers
+        // identifiers, normalized so that names compare equal however they were typed
         lexeme.push(letter);
-        lexeme.push_str(&Self::read_lexeme_while(characters, |c| c.is_ascii_alphanumeric() || c == &'_'));
+        lexeme.push_str(&Self::read_lexeme_while(characters, |c| c.is_xid_continue()));
+        let lexeme: String = lexeme.nfc().collect();
 
-        return if let Some(reserved_word) =
-          Token::reserved_word(&lexeme, Position::new(characters.current_line(), start_of_lexeme))
-        {
+        return if let Some(reserved_word) = Token::reserved_word(
+          &lexeme,
+          Position::new(characters.current_line(), start_of_lexeme),
+          characters.span_from(start),
+        ) {
           Some(reserved_word)
         } else {
           Some(Token::Literal(LiteralToken {
@@ -58,391 +105,369 @@ impl Lexer {
               line: characters.current_line(),
               column: start_of_lexeme,
             },
+            span: characters.span_from(start),
             literal: Literal::Identifier { lexeme },
           }))
         };
       }
       digit if digit.is_ascii_digit() => {
-        let start_of_lexeme = characters.current_column();
-
-        // numbers
-        lexeme.push(digit);
-        lexeme.push_str(&Self::read_lexeme_while(characters, |c| c.is_ascii_digit()));
-
-        return if let Ok(lexeme) = lexeme.parse::<i64>() {
-          Some(Token::Literal(LiteralToken {
-            position: Position {
-              line: characters.current_line(),
-              column: start_of_lexeme,
-            },
-            literal: Literal::Number { lexeme },
-          }))
-        } else {
-          Some(Token::Invalid {
-            position: characters.current_position(),
-            error: InterpreterError::SyntaxError {
-              position: characters.current_position(),
-              message: "Failed to parse number".into(),
-            },
-          })
-        };
+        let position = characters.current_position();
+        return Some(Self::lex_number(characters, digit, position, start));
+      }
+      '\'' => {
+        let position = characters.current_position();
+        return Some(Self::lex_char(characters, position, start));
+      }
+      '\"' => {
+        let position = characters.current_position();
+        return Some(Self::lex_string(characters, position, start));
+      }
+      '/' if characters.peek() == Some(&'/') => {
+        // the character provider only lets doc comments through
+        let position = characters.current_position();
+        return Some(Self::lex_doc_comment(characters, position, start));
+      }
+      '(' if Self::next_char_is(characters, ')') => {
+        return Some(Token::Literal(LiteralToken {
+          position: characters.current_position(),
+          span: characters.span_from(start),
+          literal: Literal::Void,
+        }));
       }
       symbol if symbol.is_ascii_punctuation() => {
-        // symbols
-        match symbol {
-          ';' => {
-            return Some(Token::Symbol(SymbolToken {
-              position: Position {
-                line: characters.current_line(),
-                column: characters.current_column(),
-              },
-              symbol: Symbol::Semicolon,
-            }))
-          }
-          ',' => {
-            return Some(Token::Symbol(SymbolToken {
-              position: Position {
-                line: characters.current_line(),
-                column: characters.current_column(),
-              },
-              symbol: Symbol::Comma,
-            }))
-          }
-          '.' => {
-            return Some(Token::Symbol(SymbolToken {
-              position: Position {
-                line: characters.current_line(),
-                column: characters.current_column(),
-              },
-              symbol: Symbol::Period,
-            }))
-          }
-          ':' => {
-            return Some(Token::Symbol(SymbolToken {
-              position: Position {
-                line: characters.current_line(),
-                column: characters.current_column(),
-              },
-              symbol: Symbol::Colon,
-            }))
-          }
-          '!' => {
-            return if Self::next_char_is(characters, '=') {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::ExclamationPointEquals,
-              }))
-            } else {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::ExclamationPoint,
-              }))
-            };
-          }
-          '=' => {
-            return if Self::next_char_is(characters, '=') {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::DoubleEquals,
-              }))
-            } else {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::Equals,
-              }))
-            };
-          }
-          '+' => {
-            return if Self::next_char_is(characters, '=') {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::PlusEquals,
-              }))
-            } else {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::Plus,
-              }))
-            };
-          }
-          '-' => {
-            return if Self::next_char_is(characters, '>') {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::RightArrow,
-              }))
-            } else if Self::next_char_is(characters, '=') {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::MinusEquals,
-              }))
-            } else {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::Minus,
-              }))
-            };
-          }
-          '/' => {
-            return if Self::next_char_is(characters, '=') {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::ForwardSlashEquals,
-              }))
-            } else {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::ForwardSlash,
-              }))
-            };
-          }
-          '*' => {
-            return if Self::next_char_is(characters, '=') {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::AsteriskEquals,
-              }))
-            } else {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::Asterisk,
-              }))
-            };
-          }
-          '^' => {
-            return if Self::next_char_is(characters, '=') {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::CaretEquals,
-              }))
-            } else {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::Caret,
-              }))
-            };
-          }
-          '&' => {
-            return if Self::next_char_is(characters, '=') {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::AmpersandEquals,
-              }))
-            } else {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::Ampersand,
-              }))
-            };
-          }
-          '(' => {
-            return if Self::next_char_is(characters, ')') {
-              Some(Token::Literal(LiteralToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                literal: Literal::Void,
-              }))
-            } else {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::LeftParenthesis,
-              }))
-            };
-          }
-          ')' => {
-            return Some(Token::Symbol(SymbolToken {
-              position: Position {
-                line: characters.current_line(),
-                column: characters.current_column(),
-              },
-              symbol: Symbol::RightParenthesis,
-            }))
-          }
-          '{' => {
-            return Some(Token::Symbol(SymbolToken {
-              position: Position {
-                line: characters.current_line(),
-                column: characters.current_column(),
-              },
-              symbol: Symbol::LeftCurlyBracket,
-            }))
-          }
-          '}' => {
-            return Some(Token::Symbol(SymbolToken {
-              position: Position {
-                line: characters.current_line(),
-                column: characters.current_column(),
-              },
-              symbol: Symbol::RightCurlyBracket,
-            }))
-          }
-          '<' => {
-            return if Self::next_char_is(characters, '=') {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::LeftAngledBracket,
-              }))
-            } else {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::LeftAngledBracketEquals,
-              }))
-            };
-          }
-          '>' => {
-            return if Self::next_char_is(characters, '=') {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::RightAngledBracket,
-              }))
-            } else {
-              Some(Token::Symbol(SymbolToken {
-                position: Position {
-                  line: characters.current_line(),
-                  column: characters.current_column(),
-                },
-                symbol: Symbol::RightAngledBracketEquals,
-              }))
-            };
+        let position = characters.current_position();
+        if let Some(token) = Self::lex_symbol(characters, symbol, position, start) {
+          return Some(token);
+        }
+      }
+      _ => {}
+    }
+
+    Some(Token::Invalid {
+      position: characters.current_position(),
+      span: characters.span_from(start),
+      error: InterpreterError::UnknownToken {
+        span: characters.span_from(start),
+        token: next_character.into(),
+      },
+    })
+  }
+
+  /// Lexes the longest operator in [`Symbol::operators`] that starts with
+  /// `first` and continues with the characters that follow it, so that `<=`
+  /// wins over `<` and `..=` over `..`.
+  fn lex_symbol(characters: &mut CharacterProvider, first: char, position: Position, start: usize) -> Option<Token> {
+    let symbol = Symbol::operators()
+      .filter(|symbol| {
+        let mut rest = symbol.lexeme().chars();
+        rest.next() == Some(first) && rest.enumerate().all(|(n, c)| characters.peek_nth(n) == Some(c))
+      })
+      .max_by_key(|symbol| symbol.lexeme().len())?;
+
+    for _ in 1..symbol.lexeme().chars().count() {
+      characters.next_raw();
+    }
+
+    Some(Token::Symbol(SymbolToken {
+      position,
+      span: characters.span_from(start),
+      symbol,
+    }))
+  }
+
+  /// Reads the body of a string literal after its opening quote. Characters
+  /// are taken verbatim (including newlines and `//`) apart from escapes.
+  ///
+  /// A `{` starts an interpolation: the tokens up to the matching `}` are
+  /// lexed as usual and kept as a part of the string. `\{` and `\}` stand for
+  /// literal braces.
+  fn lex_string(characters: &mut CharacterProvider, position: Position, start: usize) -> Token {
+    let mut parts = Vec::new();
+    let mut lexeme = String::new();
+    let mut text_start = characters.current_offset();
+    loop {
+      match characters.next_raw() {
+        Some('\"') => {
+          let span = characters.span_from(start);
+          if parts.is_empty() {
+            return Token::Literal(LiteralToken {
+              position,
+              span,
+              literal: Literal::String { lexeme },
+            });
           }
-          '[' => {
-            return Some(Token::Symbol(SymbolToken {
-              position: Position {
-                line: characters.current_line(),
-                column: characters.current_column(),
-              },
-              symbol: Symbol::LeftSquareBracket,
-            }))
+
+          if !lexeme.is_empty() {
+            parts.push(StringPart::Text {
+              text: lexeme,
+              span: Span::new(characters.file_id(), text_start, characters.last_offset()),
+            });
           }
-          ']' => {
-            return Some(Token::Symbol(SymbolToken {
-              position: Position {
-                line: characters.current_line(),
-                column: characters.current_column(),
-              },
-              symbol: Symbol::RightSquareBracket,
-            }))
+          return Token::Literal(LiteralToken {
+            position,
+            span,
+            literal: Literal::InterpolatedString { parts },
+          });
+        }
+        Some('{') => {
+          let open = characters.last_offset();
+          if !lexeme.is_empty() {
+            parts.push(StringPart::Text {
+              text: std::mem::take(&mut lexeme),
+              span: Span::new(characters.file_id(), text_start, open),
+            });
           }
-          '\'' => {
-            return Some(Token::Symbol(SymbolToken {
-              position: Position {
-                line: characters.current_line(),
-                column: characters.current_column(),
-              },
-              symbol: Symbol::Apostrophe,
-            }))
+
+          let Some(tokens) = Self::lex_interpolation(characters) else {
+            break;
+          };
+          parts.push(StringPart::Expression {
+            tokens,
+            span: characters.span_from(open),
+          });
+          text_start = characters.current_offset();
+        }
+        Some('\\') => {
+          let escape_start = characters.current_offset() - 1;
+          match Self::read_escape(characters) {
+            Ok(character) => lexeme.push(character),
+            Err(sequence) => characters.push_error(InterpreterError::InvalidEscape {
+              span: characters.span_from(escape_start),
+              sequence,
+            }),
           }
-          '\"' => {
-            let start_of_lexeme = characters.current_column();
-
-            let is_end_of_string = Self::next_char_is(characters, '\"');
-            if let Some(_) = characters.peek()
-              && !is_end_of_string
-            {
-              while let Some(c) = characters.peek() {
-                if *c != '\"' {
-                  lexeme.push(characters.next_with_spaces().unwrap());
-                } else {
-                  characters.next().unwrap();
-
-                  return Some(Token::Literal(LiteralToken {
-                    position: Position {
-                      line: characters.current_line(),
-                      column: start_of_lexeme,
-                    },
-                    literal: Literal::String { lexeme },
-                  }));
-                }
-              }
-            }
+        }
+        Some(character) => lexeme.push(character),
+        None => break,
+      }
+    }
 
-            return Some(Token::Invalid {
-              position: characters.current_position(),
-              error: InterpreterError::UnterminatedString {
-                position: characters.current_position(),
-              },
+    let span = characters.span_from(start);
+    Token::Invalid {
+      position,
+      span,
+      error: InterpreterError::UnterminatedString { span },
+    }
+  }
+
+  /// Lexes the tokens of an interpolation whose `{` was just read, through to
+  /// the matching `}`. Braces of blocks inside the interpolation are matched
+  /// up along the way. Returns `None` if the source ends first.
+  fn lex_interpolation(characters: &mut CharacterProvider) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut depth = 0;
+    loop {
+      let token = Self::build_token(characters)?;
+      match &token {
+        Token::Symbol(SymbolToken {
+          symbol: Symbol::LeftCurlyBracket,
+          ..
+        }) => depth += 1,
+        Token::Symbol(SymbolToken {
+          symbol: Symbol::RightCurlyBracket,
+          position,
+          span,
+        }) => {
+          if depth == 0 {
+            tokens.push(Token::EndOfFile {
+              position: position.clone(),
+              span: Span::new(span.file_id, span.start, span.start),
             });
+            return Some(tokens);
           }
-          _ => {}
+          depth -= 1;
         }
+        Token::Invalid { error, .. } => characters.push_error(error.clone()),
+        _ => {}
       }
-      _ => {}
+      tokens.push(token);
     }
-    
-    Some(Token::Invalid {
-      position: characters.current_position(),
-      error: InterpreterError::UnknownToken {
-        position: characters.current_position(),
-        token: next_character.into(),
-      },
+  }
+
+  /// Reads a `///` or `//!` doc comment whose first `/` was just read. The text
+  /// runs to the end of the line and is kept verbatim.
+  fn lex_doc_comment(characters: &mut CharacterProvider, position: Position, start: usize) -> Token {
+    characters.next_raw();
+    let kind = match characters.next_raw() {
+      Some('!') => DocCommentKind::Inner,
+      _ => DocCommentKind::Outer,
+    };
+
+    let mut text = String::new();
+    while let Some(c) = characters.peek()
+      && *c != '\n'
+    {
+      text.push(characters.next_raw().unwrap());
+    }
+
+    Token::DocComment(DocCommentToken {
+      position,
+      span: characters.span_from(start),
+      kind,
+      text,
     })
   }
 
+  /// Reads the body of a character literal after its opening apostrophe. The
+  /// literal may not span lines, and must hold exactly one codepoint.
+  fn lex_char(characters: &mut CharacterProvider, position: Position, start: usize) -> Token {
+    let mut lexeme = String::new();
+    loop {
+      match characters.peek() {
+        Some('\'') => {
+          characters.next_raw();
+          break;
+        }
+        Some('\n') | None => {
+          let span = characters.span_from(start);
+          return Token::Invalid {
+            position,
+            span,
+            error: InterpreterError::UnterminatedChar { span },
+          };
+        }
+        Some('\\') => {
+          characters.next_raw();
+          let escape_start = characters.current_offset() - 1;
+          match Self::read_escape(characters) {
+            Ok(character) => lexeme.push(character),
+            Err(sequence) => {
+              characters.push_error(InterpreterError::InvalidEscape {
+                span: characters.span_from(escape_start),
+                sequence,
+              });
+              // stand-in so the literal isn't also reported as empty
+              lexeme.push(char::REPLACEMENT_CHARACTER);
+            }
+          }
+        }
+        Some(_) => lexeme.push(characters.next_raw().unwrap()),
+      }
+    }
+
+    let span = characters.span_from(start);
+    let mut chars = lexeme.chars();
+    match (chars.next(), chars.next()) {
+      (Some(lexeme), None) => Token::Literal(LiteralToken {
+        position,
+        span,
+        literal: Literal::Char { lexeme },
+      }),
+      (None, _) => Token::Invalid {
+        position,
+        span,
+        error: InterpreterError::EmptyChar { span },
+      },
+      (Some(_), Some(_)) => Token::Invalid {
+        position,
+        span,
+        error: InterpreterError::MultipleCodepointChar { span, lexeme },
+      },
+    }
+  }
+
+  /// Reads the escape sequence following a `\\`. On failure, the offending
+  /// sequence is returned so it can be reported.
+  fn read_escape(characters: &mut CharacterProvider) -> Result<char, String> {
+    let Some(escaped) = characters.next_raw() else {
+      return Err("\\".into());
+    };
+
+    match escaped {
+      'n' => Ok('\n'),
+      't' => Ok('\t'),
+      'r' => Ok('\r'),
+      '0' => Ok('\0'),
+      '\\' => Ok('\\'),
+      '\"' => Ok('\"'),
+      '\'' => Ok('\''),
+      '{' => Ok('{'),
+      '}' => Ok('}'),
+      'u' => {
+        let mut sequence = String::from("\\u");
+        if characters.peek() != Some(&'{') {
+          return Err(sequence);
+        }
+        sequence.push(characters.next_raw().unwrap());
+
+        while let Some(c) = characters.peek()
+          && c.is_ascii_hexdigit()
+        {
+          sequence.push(characters.next_raw().unwrap());
+        }
+
+        if characters.peek() != Some(&'}') {
+          return Err(sequence);
+        }
+        sequence.push(characters.next_raw().unwrap());
+
+        let digits = &sequence[3..sequence.len() - 1];
+        if digits.is_empty() || digits.len() > 6 {
+          return Err(sequence);
+        }
+
+        u32::from_str_radix(digits, 16)
+          .ok()
+          .and_then(char::from_u32)
+          .ok_or(sequence)
+      }
+      other => Err(format!("\\{other}")),
+    }
+  }
+
+  /// Checks whether the `r` that was just read opens a raw string, i.e. is
+  /// followed by any number of `#` and then a `"`.
+  fn is_raw_string_start(characters: &mut CharacterProvider) -> bool {
+    let mut offset = 0;
+    while characters.peek_nth(offset) == Some('#') {
+      offset += 1;
+    }
+    characters.peek_nth(offset) == Some('\"')
+  }
+
+  /// Reads a raw string after its `r`. The string ends at the first `"` that
+  /// is followed by as many `#` as opened it, and no escapes are processed.
+  fn lex_raw_string(characters: &mut CharacterProvider, position: Position, start: usize) -> Token {
+    // counts the opening hashes and consumes the opening quote
+    let mut hashes = 0;
+    while characters.next_raw() == Some('#') {
+      hashes += 1;
+    }
+
+    let mut lexeme = String::new();
+    while let Some(character) = characters.next_raw() {
+      if character == '\"' {
+        let closing = (0..hashes).take_while(|&n| characters.peek_nth(n) == Some('#')).count();
+        if closing == hashes {
+          for _ in 0..hashes {
+            characters.next_raw();
+          }
+
+          return Token::Literal(LiteralToken {
+            position,
+            span: characters.span_from(start),
+            literal: Literal::String { lexeme },
+          });
+        }
+      }
+
+      lexeme.push(character);
+    }
+
+    let span = characters.span_from(start);
+    Token::Invalid {
+      position,
+      span,
+      error: InterpreterError::UnterminatedString { span },
+    }
+  }
+
+  /// Identifiers follow Unicode's XID rules, which allow letters from any
+  /// script, plus a leading `_`.
+  fn is_identifier_start(c: char) -> bool {
+    c == '_' || c.is_xid_start()
+  }
+
   fn next_char_is(characters: &mut CharacterProvider, character: char) -> bool {
     if let Some(c) = characters.peek()
       && c == &character
@@ -454,6 +479,176 @@ impl Lexer {
     }
   }
 
+  /// Lexes a number literal starting with `first`. Decimal literals may have a
+  /// fraction and exponent, `0x`/`0o`/`0b` prefixes select the radix, `_` can
+  /// separate digits anywhere after the first one, and integers may end in a
+  /// type suffix such as `i32` or `u8`.
+  fn lex_number(characters: &mut CharacterProvider, first: char, position: Position, start: usize) -> Token {
+    let radix = match (first, characters.peek()) {
+      ('0', Some('x')) => Some(16),
+      ('0', Some('o')) => Some(8),
+      ('0', Some('b')) => Some(2),
+      _ => None,
+    };
+
+    if let Some(radix) = radix {
+      return Self::lex_radix_integer(characters, radix, position, start);
+    }
+
+    let mut lexeme = String::from(first);
+    lexeme.push_str(&Self::read_lexeme_while(characters, Self::is_decimal_digit));
+    let is_float = Self::read_fraction(characters, &mut lexeme) | Self::read_exponent(characters, &mut lexeme);
+    let suffix = Self::read_lexeme_while(characters, |c| c.is_ascii_alphanumeric() || c == &'_');
+    let digits: String = lexeme.chars().filter(|c| *c != '_').collect();
+    let span = characters.span_from(start);
+
+    if !is_float {
+      return Self::integer_literal(&digits, 10, &format!("{lexeme}{suffix}"), &suffix, position, span);
+    }
+
+    if !suffix.is_empty() {
+      return Self::number_error(
+        position,
+        span,
+        format!("Invalid suffix `{suffix}` for float literal `{lexeme}`"),
+      );
+    }
+
+    match digits.parse::<f64>() {
+      Ok(lexeme) => Token::Literal(LiteralToken {
+        position,
+        span,
+        literal: Literal::Float { lexeme },
+      }),
+      Err(_) => Self::number_error(position, span, format!("Failed to parse float literal `{lexeme}`")),
+    }
+  }
+
+  /// Lexes the rest of a `0x`, `0o` or `0b` literal. Everything alphanumeric is
+  /// read up front so a stray digit like the `2` in `0b102` is reported instead
+  /// of silently starting a new token.
+  fn lex_radix_integer(characters: &mut CharacterProvider, radix: u32, position: Position, start: usize) -> Token {
+    let prefix = characters.next().unwrap();
+    let body = Self::read_lexeme_while(characters, |c| c.is_ascii_alphanumeric() || c == &'_');
+    let lexeme = format!("0{prefix}{body}");
+    let span = characters.span_from(start);
+
+    let (digits, suffix) = body.split_at(body.find(['i', 'u']).unwrap_or(body.len()));
+    let digits: String = digits.chars().filter(|c| *c != '_').collect();
+
+    if digits.is_empty() {
+      return Self::number_error(position, span, format!("Missing digits after `0{prefix}` in `{lexeme}`"));
+    }
+
+    if let Some(invalid) = digits.chars().find(|c| !c.is_digit(radix)) {
+      return Self::number_error(
+        position,
+        span,
+        format!("Invalid digit `{invalid}` for a base {radix} literal `{lexeme}`"),
+      );
+    }
+
+    Self::integer_literal(&digits, radix, &lexeme, suffix, position, span)
+  }
+
+  /// Builds an integer literal from its separator-free digits, checking that
+  /// the value fits the suffixed type (or `i64` when there is no suffix). The
+  /// magnitude of the type's most negative value is let through, as in
+  /// `-9223372036854775808`, for the binder to check that a `-` comes before.
+  /// Literals have no sign of their own.
+  fn integer_literal(digits: &str, radix: u32, lexeme: &str, suffix: &str, position: Position, span: Span) -> Token {
+    let suffix = if suffix.is_empty() {
+      None
+    } else {
+      match Primitive::from_suffix(suffix) {
+        Some(primitive) if primitive.is_integer() => Some(primitive),
+        _ => {
+          return Self::number_error(
+            position,
+            span,
+            format!("Invalid suffix `{suffix}` for integer literal `{lexeme}`"),
+          );
+        }
+      }
+    };
+
+    let data_type = suffix.unwrap_or(Primitive::I64);
+    let range = data_type.integer_range().expect("integer types have a range");
+    let largest = range.end().max(&-range.start()).unsigned_abs();
+    match u128::from_str_radix(digits, radix) {
+      Ok(value) if value <= largest => Token::Literal(LiteralToken {
+        position,
+        span,
+        literal: Literal::Number {
+          lexeme: value as i128,
+          suffix,
+        },
+      }),
+      _ => Token::Invalid {
+        position,
+        span,
+        error: InterpreterError::LiteralOutOfRange {
+          span,
+          literal: lexeme.into(),
+          data_type: data_type.to_string(),
+          range: format!("{}..={}", range.start(), range.end()),
+        },
+      },
+    }
+  }
+
+  fn number_error(position: Position, span: Span, message: String) -> Token {
+    Token::Invalid {
+      position,
+      span,
+      error: InterpreterError::SyntaxError { span, message },
+    }
+  }
+
+  fn is_decimal_digit(c: &char) -> bool {
+    c.is_ascii_digit() || c == &'_'
+  }
+
+  /// Reads `.digits` onto the lexeme. The period is only consumed when a digit
+  /// follows it, so `1.foo` and `1..2` still lex as separate tokens.
+  fn read_fraction(characters: &mut CharacterProvider, lexeme: &mut String) -> bool {
+    if characters.peek() == Some(&'.')
+      && let Some(digit) = characters.peek_nth(1)
+      && digit.is_ascii_digit()
+    {
+      lexeme.push(characters.next().unwrap());
+      lexeme.push_str(&Self::read_lexeme_while(characters, Self::is_decimal_digit));
+      true
+    } else {
+      false
+    }
+  }
+
+  /// Reads `e` or `E`, an optional sign and digits onto the lexeme. Nothing is
+  /// consumed unless at least one digit is present.
+  fn read_exponent(characters: &mut CharacterProvider, lexeme: &mut String) -> bool {
+    let Some('e' | 'E') = characters.peek().copied() else {
+      return false;
+    };
+
+    let digit_offset = match characters.peek_nth(1) {
+      Some('+' | '-') => 2,
+      _ => 1,
+    };
+
+    if let Some(digit) = characters.peek_nth(digit_offset)
+      && digit.is_ascii_digit()
+    {
+      for _ in 0..digit_offset {
+        lexeme.push(characters.next().unwrap());
+      }
+      lexeme.push_str(&Self::read_lexeme_while(characters, Self::is_decimal_digit));
+      true
+    } else {
+      false
+    }
+  }
+
   fn read_lexeme_while(characters: &mut CharacterProvider, condition: impl Fn(&char) -> bool) -> String {
     let mut lexeme = String::new();
     while let Some(c) = characters.peek()
@@ -464,3 +659,445 @@ impl Lexer {
     lexeme
   }
 }
+
+impl Iterator for Lexer<'_> {
+  type Item = Token;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.finished {
+      return None;
+    }
+
+    let token = Self::build_token(&mut self.characters).unwrap_or_else(|| {
+      self.finished = true;
+      Token::EndOfFile {
+        position: self.characters.current_position(),
+        span: self.characters.span_from(self.characters.current_offset()),
+      }
+    });
+
+    for error in self.characters.take_errors() {
+      self.error_handler.get_mut().push(error);
+    }
+    if let Token::Invalid { error, .. } = &token {
+      self.error_handler.get_mut().push(error.clone());
+    }
+
+    Some(token)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::interpreter::grammar::keyword::Keyword;
+
+  fn lex(source: &str, error_handler: Handle<ErrorHandler>) -> Vec<Token> {
+    Lexer::new(source.as_bytes(), FileId(0), error_handler).collect()
+  }
+
+  fn symbols(source: &str) -> Vec<Symbol> {
+    let error_handler = Handle::new(ErrorHandler::new());
+    let tokens = lex(source, error_handler.clone());
+    assert!(
+      !error_handler.get().had_error(),
+      "`{source}` failed to lex: {:?}",
+      error_handler.get().errors()
+    );
+    tokens
+      .into_iter()
+      .filter_map(|token| match token {
+        Token::Symbol(SymbolToken { symbol, .. }) => Some(symbol),
+        Token::EndOfFile { .. } => None,
+        other => panic!("`{source}` lexed to {other:?}"),
+      })
+      .collect()
+  }
+
+  /// The literals `source` lexes to, which has to lex without errors.
+  fn literals(source: &str) -> Vec<Literal> {
+    let error_handler = Handle::new(ErrorHandler::new());
+    let tokens = lex(source, error_handler.clone());
+    assert!(
+      !error_handler.get().had_error(),
+      "`{source}` failed to lex: {:?}",
+      error_handler.get().errors()
+    );
+    tokens
+      .into_iter()
+      .filter_map(|token| match token {
+        Token::Literal(LiteralToken { literal, .. }) => Some(literal),
+        _ => None,
+      })
+      .collect()
+  }
+
+  /// The errors lexing `source` reports, each with the source it points at.
+  fn errors(source: &str) -> Vec<(String, &str)> {
+    let error_handler = Handle::new(ErrorHandler::new());
+    lex(source, error_handler.clone());
+    let errors = error_handler.get().errors().to_vec();
+    errors
+      .iter()
+      .map(|error| {
+        let span = error.span().expect("lexing errors have a span");
+        (error.to_string(), &source[span.start..span.end])
+      })
+      .collect()
+  }
+
+  #[test]
+  fn lexes_every_operator() {
+    for symbol in Symbol::operators() {
+      assert_eq!(symbols(symbol.lexeme()), [symbol], "`{}`", symbol.lexeme());
+    }
+  }
+
+  #[test]
+  fn comparisons_are_not_swapped() {
+    assert_eq!(symbols("<"), [Symbol::LeftAngledBracket]);
+    assert_eq!(symbols("<="), [Symbol::LeftAngledBracketEquals]);
+    assert_eq!(symbols(">"), [Symbol::RightAngledBracket]);
+    assert_eq!(symbols(">="), [Symbol::RightAngledBracketEquals]);
+  }
+
+  #[test]
+  fn takes_the_longest_match() {
+    assert_eq!(symbols("..="), [Symbol::DoublePeriodEquals]);
+    assert_eq!(symbols("..."), [Symbol::DoublePeriod, Symbol::Period]);
+    assert_eq!(symbols(":::="), [Symbol::DoubleColon, Symbol::ColonEquals]);
+    assert_eq!(symbols("->="), [Symbol::RightArrow, Symbol::Equals]);
+    assert_eq!(symbols("&&="), [Symbol::DoubleAmpersand, Symbol::Equals]);
+    assert_eq!(symbols("|||"), [Symbol::DoublePipe, Symbol::Pipe]);
+    assert_eq!(symbols("!=="), [Symbol::ExclamationPointEquals, Symbol::Equals]);
+  }
+
+  #[test]
+  fn whitespace_splits_operators() {
+    assert_eq!(symbols("< ="), [Symbol::LeftAngledBracket, Symbol::Equals]);
+    assert_eq!(symbols(". ."), [Symbol::Period, Symbol::Period]);
+    assert_eq!(symbols(": :"), [Symbol::Colon, Symbol::Colon]);
+  }
+
+  #[test]
+  fn operators_between_operands() {
+    let error_handler = Handle::new(ErrorHandler::new());
+    let tokens = lex("a := 1..=10", error_handler.clone());
+    let lexemes: Vec<_> = tokens.iter().map(Token::lexeme).collect();
+    assert_eq!(lexemes, ["a", ":=", "1", "..=", "10", "[EOF]"]);
+    assert!(!error_handler.get().had_error());
+  }
+
+  #[test]
+  fn lexes_unicode_identifiers() {
+    let error_handler = Handle::new(ErrorHandler::new());
+    let tokens = lex("größe _名前 δx", error_handler.clone());
+    let lexemes: Vec<_> = tokens.iter().map(Token::lexeme).collect();
+    assert_eq!(lexemes, ["größe", "_名前", "δx", "[EOF]"]);
+    assert!(!error_handler.get().had_error());
+  }
+
+  #[test]
+  fn normalizes_identifiers() {
+    let error_handler = Handle::new(ErrorHandler::new());
+    // precomposed `é`, then `e` followed by a combining acute accent
+    let tokens = lex("caf\u{e9} cafe\u{301}", error_handler);
+    assert_eq!(tokens[0].lexeme(), tokens[1].lexeme());
+  }
+
+  #[test]
+  fn columns_match_the_display() {
+    let error_handler = Handle::new(ErrorHandler::new());
+    let tokens = lex("\t日本 + x", error_handler);
+    let columns: Vec<_> = tokens.iter().map(|token| token.position().column).collect();
+    assert_eq!(&columns[..3], [5, 10, 12]);
+  }
+
+  #[test]
+  fn lexes_lazily() {
+    let error_handler = Handle::new(ErrorHandler::new());
+    let plus_forever = std::io::repeat(b'+');
+    let lexemes: Vec<_> = Lexer::from_reader(plus_forever, FileId(0), error_handler)
+      .take(3)
+      .map(|token| token.lexeme())
+      .collect();
+    assert_eq!(lexemes, ["+", "+", "+"]);
+  }
+
+  #[test]
+  fn reads_characters_split_across_reads() {
+    let source = "größe := \"日本\" // 🎉\n1..=2";
+    let error_handler = Handle::new(ErrorHandler::new());
+    let whole = lex(source, error_handler.clone());
+    let byte_at_a_time = BufReader::with_capacity(1, source.as_bytes());
+    let streamed: Vec<_> = Lexer::new(byte_at_a_time, FileId(0), error_handler.clone()).collect();
+    assert_eq!(format!("{whole:?}"), format!("{streamed:?}"));
+    assert!(!error_handler.get().had_error());
+  }
+
+  #[test]
+  fn replaces_invalid_utf8() {
+    let error_handler = Handle::new(ErrorHandler::new());
+    let tokens: Vec<_> = Lexer::new(&b"a \xff b"[..], FileId(0), error_handler.clone()).collect();
+    assert_eq!(tokens[2].lexeme(), "b");
+    assert_eq!(tokens[2].span().start, 4);
+    assert!(error_handler.get().had_error());
+  }
+
+  #[test]
+  fn splits_interpolated_strings() {
+    let source = r#"x = "hi {name}, next year {age + 1}""#;
+    let tokens = lex(source, Handle::new(ErrorHandler::new()));
+    let Token::Literal(LiteralToken {
+      literal: Literal::InterpolatedString { parts },
+      ..
+    }) = &tokens[2]
+    else {
+      panic!("expected an interpolated string, got {:?}", tokens[2]);
+    };
+
+    let lexemes: Vec<_> = parts.iter().map(StringPart::lexeme).collect();
+    assert_eq!(lexemes, ["hi ", "{name}", ", next year ", "{age + 1}"]);
+    let StringPart::Expression { tokens, .. } = &parts[3] else {
+      unreachable!()
+    };
+    let spans: Vec<_> = tokens
+      .iter()
+      .map(|token| &source[token.span().start..token.span().end])
+      .collect();
+    assert_eq!(spans, ["age", "+", "1", ""]);
+  }
+
+  #[test]
+  fn lexes_every_keyword() {
+    for &keyword in Keyword::ALL {
+      let tokens = lex(keyword.lexeme(), Handle::new(ErrorHandler::new()));
+      assert!(
+        matches!(&tokens[0], Token::Keyword(token) if token.keyword == keyword),
+        "`{}` lexed to {:?}",
+        keyword.lexeme(),
+        tokens[0]
+      );
+    }
+  }
+
+  #[test]
+  fn lexes_keyword_spellings() {
+    let tokens = lex("pub public Self self selfish", Handle::new(ErrorHandler::new()));
+    let keywords: Vec<_> = tokens
+      .iter()
+      .map(|token| match token {
+        Token::Keyword(token) => Some(token.keyword),
+        _ => None,
+      })
+      .collect();
+    assert_eq!(keywords, [
+      Some(Keyword::Public),
+      Some(Keyword::Public),
+      Some(Keyword::_SelfType),
+      Some(Keyword::_Self),
+      None,
+      None
+    ]);
+  }
+
+  #[test]
+  fn contextual_keywords_can_be_names() {
+    let tokens = lex("true in if", Handle::new(ErrorHandler::new()));
+    let names: Vec<_> = tokens
+      .iter()
+      .map(|token| token.as_identifier().map(|name| name.literal))
+      .collect();
+    assert_eq!(names, [
+      Some(Literal::Identifier { lexeme: "true".into() }),
+      Some(Literal::Identifier { lexeme: "in".into() }),
+      None,
+      None
+    ]);
+  }
+
+  /// Lexes `source` losslessly and checks that writing the tokens back out
+  /// gives `source` again.
+  fn round_trip(source: &str) -> Vec<LosslessToken> {
+    let tokens = Lexer::lex_lossless(source, FileId(0), Handle::new(ErrorHandler::new()));
+    let written: String = tokens.iter().map(ToString::to_string).collect();
+    assert_eq!(written, source);
+    tokens
+  }
+
+  fn trivia(tokens: &[LosslessToken]) -> Vec<Trivia> {
+    tokens
+      .iter()
+      .flat_map(|token| token.leading.iter().chain(&token.trailing).cloned())
+      .collect()
+  }
+
+  #[test]
+  fn round_trips_line_endings() {
+    let tokens = round_trip("x := 1;\r\n// note\r\ny := x;\r\n");
+    let newlines: Vec<_> = trivia(&tokens)
+      .into_iter()
+      .filter(|piece| matches!(piece, Trivia::Newline(_)))
+      .collect();
+    assert_eq!(newlines, vec![Trivia::Newline("\r\n".into()); 3]);
+  }
+
+  #[test]
+  fn round_trips_block_comments() {
+    let tokens = round_trip("a /* outer /* inner */ still outer */ b");
+    assert_eq!(
+      tokens[0].trailing[1],
+      Trivia::BlockComment("/* outer /* inner */ still outer */".into())
+    );
+    round_trip("a /* never /* closed */");
+  }
+
+  #[test]
+  fn round_trips_doc_comments() {
+    round_trip("/// Adds one.\n/// Twice.\nf: (x: i64) -> i64 { x + 1 }\n");
+  }
+
+  #[test]
+  fn round_trips_strings() {
+    round_trip("s := \"a {1 + 2} b {\"c\"}\";\n");
+    round_trip("r := r#\"raw \"quoted\" {not} \\n\"#;\n");
+  }
+
+  #[test]
+  fn round_trips_wide_characters() {
+    round_trip("名前 := \"日本語 🦀\"; /* é */ c := '🦀';\n");
+  }
+
+  #[test]
+  fn keeps_trivia_at_the_end() {
+    let tokens = round_trip("x := 1; // last\n\n  /* done */ \n");
+    assert!(matches!(tokens.last().unwrap().token, Token::EndOfFile { .. }));
+    round_trip("   \n");
+    round_trip("");
+  }
+
+  #[test]
+  fn lexes_every_asset() {
+    let assets = std::fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/assets")).unwrap();
+    for asset in assets {
+      let path = asset.unwrap().path();
+      let source = std::fs::read_to_string(&path).unwrap();
+      let error_handler = Handle::new(ErrorHandler::new());
+      lex(&source, error_handler.clone());
+      let errors = error_handler.get().errors().to_vec();
+      assert!(errors.is_empty(), "{} failed to lex: {errors:?}", path.display());
+    }
+  }
+
+  #[test]
+  #[allow(clippy::approx_constant)]
+  fn lexes_floats() {
+    assert_eq!(literals("3.14 1.5e-3 1e10 2E+2"), [
+      Literal::Float { lexeme: 3.14 },
+      Literal::Float { lexeme: 1.5e-3 },
+      Literal::Float { lexeme: 1e10 },
+      Literal::Float { lexeme: 2e2 },
+    ]);
+  }
+
+  #[test]
+  fn periods_after_integers_are_not_fractions() {
+    let lexemes = |source| -> Vec<_> {
+      let tokens = lex(source, Handle::new(ErrorHandler::new()));
+      tokens.iter().map(Token::lexeme).collect()
+    };
+    assert_eq!(lexemes("1.foo"), ["1", ".", "foo", "[EOF]"]);
+    assert_eq!(lexemes("1..2"), ["1", "..", "2", "[EOF]"]);
+    assert_eq!(literals("1..2"), [
+      Literal::Number {
+        lexeme: 1,
+        suffix: None
+      },
+      Literal::Number {
+        lexeme: 2,
+        suffix: None
+      },
+    ]);
+  }
+
+  #[test]
+  fn lexes_string_escapes() {
+    let string = |lexeme: &str| Literal::String { lexeme: lexeme.into() };
+    assert_eq!(literals(r#""a\nb\tc\\d\"e\u{41}\u{1F600}""#), [string("a\nb\tc\\d\"eA😀")]);
+    assert_eq!(literals(r#""http://x""#), [string("http://x")]);
+    assert_eq!(literals("\"one\ntwo\""), [string("one\ntwo")]);
+  }
+
+  #[test]
+  fn lexes_raw_strings() {
+    let string = |lexeme: &str| Literal::String { lexeme: lexeme.into() };
+    assert_eq!(literals(r#"r"a\nb""#), [string(r"a\nb")]);
+    assert_eq!(literals(r###"r#"say "hi""#"###), [string(r#"say "hi""#)]);
+  }
+
+  #[test]
+  fn reports_invalid_escapes_where_they_are() {
+    assert_eq!(errors(r#""bad \q here""#), [(
+      "Invalid escape sequence `\\q`".to_string(),
+      r"\q"
+    )]);
+  }
+
+  #[test]
+  fn lexes_chars() {
+    let char = |lexeme| Literal::Char { lexeme };
+    assert_eq!(literals(r"'a' '\n' '\u{1F600}' '日'"), [
+      char('a'),
+      char('\n'),
+      char('😀'),
+      char('日')
+    ]);
+  }
+
+  #[test]
+  fn reports_malformed_chars() {
+    assert_eq!(errors("''"), [("Empty character literal".to_string(), "''")]);
+    assert_eq!(errors("'ab'"), [(
+      "Character literal `'ab'` must contain exactly one codepoint".to_string(),
+      "'ab'"
+    )]);
+    assert_eq!(errors("'a"), [("Unterminated character literal".to_string(), "'a")]);
+  }
+
+  #[test]
+  fn block_comments_nest() {
+    let tokens = lex("1 /* a /* b */ c */ + 2", Handle::new(ErrorHandler::new()));
+    let lexemes: Vec<_> = tokens.iter().map(Token::lexeme).collect();
+    assert_eq!(lexemes, ["1", "+", "2", "[EOF]"]);
+    assert_eq!(errors("x := 1; /* open /* inner */ still open"), [(
+      "Unterminated block comment".to_string(),
+      "/* open /* inner */ still open"
+    )]);
+  }
+
+  #[test]
+  fn lexes_integers_in_every_base() {
+    let number = |lexeme, suffix| Literal::Number { lexeme, suffix };
+    assert_eq!(literals("0xFF 0b1010 0o755 1_000_000 10i32 255u8"), [
+      number(255, None),
+      number(10, None),
+      number(493, None),
+      number(1_000_000, None),
+      number(10, Some(Primitive::I32)),
+      number(255, Some(Primitive::U8)),
+    ]);
+  }
+
+  #[test]
+  fn reports_malformed_integers() {
+    assert_eq!(errors("256u8"), [(
+      "Integer literal `256u8` is out of range for `u8` (0..=255)".to_string(),
+      "256u8"
+    )]);
+    assert_eq!(errors("0x"), [("Missing digits after `0x` in `0x`".to_string(), "0x")]);
+    assert_eq!(errors("1.5u8"), [(
+      "Invalid suffix `u8` for float literal `1.5`".to_string(),
+      "1.5u8"
+    )]);
+  }
+}
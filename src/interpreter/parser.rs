@@ -4,12 +4,33 @@ use foxy_utils::types::handle::Handle;
 use super::{
   error::{error_handler::ErrorHandler, InterpreterError},
   grammar::{
-    expression::Expression,
+    constant::Constant,
+    enumeration::Enum,
+    expression::{Expression, InterpolationPart},
+    function::{Function, Parameter, Receiver, ReceiverKind},
+    generic::{GenericArgument, GenericParameter},
+    implementation::Impl,
+    import::{Import, ImportTree, Include},
+    item::{Item, ItemKind},
     keyword::Keyword,
-    literal::Literal,
+    literal::{Literal, StringPart},
+    record::{Field, FieldInitializer, Record},
+    span::Span,
+    statement::Statement,
     symbol::Symbol,
     syntax_tree::SyntaxTree,
-    token::{KeywordToken, LiteralToken, Position, SymbolToken, Token, TokenDiscriminants},
+    token::{
+      DocCommentKind,
+      DocCommentToken,
+      KeywordToken,
+      LiteralToken,
+      Position,
+      SymbolToken,
+      Token,
+      TokenDiscriminants,
+    },
+    trait_declaration::Trait,
+    type_expression::TypeExpression,
   },
   util::token_provider::{Next, TokenProvider},
 };
@@ -24,7 +45,7 @@ enum DelimiterType {
 
 #[derive(Debug, Eq)]
 struct Delimiter {
-  position: Position,
+  span: Span,
   delimiter: DelimiterType,
 }
 
@@ -38,8 +59,8 @@ impl Delimiter {
     }
   }
 
-  fn position(&self) -> &Position {
-    &self.position
+  fn span(&self) -> Span {
+    self.span
   }
 }
 
@@ -57,9 +78,10 @@ impl TryFrom<Token> for Delimiter {
       return Err(());
     };
 
+    // a lone `>` is a comparison, not a closing delimiter
     if !matches!(
       symbol,
-      Symbol::RightParenthesis | Symbol::RightAngledBracket | Symbol::RightCurlyBracket | Symbol::RightSquareBracket
+      Symbol::RightParenthesis | Symbol::RightCurlyBracket | Symbol::RightSquareBracket
     ) {
       return Err(());
     }
@@ -78,7 +100,7 @@ impl TryFrom<Token> for Delimiter {
 
     Ok(Self {
       delimiter,
-      position: token.position().clone(),
+      span: token.span(),
     })
   }
 }
@@ -86,6 +108,9 @@ impl TryFrom<Token> for Delimiter {
 pub struct Parser {
   error_handler: Handle<ErrorHandler>,
   delimiter_stack: Vec<Delimiter>,
+  /// Whether a `{` after a name opens a block rather than a record literal,
+  /// as it does after the condition of an `if`.
+  no_records: bool,
 }
 
 impl Parser {
@@ -93,35 +118,1390 @@ impl Parser {
     Self {
       error_handler,
       delimiter_stack: Default::default(),
+      no_records: false,
     }
   }
 
-  pub fn parse(&mut self, tokens: &[Token]) -> SyntaxTree {
-    let mut tokens = TokenProvider::new(tokens);
-    let root = self.expression(&mut tokens);
+  /// Parses the tokens as they come, never looking more than a few tokens
+  /// ahead. A program is a sequence of items, the last of which may be an
+  /// expression without a `;` that gives the program its value.
+  pub fn parse<'a>(&mut self, tokens: impl IntoIterator<Item = Token, IntoIter: 'a>) -> SyntaxTree {
+    let mut tokens = TokenProvider::new(tokens.into_iter());
+    let mut items = Vec::new();
+    let mut docs = Vec::new();
 
-    let next = tokens.peek().cloned();
-    match next {
-      Next::Token(t) => {
-        self.error_handler.get_mut().push(InterpreterError::ParseError {
-          position: t.position().clone(),
-          message: format!("Expected EOF but got `{}`", t),
+    loop {
+      self.skip_empty_statements(&mut tokens);
+      if !matches!(tokens.peek(), Next::Token(_)) {
+        break;
+      }
+
+      let (outer, inner) = tokens
+        .take_docs()
+        .into_iter()
+        .partition(|doc| doc.kind == DocCommentKind::Outer);
+      docs.extend(inner);
+      items.push(Item {
+        docs: outer,
+        kind: self.item(&mut tokens),
+      });
+    }
+    docs.extend(tokens.take_docs());
+
+    let (Next::EndOfFile { position, span } | Next::EndOfStream { position, span }) = tokens.peek().cloned() else {
+      unreachable!("parsing stops at the end of the tokens")
+    };
+
+    SyntaxTree {
+      items,
+      docs,
+      eof: Token::EndOfFile { position, span },
+    }
+  }
+
+  fn item(&mut self, tokens: &mut TokenProvider) -> ItemKind {
+    if let Some(item) = self.import(tokens) {
+      return item;
+    }
+
+    if self.at_generic_declaration(tokens) {
+      let Next::Token(token) = tokens.next() else {
+        unreachable!("the name was just peeked")
+      };
+      let name = token.as_identifier().expect("the name was just peeked");
+      let generics = self.generic_parameters(tokens);
+      return match self.declared_item(tokens, name.clone(), generics) {
+        Some(item) => item,
+        None => ItemKind::Statement(self.declaration(tokens, name)),
+      };
+    }
+
+    if let Some(name) = self.match_declaration(tokens) {
+      return match self.declared_item(tokens, name.clone(), Vec::new()) {
+        Some(item) => item,
+        None => ItemKind::Statement(self.declaration(tokens, name)),
+      };
+    }
+
+    ItemKind::Statement(self.statement(tokens))
+  }
+
+  /// Parses the rest of a declaration of `name` if it declares an item, such
+  /// as a function or an enum, rather than a variable.
+  fn declared_item(
+    &mut self,
+    tokens: &mut TokenProvider,
+    name: LiteralToken,
+    generics: Vec<GenericParameter>,
+  ) -> Option<ItemKind> {
+    if self.at_function_signature(tokens) {
+      return Some(ItemKind::Function(self.function(tokens, name, generics)));
+    }
+
+    let keyword = self.declaration_keyword(tokens);
+    if !generics.is_empty() && !matches!(keyword, Some(Keyword::Type | Keyword::Impl)) {
+      self.error(InterpreterError::ParseError {
+        span: name.span,
+        message: "Only functions, types and `impl`s can have type parameters".into(),
+      });
+    }
+
+    match keyword? {
+      Keyword::Enum => Some(ItemKind::Enum(self.enumeration(tokens, name))),
+      Keyword::Type => Some(ItemKind::Record(self.record(tokens, name, generics))),
+      Keyword::Impl => Some(ItemKind::Impl(self.implementation(tokens, name, generics))),
+      Keyword::Trait => Some(ItemKind::Trait(self.trait_declaration(tokens, name))),
+      Keyword::Const => Some(ItemKind::Constant(self.constant(tokens, name))),
+      _ => None,
+    }
+  }
+
+  fn statement(&mut self, tokens: &mut TokenProvider) -> Statement {
+    if let Some(name) = self.match_declaration(tokens) {
+      if self.declared_item(tokens, name.clone(), Vec::new()).is_some() {
+        self.error(InterpreterError::ParseError {
+          span: name.span,
+          message: "Only variables can be declared inside a block".into(),
+        });
+        return Statement::Expression(Expression::Block {
+          statements: Vec::new(),
+          span: name.span,
+        });
+      }
+      return self.declaration(tokens, name);
+    }
+
+    let errors = self.error_handler.get().errors().len();
+    let expression = self.expression(tokens);
+    self.finish_statement(tokens, expression, errors)
+  }
+
+  /// Ends the statement made of `expression` with a `;`, if it needs one.
+  /// `errors` is how many errors there were before `expression`.
+  fn finish_statement(&mut self, tokens: &mut TokenProvider, expression: Expression, errors: usize) -> Statement {
+    if self.error_handler.get().errors().len() > errors {
+      self.synchronize(tokens);
+      return Statement::Expression(expression);
+    }
+
+    if let Some(semicolon) = self.match_symbols(tokens, Symbol::Semicolon.into()) {
+      return Statement::Semicolon {
+        span: expression.span().to(semicolon.span),
+        expression,
+      };
+    }
+
+    if !expression.is_block_like() && !self.at_end_of_block(tokens) {
+      self.error(InterpreterError::ParseError {
+        span: expression.span().after(),
+        message: "Expected `;` after expression".into(),
+      });
+    }
+
+    Statement::Expression(expression)
+  }
+
+  /// Takes the `///` doc comments written above what comes next. `//!` ones
+  /// document the program as a whole, so only those at the top level count.
+  fn outer_docs(tokens: &mut TokenProvider) -> Vec<DocCommentToken> {
+    let mut docs = tokens.take_docs();
+    docs.retain(|doc| doc.kind == DocCommentKind::Outer);
+    docs
+  }
+
+  /// A statement starting with a name and then `:` or `:=` declares that
+  /// name. Consumes the name if so. Keywords that can't be names, along with
+  /// `true` and `false`, which would always be read as literals, are reported
+  /// if they are declared.
+  fn match_declaration(&mut self, tokens: &mut TokenProvider) -> Option<LiteralToken> {
+    let Next::Token(token) = tokens.peek() else {
+      return None;
+    };
+    let keyword = match token {
+      Token::Keyword(KeywordToken { keyword, text, .. })
+        if !keyword.is_contextual() || matches!(keyword, Keyword::True | Keyword::False) =>
+      {
+        Some(text.clone())
+      }
+      _ => None,
+    };
+    let name = match token {
+      Token::Keyword(keyword) => keyword.as_name(),
+      token => token.as_identifier()?,
+    };
+
+    let Next::Token(Token::Symbol(SymbolToken {
+      symbol: Symbol::Colon | Symbol::ColonEquals,
+      ..
+    })) = tokens.peek_nth(1)
+    else {
+      return None;
+    };
+
+    tokens.next();
+    if let Some(keyword) = keyword {
+      self.error(InterpreterError::ParseError {
+        span: name.span,
+        message: format!("Cannot declare `{keyword}`, which is a keyword"),
+      });
+    }
+    Some(name)
+  }
+
+  /// Parses the rest of `name: Type = value;`, `name: Type;` or
+  /// `name := value;`.
+  fn declaration(&mut self, tokens: &mut TokenProvider, name: LiteralToken) -> Statement {
+    let errors = self.error_handler.get().errors().len();
+    let (data_type, initializer) = if self.match_symbols(tokens, Symbol::ColonEquals.into()).is_some() {
+      (None, Some(self.expression(tokens)))
+    } else {
+      self.match_symbols(tokens, Symbol::Colon.into());
+      let data_type = self.type_expression(tokens);
+      let initializer = self
+        .match_symbols(tokens, Symbol::Equals.into())
+        .map(|_| self.expression(tokens));
+      (Some(data_type), initializer)
+    };
+
+    let mut span = match (&data_type, &initializer) {
+      (_, Some(initializer)) => name.span.to(initializer.span()),
+      (Some(data_type), None) => name.span.to(data_type.span()),
+      (None, None) => name.span,
+    };
+
+    if self.error_handler.get().errors().len() > errors {
+      self.synchronize(tokens);
+    } else if let Some(semicolon) = self.match_symbols(tokens, Symbol::Semicolon.into()) {
+      span = span.to(semicolon.span);
+    } else if !self.at_end_of_block(tokens) {
+      self.error(InterpreterError::ParseError {
+        span: span.after(),
+        message: "Expected `;` after declaration".into(),
+      });
+    }
+
+    Statement::Declaration {
+      docs: Vec::new(),
+      name,
+      data_type,
+      initializer,
+      span,
+    }
+  }
+
+  /// Whether the `:` after a declared name is followed by a function
+  /// signature rather than a type, past any `export` or `pub`. A `(` can only
+  /// open a parameter list, while `()` is a function's empty parameter list as
+  /// long as a `->` or a `= {` body comes after it, and the `()` type
+  /// otherwise.
+  fn at_function_signature(&mut self, tokens: &mut TokenProvider) -> bool {
+    if !Self::peek_symbol(tokens, 0, Symbol::Colon) {
+      return false;
+    }
+
+    let mut n = 1;
+    if Self::peek_keyword(tokens, n, Keyword::Export) || Self::peek_keyword(tokens, n, Keyword::Public) {
+      n += 1;
+    }
+    match tokens.peek_nth(n) {
+      Next::Token(Token::Symbol(SymbolToken {
+        symbol: Symbol::LeftParenthesis,
+        ..
+      })) => true,
+      Next::Token(Token::Literal(LiteralToken {
+        literal: Literal::Void, ..
+      })) => {
+        Self::peek_symbol(tokens, n + 1, Symbol::RightArrow)
+          || Self::peek_symbol(tokens, n + 1, Symbol::Equals)
+            && Self::peek_symbol(tokens, n + 2, Symbol::LeftCurlyBracket)
+      }
+      _ => false,
+    }
+  }
+
+  /// Parses `import path;`, `export import path;` or `include path;`, if one
+  /// starts here.
+  fn import(&mut self, tokens: &mut TokenProvider) -> Option<ItemKind> {
+    if let Some(keyword) = self.match_keyword(tokens, Keyword::Include) {
+      let errors = self.error_handler.get().errors().len();
+      let path = match self.import_tree(tokens, false) {
+        Some(ImportTree::Path { segments, alias: None }) => segments,
+        Some(tree) => {
+          self.error(InterpreterError::ParseError {
+            span: keyword.span.after(),
+            message: format!("Expected a module to include but got `{tree}`"),
+          });
+          Vec::new()
+        }
+        None => Vec::new(),
+      };
+      let end = path.last().map_or(keyword.span, |segment| segment.span);
+      let span = self.end_import(tokens, keyword.span.to(end), errors);
+      return Some(ItemKind::Include(Include { path, span }));
+    }
+
+    let exported = Self::peek_keyword(tokens, 0, Keyword::Export) && Self::peek_keyword(tokens, 1, Keyword::Import);
+    let start = match exported {
+      true => self.match_keyword(tokens, Keyword::Export)?.span,
+      false => self.match_keyword(tokens, Keyword::Import)?.span,
+    };
+    let errors = self.error_handler.get().errors().len();
+    self.match_keyword(tokens, Keyword::Import);
+    let tree = self.import_tree(tokens, false).unwrap_or(ImportTree::List {
+      prefix: Vec::new(),
+      items: Vec::new(),
+    });
+    let end = tokens.previous_valid().span();
+    let span = self.end_import(tokens, start.to(end), errors);
+    Some(ItemKind::Import(Import { exported, tree, span }))
+  }
+
+  /// Parses what an import names: a path whose parts are separated by `.` or
+  /// `::`, ending in a name, a `*` or a `{ ... }` list of more of them.
+  /// `self` can only be named in a list.
+  fn import_tree(&mut self, tokens: &mut TokenProvider, in_list: bool) -> Option<ImportTree> {
+    let mut segments = Vec::new();
+    loop {
+      let Next::Token(token) = tokens.peek().cloned() else {
+        return None;
+      };
+      match token {
+        Token::Symbol(SymbolToken {
+          symbol: Symbol::Asterisk,
+          ..
+        }) if !segments.is_empty() => {
+          tokens.next();
+          return Some(ImportTree::Glob { prefix: segments });
+        }
+        Token::Symbol(SymbolToken {
+          symbol: Symbol::LeftCurlyBracket,
+          span,
+          ..
+        }) => {
+          tokens.next();
+          return Some(self.import_list(tokens, segments, span));
+        }
+        Token::Keyword(keyword) if keyword.keyword == Keyword::_Self && in_list && segments.is_empty() => {
+          tokens.next();
+          segments.push(keyword.as_name());
+          break;
+        }
+        token => match token.as_identifier() {
+          Some(name) => {
+            tokens.next();
+            segments.push(name);
+          }
+          None => {
+            self.error(InterpreterError::ParseError {
+              span: token.span(),
+              message: format!("Expected a module path but got `{token}`"),
+            });
+            return None;
+          }
+        },
+      }
+
+      if self
+        .match_symbols(tokens, Symbol::Period | Symbol::DoubleColon)
+        .is_none()
+      {
+        break;
+      }
+    }
+
+    let alias = match self.match_keyword(tokens, Keyword::As) {
+      Some(_) => Some(self.member_name(tokens, "alias")?),
+      None => None,
+    };
+    Some(ImportTree::Path { segments, alias })
+  }
+
+  /// Parses the rest of the `{ ... }` list of an import after the `{` at
+  /// `open`.
+  fn import_list(&mut self, tokens: &mut TokenProvider, prefix: Vec<LiteralToken>, open: Span) -> ImportTree {
+    self.delimiter_stack.push(Delimiter {
+      delimiter: DelimiterType::Curly,
+      span: open,
+    });
+
+    let mut items = Vec::new();
+    while !Self::peek_symbol(tokens, 0, Symbol::RightCurlyBracket) {
+      match self.import_tree(tokens, true) {
+        Some(item) => items.push(item),
+        None => break,
+      }
+      if self.match_symbols(tokens, Symbol::Comma.into()).is_none() {
+        break;
+      }
+    }
+
+    self.pair_delimiter(tokens, Symbol::RightCurlyBracket);
+    ImportTree::List { prefix, items }
+  }
+
+  /// Ends an import spanning `span` with a `;`, giving the span with it.
+  /// `errors` is how many errors there were before the import.
+  fn end_import(&mut self, tokens: &mut TokenProvider, span: Span, errors: usize) -> Span {
+    if self.error_handler.get().errors().len() > errors {
+      self.synchronize(tokens);
+      return span;
+    }
+
+    match self.match_symbols(tokens, Symbol::Semicolon.into()) {
+      Some(semicolon) => span.to(semicolon.span),
+      None => {
+        self.error(InterpreterError::ParseError {
+          span: span.after(),
+          message: "Expected `;` after import".into(),
+        });
+        span
+      }
+    }
+  }
+
+  /// The keyword saying what kind of item the `:` after a declared name
+  /// starts, such as the `enum` in `logic: export enum = { ... }`.
+  fn declaration_keyword(&mut self, tokens: &mut TokenProvider) -> Option<Keyword> {
+    if !Self::peek_symbol(tokens, 0, Symbol::Colon) {
+      return None;
+    }
+
+    let mut n = 1;
+    if Self::peek_keyword(tokens, n, Keyword::Export) || Self::peek_keyword(tokens, n, Keyword::Public) {
+      n += 1;
+    }
+
+    [
+      Keyword::Enum,
+      Keyword::Type,
+      Keyword::Impl,
+      Keyword::Trait,
+      Keyword::Const,
+    ]
+    .into_iter()
+    .find(|keyword| Self::peek_keyword(tokens, n, *keyword))
+  }
+
+  fn peek_keyword(tokens: &mut TokenProvider, n: usize, keyword: Keyword) -> bool {
+    matches!(tokens.peek_nth(n), Next::Token(Token::Keyword(token)) if token.keyword == keyword)
+  }
+
+  /// Parses the rest of `NAME: const Type = value;`, which may be `export`ed.
+  /// The type may be left out.
+  fn constant(&mut self, tokens: &mut TokenProvider, name: LiteralToken) -> Constant {
+    let errors = self.error_handler.get().errors().len();
+    self.match_symbols(tokens, Symbol::Colon.into());
+    let exported =
+      self.match_keyword(tokens, Keyword::Export).is_some() || self.match_keyword(tokens, Keyword::Public).is_some();
+    let keyword = self.match_keyword(tokens, Keyword::Const);
+    let data_type = match Self::peek_symbol(tokens, 0, Symbol::Equals) {
+      true => None,
+      false => Some(self.type_expression(tokens)),
+    };
+
+    let value = match self.match_symbols(tokens, Symbol::Equals.into()) {
+      Some(_) => self.expression(tokens),
+      None => {
+        let span = match (&data_type, keyword) {
+          (Some(data_type), _) => data_type.span(),
+          (None, Some(keyword)) => keyword.span,
+          (None, None) => name.span,
+        };
+        self.error(InterpreterError::ParseError {
+          span: span.after(),
+          message: "Expected `=` and the value of the constant".into(),
+        });
+        Expression::Block {
+          statements: Vec::new(),
+          span: span.after(),
+        }
+      }
+    };
+
+    let mut span = name.span.to(value.span());
+    if self.error_handler.get().errors().len() > errors {
+      self.synchronize(tokens);
+    } else if let Some(semicolon) = self.match_symbols(tokens, Symbol::Semicolon.into()) {
+      span = span.to(semicolon.span);
+    } else {
+      self.error(InterpreterError::ParseError {
+        span: span.after(),
+        message: "Expected `;` after constant".into(),
+      });
+    }
+
+    Constant {
+      name,
+      exported,
+      data_type,
+      value,
+      span,
+    }
+  }
+
+  /// Parses the rest of `name: enum = { variant, ... }`, which may be
+  /// `export`ed.
+  fn enumeration(&mut self, tokens: &mut TokenProvider, name: LiteralToken) -> Enum {
+    self.match_symbols(tokens, Symbol::Colon.into());
+    let exported =
+      self.match_keyword(tokens, Keyword::Export).is_some() || self.match_keyword(tokens, Keyword::Public).is_some();
+    let keyword = self.match_keyword(tokens, Keyword::Enum);
+    let (variants, close) = self.braced_names(tokens, keyword.map_or(name.span, |keyword| keyword.span), "variant");
+
+    Enum {
+      span: name.span.to(close),
+      name,
+      exported,
+      variants,
+    }
+  }
+
+  /// Parses the rest of `Type: impl = { method: (...) { ... } ... }`, or of
+  /// `Type: impl Trait = { ... }`.
+  fn implementation(
+    &mut self,
+    tokens: &mut TokenProvider,
+    target: LiteralToken,
+    generics: Vec<GenericParameter>,
+  ) -> Impl {
+    self.match_symbols(tokens, Symbol::Colon.into());
+    let keyword = self.match_keyword(tokens, Keyword::Impl);
+    let trait_name = match Self::peek_symbol(tokens, 0, Symbol::Equals) {
+      true => None,
+      false => Some(self.type_expression(tokens)),
+    };
+
+    let span = match (&trait_name, keyword) {
+      (Some(trait_name), _) => trait_name.span(),
+      (None, Some(keyword)) => keyword.span,
+      (None, None) => target.span,
+    };
+    let (methods, close) = self.method_list(tokens, span, "an `impl`");
+    Impl {
+      span: target.span.to(close),
+      target,
+      generics,
+      trait_name,
+      methods,
+    }
+  }
+
+  /// Parses the rest of `Name: trait = { method: (...) -> Type; ... }`, which
+  /// may be `export`ed.
+  fn trait_declaration(&mut self, tokens: &mut TokenProvider, name: LiteralToken) -> Trait {
+    self.match_symbols(tokens, Symbol::Colon.into());
+    let exported =
+      self.match_keyword(tokens, Keyword::Export).is_some() || self.match_keyword(tokens, Keyword::Public).is_some();
+    let keyword = self.match_keyword(tokens, Keyword::Trait);
+
+    let span = keyword.map_or(name.span, |keyword| keyword.span);
+    let (methods, close) = self.method_list(tokens, span, "a trait");
+    Trait {
+      span: name.span.to(close),
+      name,
+      exported,
+      methods,
+    }
+  }
+
+  /// Parses `= { method: (...) { ... } ... }` after the token at `span`,
+  /// giving the methods and the span of the `}`. `within` names what the
+  /// methods are declared in, for the error if something else is.
+  fn method_list(&mut self, tokens: &mut TokenProvider, span: Span, within: &str) -> (Vec<Function>, Span) {
+    if self.match_symbols(tokens, Symbol::Equals.into()).is_none() {
+      self.error(InterpreterError::ParseError {
+        span: span.after(),
+        message: "Expected `=`".into(),
+      });
+    }
+    let Some(open) = self.match_symbols(tokens, Symbol::LeftCurlyBracket.into()) else {
+      let span = tokens.previous_valid().span().after();
+      self.error(InterpreterError::ParseError {
+        span,
+        message: "Expected `{`".into(),
+      });
+      self.synchronize(tokens);
+      return (Vec::new(), span);
+    };
+    self.delimiter_stack.push(Delimiter {
+      delimiter: DelimiterType::Curly,
+      span: open.span,
+    });
+
+    let mut methods = Vec::new();
+    loop {
+      self.skip_empty_statements(tokens);
+      let docs = Self::outer_docs(tokens);
+      if self.at_end_of_block(tokens) {
+        break;
+      }
+
+      let Some(name) = self.member_name(tokens, "method") else {
+        self.synchronize(tokens);
+        continue;
+      };
+      let generics = self.generic_parameters(tokens);
+      if !self.at_function_signature(tokens) {
+        self.error(InterpreterError::ParseError {
+          span: name.span,
+          message: format!("Expected a method, only methods can be declared in {within}"),
+        });
+        self.synchronize(tokens);
+        continue;
+      }
+      methods.push(Function {
+        docs,
+        ..self.function(tokens, name, generics)
+      });
+    }
+
+    let close = self.pair_delimiter(tokens, Symbol::RightCurlyBracket);
+    (methods, close.span())
+  }
+
+  /// Parses the rest of `Name: type = { field: Type; ... }`, which may be
+  /// `export`ed, and whose fields may be `pub` or `public`.
+  fn record(&mut self, tokens: &mut TokenProvider, name: LiteralToken, generics: Vec<GenericParameter>) -> Record {
+    self.match_symbols(tokens, Symbol::Colon.into());
+    let exported =
+      self.match_keyword(tokens, Keyword::Export).is_some() || self.match_keyword(tokens, Keyword::Public).is_some();
+    let keyword = self.match_keyword(tokens, Keyword::Type);
+
+    let span = keyword.map_or(name.span, |keyword| keyword.span);
+    if self.match_symbols(tokens, Symbol::Equals.into()).is_none() {
+      self.error(InterpreterError::ParseError {
+        span: span.after(),
+        message: "Expected `=`".into(),
+      });
+    }
+    let Some(open) = self.match_symbols(tokens, Symbol::LeftCurlyBracket.into()) else {
+      let span = tokens.previous_valid().span().after();
+      self.error(InterpreterError::ParseError {
+        span,
+        message: "Expected `{`".into(),
+      });
+      self.synchronize(tokens);
+      return Record {
+        span: name.span.to(span),
+        name,
+        generics,
+        exported,
+        fields: Vec::new(),
+        methods: Vec::new(),
+      };
+    };
+    self.delimiter_stack.push(Delimiter {
+      delimiter: DelimiterType::Curly,
+      span: open.span,
+    });
+
+    let mut fields = Vec::new();
+    let mut methods = Vec::new();
+    loop {
+      self.skip_empty_statements(tokens);
+      let docs = Self::outer_docs(tokens);
+      if self.at_end_of_block(tokens) {
+        break;
+      }
+
+      let errors = self.error_handler.get().errors().len();
+      let Some(name) = self.member_name(tokens, "field") else {
+        self.synchronize(tokens);
+        continue;
+      };
+      // methods end with their body, so they need no `;` after them
+      let generics = self.generic_parameters(tokens);
+      if !generics.is_empty() || self.at_function_signature(tokens) {
+        methods.push(Function {
+          docs,
+          ..self.function(tokens, name, generics)
+        });
+        continue;
+      }
+
+      if let Some(field) = self.field(tokens, name) {
+        fields.push(Field { docs, ..field });
+      }
+      if self.error_handler.get().errors().len() > errors {
+        self.synchronize(tokens);
+      } else if self.match_symbols(tokens, Symbol::Semicolon.into()).is_none() && !self.at_end_of_block(tokens) {
+        let span = tokens.previous_valid().span().after();
+        self.error(InterpreterError::ParseError {
+          span,
+          message: "Expected `;` after field".into(),
+        });
+        self.synchronize(tokens);
+      }
+    }
+
+    let close = self.pair_delimiter(tokens, Symbol::RightCurlyBracket);
+    Record {
+      span: name.span.to(close.span()),
+      name,
+      generics,
+      exported,
+      fields,
+      methods,
+    }
+  }
+
+  /// Reads the name starting a field or method in the body of a type. `kind`
+  /// says what was expected, for the error if it isn't a name.
+  fn member_name(&mut self, tokens: &mut TokenProvider, kind: &str) -> Option<LiteralToken> {
+    match tokens.next() {
+      Next::Token(token) => {
+        let name = token.as_identifier();
+        if name.is_none() {
+          self.error(InterpreterError::ParseError {
+            span: token.span(),
+            message: format!("Expected {kind} name but got `{token}`"),
+          });
+        }
+        name
+      }
+      _ => None,
+    }
+  }
+
+  /// Parses the rest of `name: Type` or `name: pub Type` in the body of a
+  /// record type.
+  fn field(&mut self, tokens: &mut TokenProvider, name: LiteralToken) -> Option<Field> {
+    if self.match_symbols(tokens, Symbol::Colon.into()).is_none() {
+      self.error(InterpreterError::ParseError {
+        span: name.span.after(),
+        message: "Expected `:` after field name".into(),
+      });
+      return None;
+    }
+    let public = self.match_keyword(tokens, Keyword::Public).is_some();
+    let data_type = self.type_expression(tokens);
+
+    Some(Field {
+      docs: Vec::new(),
+      span: name.span.to(data_type.span()),
+      name,
+      public,
+      data_type,
+    })
+  }
+
+  /// Whether the `{` `n` tokens ahead starts the fields of a record literal
+  /// after a type name: `{}`, or a name followed by `,`, `=` or `}`.
+  fn at_record_literal(&mut self, tokens: &mut TokenProvider, n: usize) -> bool {
+    if self.no_records || !Self::peek_symbol(tokens, n, Symbol::LeftCurlyBracket) {
+      return false;
+    }
+
+    if Self::peek_symbol(tokens, n + 1, Symbol::RightCurlyBracket) {
+      return true;
+    }
+
+    let is_name = matches!(tokens.peek_nth(n + 1), Next::Token(token) if token.as_identifier().is_some());
+    is_name
+      && (Self::peek_symbol(tokens, n + 2, Symbol::Comma)
+        || Self::peek_symbol(tokens, n + 2, Symbol::Equals)
+        || Self::peek_symbol(tokens, n + 2, Symbol::RightCurlyBracket))
+  }
+
+  /// Parses the fields of a record literal up to its `}`, after the `{` at
+  /// `open` was read and pushed as a delimiter. `first` is a field already
+  /// read, if any.
+  fn record_literal(
+    &mut self,
+    tokens: &mut TokenProvider,
+    name: Option<LiteralToken>,
+    arguments: Vec<GenericArgument>,
+    open: Span,
+    first: Option<FieldInitializer>,
+  ) -> Expression {
+    let outer = std::mem::replace(&mut self.no_records, false);
+
+    let mut fields: Vec<_> = first.into_iter().collect();
+    // the first field was already followed by a `,`
+    while fields.is_empty() || self.match_symbols(tokens, Symbol::Comma.into()).is_some() {
+      if Self::peek_symbol(tokens, 0, Symbol::RightCurlyBracket) {
+        break;
+      }
+
+      let field = match tokens.peek() {
+        Next::Token(token) => token.as_identifier().ok_or_else(|| (token.span(), token.to_string())),
+        _ => break,
+      };
+      let field = match field {
+        Ok(field) => field,
+        Err((span, token)) => {
+          self.error(InterpreterError::ParseError {
+            span,
+            message: format!("Expected field name but got `{token}`"),
+          });
+          break;
+        }
+      };
+      tokens.next();
+
+      fields.push(match self.match_symbols(tokens, Symbol::Equals.into()) {
+        Some(_) => {
+          let value = self.expression(tokens);
+          FieldInitializer {
+            span: field.span.to(value.span()),
+            name: field,
+            value,
+          }
+        }
+        None => FieldInitializer {
+          span: field.span,
+          value: Expression::Variable { name: field.clone() },
+          name: field,
+        },
+      });
+    }
+
+    self.no_records = outer;
+    let close = self.pair_delimiter(tokens, Symbol::RightCurlyBracket);
+    Expression::Record {
+      span: name.as_ref().map_or(open, |name| name.span).to(close.span()),
+      name,
+      arguments,
+      fields,
+    }
+  }
+
+  /// Parses `= { name, ... }` after the token at `span`, giving the names
+  /// and the span of the closing `}`. `kind` says what the names are, for
+  /// errors.
+  fn braced_names(&mut self, tokens: &mut TokenProvider, span: Span, kind: &str) -> (Vec<LiteralToken>, Span) {
+    if self.match_symbols(tokens, Symbol::Equals.into()).is_none() {
+      self.error(InterpreterError::ParseError {
+        span: span.after(),
+        message: "Expected `=`".into(),
+      });
+    }
+    let Some(open) = self.match_symbols(tokens, Symbol::LeftCurlyBracket.into()) else {
+      let span = tokens.previous_valid().span().after();
+      self.error(InterpreterError::ParseError {
+        span,
+        message: "Expected `{`".into(),
+      });
+      self.synchronize(tokens);
+      return (Vec::new(), span);
+    };
+    self.delimiter_stack.push(Delimiter {
+      delimiter: DelimiterType::Curly,
+      span: open.span,
+    });
+
+    let mut names = Vec::new();
+    while !Self::peek_symbol(tokens, 0, Symbol::RightCurlyBracket) {
+      let name = match tokens.peek() {
+        Next::Token(token) => token.as_identifier().ok_or_else(|| (token.span(), token.to_string())),
+        _ => break,
+      };
+      match name {
+        Ok(name) => names.push(name),
+        Err((span, token)) => {
+          self.error(InterpreterError::ParseError {
+            span,
+            message: format!("Expected {kind} name but got `{token}`"),
+          });
+          break;
+        }
+      }
+      tokens.next();
+
+      if self.match_symbols(tokens, Symbol::Comma.into()).is_none() {
+        break;
+      }
+    }
+
+    let close = self.pair_delimiter(tokens, Symbol::RightCurlyBracket);
+    (names, close.span())
+  }
+
+  fn peek_symbol(tokens: &mut TokenProvider, n: usize, symbol: Symbol) -> bool {
+    matches!(tokens.peek_nth(n), Next::Token(Token::Symbol(token)) if token.symbol == symbol)
+  }
+
+  /// Parses the rest of `name: (parameters) -> Type = { ... }`, which may be
+  /// `export`ed. The `-> Type` may be left out for functions returning `()`,
+  /// and so may the `=`. A `;` in place of the body leaves it out, as the
+  /// required methods of a trait do.
+  fn function(&mut self, tokens: &mut TokenProvider, name: LiteralToken, generics: Vec<GenericParameter>) -> Function {
+    self.match_symbols(tokens, Symbol::Colon.into());
+    let exported =
+      self.match_keyword(tokens, Keyword::Export).is_some() || self.match_keyword(tokens, Keyword::Public).is_some();
+    let (receiver, parameters) = self.parameters(tokens);
+    let return_type = self
+      .match_symbols(tokens, Symbol::RightArrow.into())
+      .map(|_| self.type_expression(tokens));
+    if let Some(semicolon) = self.match_symbols(tokens, Symbol::Semicolon.into()) {
+      return Function {
+        docs: Vec::new(),
+        span: name.span.to(semicolon.span),
+        name,
+        generics,
+        exported,
+        receiver,
+        parameters,
+        return_type,
+        body: None,
+      };
+    }
+    self.match_symbols(tokens, Symbol::Equals.into());
+
+    let body = match self.match_symbols(tokens, Symbol::LeftCurlyBracket.into()) {
+      Some(open) => self.block(tokens, open.span),
+      None => {
+        let prev = tokens.previous_valid().span();
+        self.error(InterpreterError::ParseError {
+          span: prev.after(),
+          message: "Expected `{` before function body".into(),
+        });
+        self.synchronize(tokens);
+        Expression::Block {
+          statements: Vec::new(),
+          span: prev.after(),
+        }
+      }
+    };
+
+    Function {
+      docs: Vec::new(),
+      span: name.span.to(body.span()),
+      name,
+      generics,
+      exported,
+      receiver,
+      parameters,
+      return_type,
+      body: Some(body),
+    }
+  }
+
+  /// Whether a declaration of a name with type parameters, such as
+  /// `show<T: Speak>: (...)`, is coming up. As `<` is also a comparison, this
+  /// looks past the closing `>` for the `:` that only a declaration has.
+  fn at_generic_declaration(&mut self, tokens: &mut TokenProvider) -> bool {
+    let is_name = |tokens: &mut TokenProvider, n| matches!(tokens.peek_nth(n), Next::Token(token) if token.as_identifier().is_some());
+    if !is_name(tokens, 0) || !Self::peek_symbol(tokens, 1, Symbol::LeftAngledBracket) {
+      return false;
+    }
+
+    let mut n = 2;
+    loop {
+      if !is_name(tokens, n) {
+        return false;
+      }
+      n += 1;
+      if Self::peek_symbol(tokens, n, Symbol::Colon) {
+        if Self::peek_keyword(tokens, n + 1, Keyword::Const) {
+          n += 1;
+        }
+        // the bounds, as paths joined by `+`, or the type of a const
+        loop {
+          n += 1;
+          if !is_name(tokens, n) {
+            return false;
+          }
+          n += 1;
+          while Self::peek_symbol(tokens, n, Symbol::Period) && is_name(tokens, n + 1) {
+            n += 2;
+          }
+          if !Self::peek_symbol(tokens, n, Symbol::Plus) {
+            break;
+          }
+        }
+      }
+
+      if Self::peek_symbol(tokens, n, Symbol::RightAngledBracket) {
+        return Self::peek_symbol(tokens, n + 1, Symbol::Colon);
+      }
+      if !Self::peek_symbol(tokens, n, Symbol::Comma) {
+        return false;
+      }
+      n += 1;
+    }
+  }
+
+  /// Parses `<T, U: Trait + Other>` if a `<` comes next.
+  fn generic_parameters(&mut self, tokens: &mut TokenProvider) -> Vec<GenericParameter> {
+    if self.match_symbols(tokens, Symbol::LeftAngledBracket.into()).is_none() {
+      return Vec::new();
+    }
+
+    let mut parameters = Vec::new();
+    while let Some(name) = self.member_name(tokens, "type parameter") {
+      let mut span = name.span;
+      let mut bounds = Vec::new();
+      let mut const_type = None;
+      if self.match_symbols(tokens, Symbol::Colon.into()).is_some() {
+        match self.match_keyword(tokens, Keyword::Const) {
+          Some(_) => {
+            let data_type = self.type_expression(tokens);
+            span = span.to(data_type.span());
+            const_type = Some(data_type);
+          }
+          None => loop {
+            let bound = self.type_expression(tokens);
+            span = span.to(bound.span());
+            bounds.push(bound);
+            if self.match_symbols(tokens, Symbol::Plus.into()).is_none() {
+              break;
+            }
+          },
+        }
+      }
+      parameters.push(GenericParameter {
+        name,
+        bounds,
+        const_type,
+        span,
+      });
+
+      if self.match_symbols(tokens, Symbol::Comma.into()).is_none()
+        || Self::peek_symbol(tokens, 0, Symbol::RightAngledBracket)
+      {
+        break;
+      }
+    }
+
+    if self.match_symbols(tokens, Symbol::RightAngledBracket.into()).is_none() {
+      self.error(InterpreterError::ParseError {
+        span: tokens.previous_valid().span().after(),
+        message: "Expected `>` after type parameters".into(),
+      });
+    }
+    parameters
+  }
+
+  /// Parses `()` or `(name: Type, ...)`, where a method's `self` comes first.
+  fn parameters(&mut self, tokens: &mut TokenProvider) -> (Option<Receiver>, Vec<Parameter>) {
+    if let Next::Token(Token::Literal(LiteralToken {
+      literal: Literal::Void, ..
+    })) = tokens.peek()
+    {
+      tokens.next();
+      return (None, Vec::new());
+    }
+
+    let Some(open) = self.match_symbols(tokens, Symbol::LeftParenthesis.into()) else {
+      return (None, Vec::new());
+    };
+    self.delimiter_stack.push(Delimiter {
+      delimiter: DelimiterType::Paren,
+      span: open.span,
+    });
+
+    let receiver = self.receiver(tokens);
+    let mut parameters = Vec::new();
+    let more = receiver.is_none() || self.match_symbols(tokens, Symbol::Comma.into()).is_some();
+    while more && !Self::peek_symbol(tokens, 0, Symbol::RightParenthesis) {
+      let name = match tokens.peek() {
+        Next::Token(token) => token.as_identifier().ok_or_else(|| (token.span(), token.to_string())),
+        _ => break,
+      };
+      let name = match name {
+        Ok(name) => name,
+        Err((span, token)) => {
+          self.error(InterpreterError::ParseError {
+            span,
+            message: format!("Expected parameter name but got `{token}`"),
+          });
+          break;
+        }
+      };
+      tokens.next();
+
+      if self.match_symbols(tokens, Symbol::Colon.into()).is_none() {
+        self.error(InterpreterError::ParseError {
+          span: name.span.after(),
+          message: "Expected `:` after parameter name".into(),
+        });
+        break;
+      }
+      let data_type = self.type_expression(tokens);
+      parameters.push(Parameter {
+        span: name.span.to(data_type.span()),
+        name,
+        data_type,
+      });
+
+      if self.match_symbols(tokens, Symbol::Comma.into()).is_none() {
+        break;
+      }
+    }
+
+    self.pair_delimiter(tokens, Symbol::RightParenthesis);
+    (receiver, parameters)
+  }
+
+  /// Parses `self`, `&self`, `&mut self`, or `self` followed by its type, if
+  /// a parameter list starts with one.
+  fn receiver(&mut self, tokens: &mut TokenProvider) -> Option<Receiver> {
+    let start = match tokens.peek() {
+      Next::Token(token) => token.span(),
+      _ => return None,
+    };
+    let n = usize::from(Self::peek_symbol(tokens, 0, Symbol::Ampersand));
+    let n = n + usize::from(n == 1 && Self::peek_keyword(tokens, n, Keyword::Mut));
+    if !Self::peek_keyword(tokens, n, Keyword::_Self) {
+      return None;
+    }
+
+    let kind = self.reference_kind(tokens);
+    let keyword = self.match_keyword(tokens, Keyword::_Self)?;
+    let mut span = start.to(keyword.span);
+    if kind != ReceiverKind::Value || self.match_symbols(tokens, Symbol::Colon.into()).is_none() {
+      return Some(Receiver {
+        kind,
+        data_type: None,
+        span,
+      });
+    }
+
+    let kind = self.reference_kind(tokens);
+    let data_type = self.type_expression(tokens);
+    span = span.to(data_type.span());
+    Some(Receiver {
+      kind,
+      data_type: Some(data_type),
+      span,
+    })
+  }
+
+  /// Reads the `&` or `&mut` in front of a `self` or its type, if any.
+  fn reference_kind(&mut self, tokens: &mut TokenProvider) -> ReceiverKind {
+    if self.match_symbols(tokens, Symbol::Ampersand.into()).is_none() {
+      return ReceiverKind::Value;
+    }
+    match self.match_keyword(tokens, Keyword::Mut) {
+      Some(_) => ReceiverKind::MutableReference,
+      None => ReceiverKind::Reference,
+    }
+  }
+
+  /// Parses a type: `()`, `void`, or a name reached through modules such as
+  /// `core.string`.
+  fn type_expression(&mut self, tokens: &mut TokenProvider) -> TypeExpression {
+    let token = match tokens.next() {
+      Next::Token(token) => token,
+      Next::EndOfFile { span, .. } | Next::EndOfStream { span, .. } => {
+        let prev = tokens.previous_valid();
+        self.error(InterpreterError::ParseError {
+          span: prev.span().after(),
+          message: format!("Expected type after `{}`", prev),
+        });
+        return TypeExpression::Void { span };
+      }
+    };
+
+    match &token {
+      Token::Symbol(SymbolToken {
+        symbol: Symbol::Ampersand,
+        span,
+        ..
+      }) => {
+        let inner = Box::new(self.type_expression(tokens));
+        return TypeExpression::Reference {
+          span: span.to(inner.span()),
+          inner,
+        };
+      }
+      Token::Literal(LiteralToken {
+        literal: Literal::Void,
+        span,
+        ..
+      })
+      | Token::Keyword(KeywordToken {
+        keyword: Keyword::Void,
+        span,
+        ..
+      }) => return TypeExpression::Void { span: *span },
+      _ => {}
+    }
+
+    let first = match &token {
+      Token::Keyword(keyword) if keyword.keyword == Keyword::_SelfType => Some(keyword.as_name()),
+      token => token.as_identifier(),
+    };
+    let Some(first) = first else {
+      self.error(InterpreterError::ParseError {
+        span: token.span(),
+        message: format!("Expected type but got `{token}`"),
+      });
+      return TypeExpression::Void { span: token.span() };
+    };
+
+    let mut span = first.span;
+    let mut segments = vec![first];
+    while let Some(period) = self.match_symbols(tokens, Symbol::Period.into()) {
+      let segment = match tokens.peek() {
+        Next::Token(token) => token.as_identifier(),
+        _ => None,
+      };
+      let Some(segment) = segment else {
+        self.error(InterpreterError::ParseError {
+          span: period.span.after(),
+          message: "Expected name after `.`".into(),
         });
-        SyntaxTree {
-          root,
-          eof: Token::EndOfFile {
-            position: Position::new(t.position().line, t.position().column + 1),
+        break;
+      };
+
+      tokens.next();
+      span = span.to(segment.span);
+      segments.push(segment);
+    }
+
+    let mut arguments = Vec::new();
+    if Self::peek_symbol(tokens, 0, Symbol::LeftAngledBracket) {
+      let close;
+      (arguments, close) = self.generic_arguments(tokens);
+      span = span.to(close);
+    }
+    TypeExpression::Path {
+      segments,
+      arguments,
+      span,
+    }
+  }
+
+  /// Parses `<i32, 5>`, the arguments given to a generic type or function,
+  /// giving them along with the span of the `>`.
+  fn generic_arguments(&mut self, tokens: &mut TokenProvider) -> (Vec<GenericArgument>, Span) {
+    let open = self.match_symbols(tokens, Symbol::LeftAngledBracket.into());
+    let mut arguments = Vec::new();
+    while !Self::peek_symbol(tokens, 0, Symbol::RightAngledBracket) {
+      let argument = match tokens.peek() {
+        Next::Token(Token::Literal(
+          token @ LiteralToken {
+            literal: Literal::Number { .. },
+            ..
           },
+        )) => {
+          let token = token.clone();
+          tokens.next();
+          GenericArgument::Const(token)
         }
+        _ => GenericArgument::Type(self.type_expression(tokens)),
+      };
+      arguments.push(argument);
+      if self.match_symbols(tokens, Symbol::Comma.into()).is_none() {
+        break;
       }
-      Next::EndOfFile { position } => SyntaxTree {
-        root,
-        eof: Token::EndOfFile { position },
-      },
-      Next::EndOfStream { position } => SyntaxTree {
-        root,
-        eof: Token::EndOfFile { position },
-      },
+    }
+
+    match self.match_symbols(tokens, Symbol::RightAngledBracket.into()) {
+      Some(close) => (arguments, close.span),
+      None => {
+        let span = tokens.previous_valid().span().after();
+        self.error(InterpreterError::ParseError {
+          span,
+          message: "Expected `>` after generic arguments".into(),
+        });
+        (arguments, open.map_or(span, |open| open.span.to(span)))
+      }
+    }
+  }
+
+  /// Where the generic arguments starting with a `<` `n` tokens ahead end,
+  /// as the position after their `>`, if the tokens from there make them up.
+  /// As `<` is also a comparison, this is how an expression tells the two
+  /// apart, along with what comes before the `<` and after the `>`.
+  fn generic_arguments_end(tokens: &mut TokenProvider, mut n: usize) -> Option<usize> {
+    if !Self::peek_symbol(tokens, n, Symbol::LeftAngledBracket) {
+      return None;
+    }
+
+    n += 1;
+    loop {
+      n = Self::generic_argument_end(tokens, n)?;
+      if Self::peek_symbol(tokens, n, Symbol::RightAngledBracket) {
+        return Some(n + 1);
+      }
+      if !Self::peek_symbol(tokens, n, Symbol::Comma) {
+        return None;
+      }
+      n += 1;
+    }
+  }
+
+  /// Where a single generic argument `n` tokens ahead ends: an integer, or a
+  /// type.
+  fn generic_argument_end(tokens: &mut TokenProvider, mut n: usize) -> Option<usize> {
+    if let Next::Token(
+      Token::Literal(LiteralToken {
+        literal: Literal::Number { .. } | Literal::Void,
+        ..
+      })
+      | Token::Keyword(KeywordToken {
+        keyword: Keyword::Void, ..
+      }),
+    ) = tokens.peek_nth(n)
+    {
+      return Some(n + 1);
+    }
+
+    while Self::peek_symbol(tokens, n, Symbol::Ampersand) {
+      n += 1;
+    }
+    let is_name = |tokens: &mut TokenProvider, n| match tokens.peek_nth(n) {
+      Next::Token(Token::Keyword(keyword)) => keyword.keyword == Keyword::_SelfType,
+      Next::Token(token) => token.as_identifier().is_some(),
+      _ => false,
+    };
+    if !is_name(tokens, n) {
+      return None;
+    }
+    n += 1;
+    while Self::peek_symbol(tokens, n, Symbol::Period) && is_name(tokens, n + 1) {
+      n += 2;
+    }
+
+    match Self::peek_symbol(tokens, n, Symbol::LeftAngledBracket) {
+      true => Self::generic_arguments_end(tokens, n),
+      false => Some(n),
+    }
+  }
+
+  fn skip_empty_statements(&mut self, tokens: &mut TokenProvider) {
+    while self.match_symbols(tokens, Symbol::Semicolon.into()).is_some() {}
+  }
+
+  fn at_end_of_block(&mut self, tokens: &mut TokenProvider) -> bool {
+    matches!(
+      tokens.peek(),
+      Next::Token(Token::Symbol(SymbolToken {
+        symbol: Symbol::RightCurlyBracket,
+        ..
+      }))
+        | Next::EndOfFile { .. }
+        | Next::EndOfStream { .. }
+    )
+  }
+
+  /// Parses the statements of a block whose `{` was just read.
+  /// A block that starts with `name,` or `name = value,` is a record literal
+  /// instead, whose type comes from where it goes. `{ name = value }` is left
+  /// a block, which the binder takes as a record wherever one is expected.
+  fn block(&mut self, tokens: &mut TokenProvider, open: Span) -> Expression {
+    self.delimiter_stack.push(Delimiter {
+      delimiter: DelimiterType::Curly,
+      span: open,
+    });
+
+    let mut statements = Vec::new();
+    let is_name = matches!(tokens.peek(), Next::Token(token) if token.as_identifier().is_some());
+    if is_name && Self::peek_symbol(tokens, 1, Symbol::Comma) {
+      return self.record_literal(tokens, None, Vec::new(), open, None);
+    }
+    if is_name && Self::peek_symbol(tokens, 1, Symbol::Equals) {
+      let errors = self.error_handler.get().errors().len();
+      let Next::Token(token) = tokens.next() else {
+        unreachable!("the name was just peeked")
+      };
+      let name = token.as_identifier().expect("the name was just peeked");
+      tokens.next();
+      let outer = std::mem::replace(&mut self.no_records, false);
+      let value = self.expression(tokens);
+      self.no_records = outer;
+
+      if Self::peek_symbol(tokens, 0, Symbol::Comma) {
+        let first = FieldInitializer {
+          span: name.span.to(value.span()),
+          name,
+          value,
+        };
+        return self.record_literal(tokens, None, Vec::new(), open, Some(first));
+      }
+
+      let assignment = Expression::Assign {
+        span: name.span.to(value.span()),
+        target: Box::new(Expression::Variable { name }),
+        operator: None,
+        value: Box::new(value),
+      };
+      statements.push(self.finish_statement(tokens, assignment, errors));
+    }
+
+    let outer = std::mem::replace(&mut self.no_records, false);
+    loop {
+      self.skip_empty_statements(tokens);
+      // doc comments only attach to declarations
+      let docs = Self::outer_docs(tokens);
+      if self.at_end_of_block(tokens) {
+        break;
+      }
+      let mut statement = self.statement(tokens);
+      if let Statement::Declaration { docs: declared, .. } = &mut statement {
+        *declared = docs;
+      }
+      statements.push(statement);
+    }
+    self.no_records = outer;
+
+    let close = self.pair_delimiter(tokens, Symbol::RightCurlyBracket);
+    Expression::Block {
+      statements,
+      span: open.to(close.span()),
     }
   }
 
@@ -141,13 +1521,13 @@ impl Parser {
     let mut peeked = tokens.peek();
     // check for rogue delimiters
     if self.is_rogue_delimiter(&peeked) {
-      let Next::Token(Token::Symbol(SymbolToken { position, symbol })) = &peeked else {
+      let Next::Token(Token::Symbol(SymbolToken { span, symbol, .. })) = &peeked else {
         return None;
       };
 
       self.error(InterpreterError::UnmatchedDelimiter {
-        position: position.clone(),
-        delimiter: symbol.lexeme(),
+        span: *span,
+        delimiter: symbol.lexeme().into(),
       });
 
       tokens.next(); // consume the delimiter
@@ -172,7 +1552,7 @@ impl Parser {
     if let Some(Token::Symbol(symbol_token)) = self.match_token_types(tokens, TokenDiscriminants::Symbol.into()) {
       if types.contains(symbol_token.symbol) {
         if let Next::Token(Token::Symbol(symbol_token)) = tokens.next() {
-          return Some(symbol_token.clone());
+          return Some(symbol_token);
         }
       }
     }
@@ -181,7 +1561,88 @@ impl Parser {
   }
 
   fn expression(&mut self, tokens: &mut TokenProvider) -> Expression {
-    self.equality(tokens)
+    self.assignment(tokens)
+  }
+
+  /// Parses `target = value` and compound assignments such as
+  /// `target += value`, the loosest of all operators, which group to the
+  /// right as in `a = (b += c)`.
+  fn assignment(&mut self, tokens: &mut TokenProvider) -> Expression {
+    let target = self.range(tokens);
+    let assignments = Symbol::Equals
+      | Symbol::PlusEquals
+      | Symbol::MinusEquals
+      | Symbol::AsteriskEquals
+      | Symbol::ForwardSlashEquals
+      | Symbol::CaretEquals
+      | Symbol::AmpersandEquals;
+    let Some(operator) = self.match_symbols(tokens, assignments) else {
+      return target;
+    };
+
+    let value = Box::new(self.assignment(tokens));
+    Expression::Assign {
+      span: target.span().to(value.span()),
+      target: Box::new(target),
+      operator: (operator.symbol != Symbol::Equals).then_some(operator),
+      value,
+    }
+  }
+
+  /// Parses an expression followed by a block, as in `if condition { ... }`,
+  /// where a `{` after a name can't start a record literal.
+  fn condition(&mut self, tokens: &mut TokenProvider) -> Expression {
+    let outer = std::mem::replace(&mut self.no_records, true);
+    let condition = self.expression(tokens);
+    self.no_records = outer;
+    condition
+  }
+
+  fn range(&mut self, tokens: &mut TokenProvider) -> Expression {
+    let start = self.or(tokens);
+    let Some(operator) = self.match_symbols(tokens, Symbol::DoublePeriod | Symbol::DoublePeriodEquals) else {
+      return start;
+    };
+
+    let end = Box::new(self.or(tokens));
+    Expression::Range {
+      span: start.span().to(end.span()),
+      start: Box::new(start),
+      end,
+      inclusive: operator.symbol == Symbol::DoublePeriodEquals,
+    }
+  }
+
+  fn or(&mut self, tokens: &mut TokenProvider) -> Expression {
+    let mut expression = self.and(tokens);
+
+    while let Some(operator) = self.match_symbols(tokens, Symbol::DoublePipe.into()) {
+      let right_operand = Box::new(self.and(tokens));
+      expression = Expression::Binary {
+        span: expression.span().to(right_operand.span()),
+        left_operand: Box::new(expression),
+        operator,
+        right_operand,
+      };
+    }
+
+    expression
+  }
+
+  fn and(&mut self, tokens: &mut TokenProvider) -> Expression {
+    let mut expression = self.equality(tokens);
+
+    while let Some(operator) = self.match_symbols(tokens, Symbol::DoubleAmpersand.into()) {
+      let right_operand = Box::new(self.equality(tokens));
+      expression = Expression::Binary {
+        span: expression.span().to(right_operand.span()),
+        left_operand: Box::new(expression),
+        operator,
+        right_operand,
+      };
+    }
+
+    expression
   }
 
   fn equality(&mut self, tokens: &mut TokenProvider) -> Expression {
@@ -190,6 +1651,7 @@ impl Parser {
     while let Some(operator) = self.match_symbols(tokens, Symbol::DoubleEquals | Symbol::ExclamationPointEquals) {
       let right_operand = Box::new(self.comparison(tokens));
       expression = Expression::Binary {
+        span: expression.span().to(right_operand.span()),
         left_operand: Box::new(expression),
         operator,
         right_operand,
@@ -211,6 +1673,7 @@ impl Parser {
     ) {
       let right_operand = Box::new(self.term(tokens));
       expression = Expression::Binary {
+        span: expression.span().to(right_operand.span()),
         left_operand: Box::new(expression),
         operator,
         right_operand,
@@ -226,6 +1689,7 @@ impl Parser {
     while let Some(operator) = self.match_symbols(tokens, Symbol::Plus | Symbol::Minus) {
       let right_operand = Box::new(self.factor(tokens));
       expression = Expression::Binary {
+        span: expression.span().to(right_operand.span()),
         left_operand: Box::new(expression),
         operator,
         right_operand,
@@ -241,6 +1705,7 @@ impl Parser {
     while let Some(operator) = self.match_symbols(tokens, Symbol::Asterisk | Symbol::ForwardSlash) {
       let right_operand = Box::new(self.unary(tokens));
       expression = Expression::Binary {
+        span: expression.span().to(right_operand.span()),
         left_operand: Box::new(expression),
         operator,
         right_operand,
@@ -250,97 +1715,345 @@ impl Parser {
     expression
   }
 
-  // fn power(&mut self, tokens: &mut TokenProvider) -> Expression {
-  //   let mut expression = self.unary(tokens);
-
-  //   while let Some(operator) = self.match_symbols(tokens, Symbol::Caret.into())
-  // {     let right_operand = Box::new(self.unary(tokens));
-  //     expression = Expression::Binary {
-  //       left_operand: Box::new(expression),
-  //       operator,
-  //       right_operand,
-  //     };
-  //   }
+  /// Parses `base ^ exponent`, which groups to the right as in
+  /// `2 ^ (3 ^ 2)`. It binds tighter than a prefix operator on its base, so
+  /// `-2 ^ 2` is `-(2 ^ 2)`, but the exponent may have one, as in `2 ^ -1`.
+  fn power(&mut self, tokens: &mut TokenProvider) -> Expression {
+    let base = self.call(tokens);
+    let Some(operator) = self.match_symbols(tokens, Symbol::Caret.into()) else {
+      return base;
+    };
 
-  //   expression
-  // }
+    let exponent = Box::new(self.unary(tokens));
+    Expression::Binary {
+      span: base.span().to(exponent.span()),
+      left_operand: Box::new(base),
+      operator,
+      right_operand: exponent,
+    }
+  }
 
   fn unary(&mut self, tokens: &mut TokenProvider) -> Expression {
-    if let Some(operator) = self.match_symbols(tokens, Symbol::ExclamationPoint | Symbol::Minus) {
+    if let Some(operator) = self.match_symbols(tokens, Symbol::ExclamationPoint | Symbol::Minus | Symbol::Ampersand) {
       let operand = Box::new(self.unary(tokens));
-      return Expression::Unary { operator, operand };
+      return Expression::Unary {
+        span: operator.span.to(operand.span()),
+        operator,
+        operand,
+      };
+    }
+
+    self.power(tokens)
+  }
+
+  fn call(&mut self, tokens: &mut TokenProvider) -> Expression {
+    let mut expression = self.primary(tokens);
+
+    // a block standing as a statement is never called by a `(` after it
+    while !expression.is_block_like() {
+      let arguments = match expression {
+        Expression::Variable { .. } | Expression::Member { .. } => {
+          self.generic_arguments_ahead(tokens, expression.span())
+        }
+        _ => None,
+      };
+      if let Some((start, _)) = arguments {
+        if start == 1 {
+          tokens.next(); // the `::`
+        }
+        let (arguments, close) = self.generic_arguments(tokens);
+        expression = Expression::Generic {
+          span: expression.span().to(close),
+          target: Box::new(expression),
+          arguments,
+        };
+        continue;
+      }
+
+      let open = match tokens.peek() {
+        Next::Token(Token::Symbol(SymbolToken {
+          symbol: Symbol::Period,
+          span,
+          ..
+        })) => {
+          let period = *span;
+          tokens.next();
+          let member = match tokens.peek() {
+            Next::Token(token) => token.as_identifier(),
+            _ => None,
+          };
+          let Some(member) = member else {
+            self.error(InterpreterError::ParseError {
+              span: period.after(),
+              message: "Expected name after `.`".into(),
+            });
+            break;
+          };
+          tokens.next();
+          expression = Expression::Member {
+            span: expression.span().to(member.span),
+            object: Box::new(expression),
+            member,
+          };
+          continue;
+        }
+        Next::Token(
+          token @ Token::Literal(LiteralToken {
+            literal: Literal::Void, ..
+          }),
+        ) => {
+          let span = token.span();
+          tokens.next();
+          expression = Expression::Call {
+            span: expression.span().to(span),
+            callee: Box::new(expression),
+            arguments: Vec::new(),
+          };
+          continue;
+        }
+        Next::Token(Token::Symbol(SymbolToken {
+          symbol: Symbol::LeftSquareBracket,
+          span,
+          ..
+        })) => {
+          let open = *span;
+          tokens.next();
+          self.delimiter_stack.push(Delimiter {
+            delimiter: DelimiterType::Square,
+            span: open,
+          });
+          let outer = std::mem::replace(&mut self.no_records, false);
+          let index = Box::new(self.expression(tokens));
+          self.no_records = outer;
+          let close = self.pair_delimiter(tokens, Symbol::RightSquareBracket);
+          expression = Expression::Index {
+            span: expression.span().to(close.span()),
+            object: Box::new(expression),
+            index,
+          };
+          continue;
+        }
+        Next::Token(Token::Symbol(SymbolToken {
+          symbol: Symbol::LeftParenthesis,
+          span,
+          ..
+        })) => *span,
+        _ => break,
+      };
+
+      tokens.next();
+      self.delimiter_stack.push(Delimiter {
+        delimiter: DelimiterType::Paren,
+        span: open,
+      });
+      let outer = std::mem::replace(&mut self.no_records, false);
+      let mut arguments = Vec::new();
+      while !Self::peek_symbol(tokens, 0, Symbol::RightParenthesis) {
+        arguments.push(self.expression(tokens));
+        if self.match_symbols(tokens, Symbol::Comma.into()).is_none() {
+          break;
+        }
+      }
+      self.no_records = outer;
+      let close = self.pair_delimiter(tokens, Symbol::RightParenthesis);
+
+      expression = Expression::Call {
+        span: expression.span().to(close.span()),
+        callee: Box::new(expression),
+        arguments,
+      };
     }
 
-    self.primary(tokens)
+    expression
+  }
+
+  /// Where the generic arguments coming up after `target` in an expression
+  /// start and end, as the positions of their `<` and after their `>`. They
+  /// are either written after `::`, as in `f::<i32>(x)`, or with their `<`
+  /// right against the name, as in `array<i32>.new()`, and then have to be
+  /// followed by a call, a `.` or the fields of a record literal. A `<` with
+  /// space before it always compares, so `f(a < b, c > (d))` still does.
+  fn generic_arguments_ahead(&mut self, tokens: &mut TokenProvider, target: Span) -> Option<(usize, usize)> {
+    if Self::peek_symbol(tokens, 0, Symbol::DoubleColon) {
+      return Self::generic_arguments_end(tokens, 1).map(|end| (1, end));
+    }
+
+    let Next::Token(open) = tokens.peek() else {
+      return None;
+    };
+    if open.span().start != target.end {
+      return None;
+    }
+    let end = Self::generic_arguments_end(tokens, 0)?;
+    let followed = Self::peek_symbol(tokens, end, Symbol::LeftParenthesis)
+      || Self::peek_symbol(tokens, end, Symbol::Period)
+      || matches!(
+        tokens.peek_nth(end),
+        Next::Token(Token::Literal(LiteralToken {
+          literal: Literal::Void,
+          ..
+        }))
+      )
+      || self.at_record_literal(tokens, end);
+    followed.then_some((0, end))
   }
 
   fn primary(&mut self, tokens: &mut TokenProvider) -> Expression {
-    let next_token = tokens.next().cloned();
+    let next_token = tokens.next();
     match next_token {
       Next::Token(token) => {
         match &token {
-          Token::Literal(token) => return Expression::Literal { token: token.clone() },
-          Token::Symbol(SymbolToken { position, symbol }) => match symbol {
-            Symbol::LeftParenthesis => {
+          Token::Literal(LiteralToken {
+            literal: Literal::InterpolatedString { parts },
+            span,
+            ..
+          }) => return self.interpolation(parts.clone(), *span),
+          Token::Literal(LiteralToken {
+            literal: Literal::Identifier { .. },
+            ..
+          })
+          | Token::Keyword(KeywordToken {
+            keyword: Keyword::_Self | Keyword::_SelfType | Keyword::In | Keyword::Void,
+            ..
+          }) => {
+            let name = match &token {
+              Token::Keyword(keyword) => keyword.as_name(),
+              token => token.as_identifier().expect("the token is an identifier"),
+            };
+            let arguments = self
+              .generic_arguments_ahead(tokens, name.span)
+              .filter(|(_, end)| self.at_record_literal(tokens, *end));
+            let arguments = arguments.map(|(start, _)| {
+              if start == 1 {
+                tokens.next(); // the `::`
+              }
+              self.generic_arguments(tokens).0
+            });
+            if arguments.is_some() || self.at_record_literal(tokens, 0) {
+              let Next::Token(open) = tokens.next() else {
+                unreachable!("the `{{` was just peeked")
+              };
               self.delimiter_stack.push(Delimiter {
-                delimiter: DelimiterType::Paren,
-                position: position.clone(),
+                delimiter: DelimiterType::Curly,
+                span: open.span(),
               });
-              let operand = Box::new(self.expression(tokens));
-              let _delimiter = self.pair_delimiter(tokens, Symbol::RightParenthesis);
-              return Expression::Grouping { operand };
+              return self.record_literal(tokens, Some(name), arguments.unwrap_or_default(), open.span(), None);
             }
-            Symbol::LeftCurlyBracket => {
-              self.delimiter_stack.push(Delimiter {
-                delimiter: DelimiterType::Curly,
+            return Expression::Variable { name };
+          }
+          Token::Literal(token) => return Expression::Literal { token: token.clone() },
+          Token::Keyword(KeywordToken {
+            keyword: keyword @ (Keyword::True | Keyword::False),
+            position,
+            span,
+            ..
+          }) => {
+            return Expression::Literal {
+              token: LiteralToken {
                 position: position.clone(),
+                span: *span,
+                literal: Literal::Bool {
+                  lexeme: *keyword == Keyword::True,
+                },
+              },
+            }
+          }
+          Token::Keyword(KeywordToken { keyword, span, .. }) => match keyword {
+            Keyword::Return => {
+              let value = self.optional_value(tokens);
+              return Expression::Return {
+                span: value.as_ref().map_or(*span, |value| span.to(value.span())),
+                value,
+              };
+            }
+            Keyword::Break => {
+              let value = self.optional_value(tokens);
+              return Expression::Break {
+                span: value.as_ref().map_or(*span, |value| span.to(value.span())),
+                value,
+              };
+            }
+            Keyword::Continue => return Expression::Continue { span: *span },
+            Keyword::If => return self.if_expression(tokens, *span),
+            Keyword::While => {
+              let condition = Box::new(self.condition(tokens));
+              let body = Box::new(self.expect_block(tokens, "condition"));
+              return Expression::While {
+                span: span.to(body.span()),
+                condition,
+                body,
+              };
+            }
+            Keyword::Loop => {
+              let body = Box::new(self.expect_block(tokens, "`loop`"));
+              return Expression::Loop {
+                span: span.to(body.span()),
+                body,
+              };
+            }
+            Keyword::For => return self.for_expression(tokens, *span),
+            _ => {}
+          },
+          Token::Symbol(SymbolToken { span, symbol, .. }) => match symbol {
+            Symbol::LeftParenthesis => {
+              self.delimiter_stack.push(Delimiter {
+                delimiter: DelimiterType::Paren,
+                span: *span,
               });
+              let outer = std::mem::replace(&mut self.no_records, false);
               let operand = Box::new(self.expression(tokens));
-              let _delimiter = self.pair_delimiter(tokens, Symbol::RightCurlyBracket);
-              self.delimiter_stack.pop();
-              return Expression::Grouping { operand };
+              self.no_records = outer;
+              let delimiter = self.pair_delimiter(tokens, Symbol::RightParenthesis);
+              return Expression::Grouping {
+                operand,
+                span: span.to(delimiter.span()),
+              };
             }
+            Symbol::LeftCurlyBracket => return self.block(tokens, *span),
             _ => {}
           },
           _ => {}
         }
 
         self.error(InterpreterError::ParseError {
-          position: token.position().clone(),
+          span: token.span(),
           message: format!("Expected expression but got `{}`", tokens.previous_valid()),
         });
 
         Expression::Literal {
           token: LiteralToken {
             position: token.position().clone(),
+            span: token.span(),
             literal: Literal::Void,
           },
         }
       }
-      Next::EndOfFile { position } => {
+      Next::EndOfFile { position, span } => {
         let prev = tokens.previous_valid();
         self.error(InterpreterError::ParseError {
-          position: Position::new(prev.position().line, prev.position().column + prev.lexeme().len() as u32),
+          span: prev.span().after(),
           message: format!("Expected expression after `{}`", prev),
         });
 
         Expression::Literal {
           token: LiteralToken {
             position,
+            span,
             literal: Literal::Void,
           },
         }
       }
-      Next::EndOfStream { position } => {
+      Next::EndOfStream { position, span } => {
         let prev = tokens.previous_valid();
         self.error(InterpreterError::ParseError {
-          position: Position::new(prev.position().line, prev.position().column + prev.lexeme().len() as u32),
+          span: prev.span().after(),
           message: format!("Expected expression after `{}`", prev),
         });
 
         Expression::Literal {
           token: LiteralToken {
             position,
+            span,
             literal: Literal::Void,
           },
         }
@@ -348,44 +2061,185 @@ impl Parser {
     }
   }
 
+  /// The value after a `return` or `break`, unless the statement ends there.
+  fn optional_value(&mut self, tokens: &mut TokenProvider) -> Option<Box<Expression>> {
+    if self.at_end_of_block(tokens) || Self::peek_symbol(tokens, 0, Symbol::Semicolon) {
+      None
+    } else {
+      Some(Box::new(self.expression(tokens)))
+    }
+  }
+
+  fn match_keyword(&mut self, tokens: &mut TokenProvider, keyword: Keyword) -> Option<KeywordToken> {
+    match tokens.peek() {
+      Next::Token(Token::Keyword(token)) if token.keyword == keyword => match tokens.next() {
+        Next::Token(Token::Keyword(token)) => Some(token),
+        _ => unreachable!("the keyword was just peeked"),
+      },
+      _ => None,
+    }
+  }
+
+  /// Parses the block that has to come next, such as the body of a loop.
+  /// `after` names what comes before it, for the error if it's missing.
+  fn expect_block(&mut self, tokens: &mut TokenProvider, after: &str) -> Expression {
+    if let Some(open) = self.match_symbols(tokens, Symbol::LeftCurlyBracket.into()) {
+      return self.block(tokens, open.span);
+    }
+
+    let span = tokens.previous_valid().span().after();
+    self.error(InterpreterError::ParseError {
+      span,
+      message: format!("Expected `{{` after {after}"),
+    });
+    Expression::Block {
+      statements: Vec::new(),
+      span,
+    }
+  }
+
+  /// Parses the rest of an `if`, whose keyword at `span` was just read, along
+  /// with any `else if`s and `else` after it.
+  fn if_expression(&mut self, tokens: &mut TokenProvider, span: Span) -> Expression {
+    let condition = Box::new(self.condition(tokens));
+    let then_branch = Box::new(self.expect_block(tokens, "condition"));
+    let else_branch = match self.match_keyword(tokens, Keyword::Else) {
+      Some(_) => match self.match_keyword(tokens, Keyword::If) {
+        Some(token) => Some(Box::new(self.if_expression(tokens, token.span))),
+        None => Some(Box::new(self.expect_block(tokens, "`else`"))),
+      },
+      None => None,
+    };
+
+    Expression::If {
+      span: span.to(else_branch.as_ref().map_or(then_branch.span(), |branch| branch.span())),
+      condition,
+      then_branch,
+      else_branch,
+    }
+  }
+
+  /// Parses the rest of `for variable in iterable { ... }`, whose `for` at
+  /// `span` was just read.
+  fn for_expression(&mut self, tokens: &mut TokenProvider, span: Span) -> Expression {
+    let variable = match tokens.peek() {
+      Next::Token(token) => token.as_identifier(),
+      _ => None,
+    };
+    let Some(variable) = variable else {
+      self.error(InterpreterError::ParseError {
+        span: span.after(),
+        message: "Expected loop variable after `for`".into(),
+      });
+      return Expression::Block {
+        statements: Vec::new(),
+        span,
+      };
+    };
+    tokens.next();
+
+    if self.match_keyword(tokens, Keyword::In).is_none() {
+      self.error(InterpreterError::ParseError {
+        span: variable.span.after(),
+        message: "Expected `in` after loop variable".into(),
+      });
+    }
+
+    let iterable = Box::new(self.condition(tokens));
+    let body = Box::new(self.expect_block(tokens, "iterable"));
+    Expression::For {
+      span: span.to(body.span()),
+      variable,
+      iterable,
+      body,
+    }
+  }
+
+  fn interpolation(&mut self, parts: Vec<StringPart>, span: Span) -> Expression {
+    let parts = parts
+      .into_iter()
+      .map(|part| match part {
+        StringPart::Text { text, .. } => InterpolationPart::Text(text),
+        StringPart::Expression { tokens, span } => InterpolationPart::Expression(self.embedded(tokens, span)),
+      })
+      .collect();
+
+    Expression::Interpolation { parts, span }
+  }
+
+  /// Parses the expression inside an interpolation's `{}`, which must be the
+  /// only thing in there.
+  fn embedded(&mut self, tokens: Vec<Token>, span: Span) -> Expression {
+    if let [Token::EndOfFile { position, .. }] = tokens.as_slice() {
+      self.error(InterpreterError::ParseError {
+        span,
+        message: "Expected expression inside `{}`".into(),
+      });
+      return Expression::Literal {
+        token: LiteralToken {
+          position: position.clone(),
+          span,
+          literal: Literal::Void,
+        },
+      };
+    }
+
+    let outer_delimiters = std::mem::take(&mut self.delimiter_stack);
+    let mut tokens = TokenProvider::new(tokens.into_iter());
+    let expression = self.expression(&mut tokens);
+    if let Next::Token(token) = tokens.peek() {
+      let (span, token) = (token.span(), token.to_string());
+      self.error(InterpreterError::ParseError {
+        span,
+        message: format!("Expected `}}` but got `{token}`"),
+      });
+    }
+    self.delimiter_stack = outer_delimiters;
+
+    expression
+  }
+
   fn pair_delimiter(&mut self, tokens: &mut TokenProvider, delimiter: Symbol) -> Token {
     let unmatched = self.delimiter_stack.pop().expect("expected Some(delimiter)");
     match self.check_delimiter(tokens, &delimiter) {
-      Ok(matches) => match tokens.next().cloned() {
+      Ok(matches) => match tokens.next() {
         Next::Token(token) => {
           if matches {
             token
           } else {
             let delimiter = Token::Symbol(SymbolToken {
               position: token.position().clone(),
+              span: token.span(),
               symbol: delimiter,
             });
             self.error(InterpreterError::UnmatchedDelimiter {
-              position: unmatched.position().clone(),
+              span: unmatched.span(),
               delimiter: unmatched.lexeme(),
             });
             delimiter
           }
         }
-        Next::EndOfFile { position } | Next::EndOfStream { position } => {
+        Next::EndOfFile { position, span } | Next::EndOfStream { position, span } => {
           let delimiter = Token::Symbol(SymbolToken {
             position,
+            span,
             symbol: delimiter,
           });
           self.error(InterpreterError::UnmatchedDelimiter {
-            position: unmatched.position().clone(),
+            span: unmatched.span(),
             delimiter: unmatched.lexeme(),
           });
           delimiter
         }
       },
-      Err(position) => {
+      Err((position, span)) => {
         let delimiter = Token::Symbol(SymbolToken {
           position,
+          span,
           symbol: delimiter,
         });
         self.error(InterpreterError::UnmatchedDelimiter {
-          position: unmatched.position().clone(),
+          span: unmatched.span(),
           delimiter: unmatched.lexeme(),
         });
         delimiter
@@ -393,11 +2247,11 @@ impl Parser {
     }
   }
 
-  fn check_delimiter(&mut self, tokens: &mut TokenProvider, delimiter: &Symbol) -> Result<bool, Position> {
+  fn check_delimiter(&mut self, tokens: &mut TokenProvider, delimiter: &Symbol) -> Result<bool, (Position, Span)> {
     match tokens.peek() {
       Next::Token(Token::Symbol(symbol_token)) => Ok(symbol_token.symbol == *delimiter),
-      Next::Token(token) => Err(token.position().to_owned()),
-      Next::EndOfFile { position } | Next::EndOfStream { position } => Err(position),
+      Next::Token(token) => Err((token.position().to_owned(), token.span())),
+      Next::EndOfFile { position, span } | Next::EndOfStream { position, span } => Err((position, span)),
     }
   }
 
@@ -405,37 +2259,381 @@ impl Parser {
     self.error_handler.get_mut().push(error.clone());
   }
 
-  #[allow(unused)]
+  /// Skips ahead after a syntax error to where the next statement probably
+  /// starts: just past a `;`, at a keyword that starts a statement, or at the
+  /// `}` closing the current block. Nested blocks are skipped whole.
   fn synchronize(&mut self, tokens: &mut TokenProvider) {
-    let mut token = tokens.next();
-    while let Next::Token(next_token) = token {
-      if matches!(
-        next_token,
-        Token::Symbol(SymbolToken {
-          symbol: Symbol::Semicolon,
-          ..
-        })
-      ) {
-        return;
-      }
-
-      if matches!(
-        next_token,
-        Token::Keyword(KeywordToken {
-          keyword: Keyword::If
-            | Keyword::For
-            | Keyword::While
-            | Keyword::Loop
-            | Keyword::Return
-            | Keyword::Type
-            | Keyword::Impl,
+    let mut depth = 0;
+    loop {
+      match tokens.peek() {
+        Next::Token(Token::Symbol(SymbolToken { symbol, .. })) => match symbol {
+          Symbol::Semicolon if depth == 0 => {
+            tokens.next();
+            return;
+          }
+          Symbol::LeftCurlyBracket => depth += 1,
+          Symbol::RightCurlyBracket if depth == 0 => return,
+          Symbol::RightCurlyBracket => depth -= 1,
+          _ => {}
+        },
+        Next::Token(Token::Keyword(KeywordToken {
+          keyword:
+            Keyword::If | Keyword::For | Keyword::While | Keyword::Loop | Keyword::Return | Keyword::Type | Keyword::Impl,
           ..
-        })
-      ) {
-        return;
+        }))
+          if depth == 0 =>
+        {
+          return
+        }
+        Next::Token(_) => {}
+        Next::EndOfFile { .. } | Next::EndOfStream { .. } => return,
       }
-
-      token = tokens.next();
+      tokens.next();
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::interpreter::{grammar::span::FileId, lexer::Lexer};
+
+  /// Parses `source`, along with the messages of the errors found.
+  fn parse_with_errors(source: &str) -> (SyntaxTree, Vec<String>) {
+    let error_handler = Handle::new(ErrorHandler::new());
+    let tokens = Lexer::new(source.as_bytes(), FileId(0), error_handler.clone());
+    let tree = Parser::new(error_handler.clone()).parse(tokens);
+    let errors = error_handler.get().errors().iter().map(ToString::to_string).collect();
+    (tree, errors)
+  }
+
+  /// Parses `source`, which has to parse without errors.
+  fn parse(source: &str) -> SyntaxTree {
+    let (tree, errors) = parse_with_errors(source);
+    assert!(errors.is_empty(), "`{source}` failed to parse: {errors:?}");
+    tree
+  }
+
+  /// Checks that `source` parses into the tree printed as `expected`.
+  fn parses_to(source: &str, expected: &[&str]) {
+    let tree = parse(source).to_string();
+    assert_eq!(tree.lines().collect::<Vec<_>>(), expected, "`{source}`");
+  }
+
+  /// Checks that `source` fails to parse with an error whose message
+  /// contains `message`.
+  fn fails_with(source: &str, message: &str) {
+    let (_, errors) = parse_with_errors(source);
+    assert!(
+      errors.iter().any(|error| error.contains(message)),
+      "`{source}` failed with {errors:?} instead of `{message}`"
+    );
+  }
+
+  fn texts(docs: &[DocCommentToken]) -> Vec<&str> {
+    docs.iter().map(|doc| doc.text.trim()).collect()
+  }
+
+  #[test]
+  fn members_keep_their_docs() {
+    let tree = parse(
+      "/// A person.\n\
+       P: type = {\n  /// Their name.\n  name: pub string;\n  /// Says hi.\n  greet: (&self) -> string { self.name }\n}\n\
+       P: impl = {\n  /// Makes one.\n  new: (name: string) -> Self { P { name } }\n}\n\
+       f: () -> i64 = {\n  /// The answer.\n  x := 42;\n  x\n}\n",
+    );
+    assert_eq!(texts(&tree.items[0].docs), ["A person."]);
+    let ItemKind::Record(record) = &tree.items[0].kind else {
+      panic!("expected a record, got {:?}", tree.items[0].kind);
+    };
+    assert_eq!(texts(&record.fields[0].docs), ["Their name."]);
+    assert_eq!(texts(&record.methods[0].docs), ["Says hi."]);
+    let ItemKind::Impl(implementation) = &tree.items[1].kind else {
+      panic!("expected an impl, got {:?}", tree.items[1].kind);
+    };
+    assert_eq!(texts(&implementation.methods[0].docs), ["Makes one."]);
+    let ItemKind::Function(Function {
+      body: Some(Expression::Block { statements, .. }),
+      ..
+    }) = &tree.items[2].kind
+    else {
+      panic!("expected a function, got {:?}", tree.items[2].kind);
+    };
+    let Statement::Declaration { docs, .. } = &statements[0] else {
+      panic!("expected a declaration, got {:?}", statements[0]);
+    };
+    assert_eq!(texts(docs), ["The answer."]);
+  }
+
+  #[test]
+  fn docs_go_to_the_next_item_or_the_file() {
+    let tree = parse(
+      "//! The file.\n//! More on it.\n/// First.\nf: () -> i64 = { 1 }\n\n/// Second.\n/// Again.\ng: () -> i64 = { 2 }\n",
+    );
+    assert_eq!(texts(&tree.docs), ["The file.", "More on it."]);
+    assert_eq!(texts(&tree.items[0].docs), ["First."]);
+    assert_eq!(texts(&tree.items[1].docs), ["Second.", "Again."]);
+  }
+
+  #[test]
+  fn parses_programs_of_statements_and_blocks() {
+    parses_to("x := 10; { y := x; y + 1 }", &[
+      "Declaration: x",
+      "  Literal: Number { lexeme: 10, suffix: None }",
+      "Block",
+      "  Declaration: y",
+      "    Variable: x",
+      "  Binary: Plus",
+      "    Variable: y",
+      "    Literal: Number { lexeme: 1, suffix: None }",
+    ]);
+    parses_to("{ 1; }", &[
+      "Block",
+      "  Semicolon",
+      "    Literal: Number { lexeme: 1, suffix: None }",
+    ]);
+    fails_with("1 2", "Expected `;` after expression");
+  }
+
+  #[test]
+  fn parses_typed_and_inferred_declarations() {
+    parses_to("x: i32 = 1; y := x;", &[
+      "Declaration: x : i32",
+      "  Literal: Number { lexeme: 1, suffix: None }",
+      "Declaration: y",
+      "  Variable: x",
+    ]);
+    fails_with("if := 1;", "Cannot declare `if`, which is a keyword");
+    fails_with("public := 1;", "Cannot declare `public`, which is a keyword");
+    fails_with("true := 1;", "Cannot declare `true`, which is a keyword");
+    parses_to("in := 3;", &[
+      "Declaration: in",
+      "  Literal: Number { lexeme: 3, suffix: None }",
+    ]);
+    fails_with("z: i64 := 2;", "Expected `;` after declaration");
+  }
+
+  #[test]
+  fn parses_control_flow() {
+    parses_to("if a == 1 { 1 } else if a == 2 { 2 } else { 3 }", &[
+      "If",
+      "  Binary: DoubleEquals",
+      "    Variable: a",
+      "    Literal: Number { lexeme: 1, suffix: None }",
+      "  Block",
+      "    Literal: Number { lexeme: 1, suffix: None }",
+      "  If",
+      "    Binary: DoubleEquals",
+      "      Variable: a",
+      "      Literal: Number { lexeme: 2, suffix: None }",
+      "    Block",
+      "      Literal: Number { lexeme: 2, suffix: None }",
+      "    Block",
+      "      Literal: Number { lexeme: 3, suffix: None }",
+    ]);
+    parses_to("while i < 3 { continue; }", &[
+      "While",
+      "  Binary: LeftAngledBracket",
+      "    Variable: i",
+      "    Literal: Number { lexeme: 3, suffix: None }",
+      "  Block",
+      "    Semicolon",
+      "      Continue",
+    ]);
+    parses_to("x := loop { break 4; }", &[
+      "Declaration: x",
+      "  Loop",
+      "    Block",
+      "      Semicolon",
+      "        Break",
+      "          Literal: Number { lexeme: 4, suffix: None }",
+    ]);
+    parses_to("for i in 0..4 { i; }", &[
+      "For: i",
+      "  Range: ..",
+      "    Literal: Number { lexeme: 0, suffix: None }",
+      "    Literal: Number { lexeme: 4, suffix: None }",
+      "  Block",
+      "    Semicolon",
+      "      Variable: i",
+    ]);
+  }
+
+  #[test]
+  fn parses_enums_and_their_variants() {
+    parses_to("logic: enum = { false, true } logic.true", &[
+      "Enum: logic { false, true }",
+      "Member: true",
+      "  Variable: logic",
+    ]);
+  }
+
+  #[test]
+  fn parses_records_and_their_literals() {
+    parses_to(
+      "V: type = { name: pub string; age: i64; } v := V { name, age = 3 }; v.age = 4;",
+      &[
+        "Record: V",
+        "  pub name: string",
+        "  age: i64",
+        "Declaration: v",
+        "  Record: V",
+        "    name =",
+        "      Variable: name",
+        "    age =",
+        "      Literal: Number { lexeme: 3, suffix: None }",
+        "Semicolon",
+        "  Assign",
+        "    Member: age",
+        "      Variable: v",
+        "    Literal: Number { lexeme: 4, suffix: None }",
+      ],
+    );
+  }
+
+  #[test]
+  fn parses_methods_and_their_receivers() {
+    parses_to(
+      "V: impl = { new: () -> Self { V { age = 1 } } greet: (&self) {} \
+       rename: (&mut self, name: string) {} eat: (self) {} }",
+      &[
+        "Impl: V",
+        "  Function: new () -> Self",
+        "    Block",
+        "      Record: V",
+        "        age =",
+        "          Literal: Number { lexeme: 1, suffix: None }",
+        "  Function: greet (&self)",
+        "    Block",
+        "  Function: rename (&mut self, name: string)",
+        "    Block",
+        "  Function: eat (self)",
+        "    Block",
+      ],
+    );
+  }
+
+  #[test]
+  fn parses_traits_and_their_impls() {
+    parses_to(
+      "Speak: trait = { name: (&self) -> string; speak: (&self) -> string { self.name() } } \
+       Cat: impl Speak = { name: (&self) -> string { \"cat\" } } \
+       hi<T: Speak>: (x: &T) -> string { x.speak() }",
+      &[
+        "Trait: Speak",
+        "  Function: name (&self) -> string",
+        "  Function: speak (&self) -> string",
+        "    Block",
+        "      Call",
+        "        Member: name",
+        "          Variable: self",
+        "Impl: Speak for Cat",
+        "  Function: name (&self) -> string",
+        "    Block",
+        "      Literal: String { lexeme: \"cat\" }",
+        "Function: hi<T: Speak> (x: &T) -> string",
+        "  Block",
+        "    Call",
+        "      Member: speak",
+        "        Variable: x",
+      ],
+    );
+  }
+
+  #[test]
+  fn parses_generic_arguments_apart_from_comparisons() {
+    parses_to("f(x < y, y > (z)); f::<i32>(x); Pair::<i64, string> { a = 1 };", &[
+      "Semicolon",
+      "  Call",
+      "    Variable: f",
+      "    Binary: LeftAngledBracket",
+      "      Variable: x",
+      "      Variable: y",
+      "    Binary: RightAngledBracket",
+      "      Variable: y",
+      "      Grouping",
+      "        Variable: z",
+      "Semicolon",
+      "  Call",
+      "    Generic: <i32>",
+      "      Variable: f",
+      "    Variable: x",
+      "Semicolon",
+      "  Record: Pair<i64, string>",
+      "    a =",
+      "      Literal: Number { lexeme: 1, suffix: None }",
+    ]);
+  }
+
+  #[test]
+  fn parses_imports() {
+    parses_to(
+      "export import bool::bool::{self, false, true}; import core.*; include core; import a.b as c;",
+      &[
+        "Export Import: bool.bool.{self, false, true}",
+        "Import: core.*",
+        "Include: core",
+        "Import: a.b as c",
+      ],
+    );
+    parses_to("array := core.array<i32>.new(); x<y, y>(z); Pair<i64> {}; x<y;", &[
+      "Declaration: array",
+      "  Call",
+      "    Member: new",
+      "      Generic: <i32>",
+      "        Member: array",
+      "          Variable: core",
+      "Semicolon",
+      "  Call",
+      "    Generic: <y, y>",
+      "      Variable: x",
+      "    Variable: z",
+      "Semicolon",
+      "  Record: Pair<i64>",
+      "Semicolon",
+      "  Binary: LeftAngledBracket",
+      "    Variable: x",
+      "    Variable: y",
+    ]);
+  }
+
+  #[test]
+  fn parses_assignments_to_the_right() {
+    parses_to("a = b = c += 1;", &[
+      "Semicolon",
+      "  Assign",
+      "    Variable: a",
+      "    Assign",
+      "      Variable: b",
+      "      Assign +=",
+      "        Variable: c",
+      "        Literal: Number { lexeme: 1, suffix: None }",
+    ]);
+  }
+
+  #[test]
+  fn parses_indices_as_places() {
+    parses_to("s[i + 1] = c;", &[
+      "Semicolon",
+      "  Assign",
+      "    Index",
+      "      Variable: s",
+      "      Binary: Plus",
+      "        Variable: i",
+      "        Literal: Number { lexeme: 1, suffix: None }",
+      "    Variable: c",
+    ]);
+    fails_with("s[1;", "Unmatched");
+  }
+
+  #[test]
+  fn exponents_bind_tighter_than_negation() {
+    parses_to("-2 ^ 2;", &[
+      "Semicolon",
+      "  Unary: Minus",
+      "    Binary: Caret",
+      "      Literal: Number { lexeme: 2, suffix: None }",
+      "      Literal: Number { lexeme: 2, suffix: None }",
+    ]);
+  }
+}
@@ -1,2 +1,3 @@
 pub mod character_provider;
+pub mod source_map;
 pub mod token_provider;
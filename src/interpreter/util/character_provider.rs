@@ -1,26 +1,88 @@
-use std::{iter::Peekable, str::Chars};
+use std::{
+  char::REPLACEMENT_CHARACTER,
+  collections::VecDeque,
+  io::{BufRead, ErrorKind},
+};
 
-use crate::interpreter::grammar::token::Position;
+use unicode_width::UnicodeWidthChar;
 
+use crate::interpreter::{
+  error::InterpreterError,
+  grammar::{
+    span::{FileId, Span},
+    token::Position,
+  },
+};
+
+/// How many columns a tab advances to, matching the default of most editors.
+pub const TAB_WIDTH: u32 = 4;
+
+/// The display column reached after `ch` when it starts at `column` (0-based).
+/// Tabs jump to the next tab stop, wide characters such as CJK take two
+/// columns and combining marks take none.
+pub fn advance_column(column: u32, ch: char) -> u32 {
+  match ch {
+    '\t' => (column / TAB_WIDTH + 1) * TAB_WIDTH,
+    _ => column + ch.width().unwrap_or(0) as u32,
+  }
+}
+
+/// Decodes characters from a UTF-8 byte stream as the lexer asks for them, so
+/// only the characters it is peeking at are held in memory.
 pub struct CharacterProvider<'a> {
-  chars: Peekable<Chars<'a>>,
+  source: Box<dyn BufRead + 'a>,
+  /// Characters decoded but not consumed yet, with their length in bytes.
+  lookahead: VecDeque<(char, usize)>,
+  /// Set once `source` is exhausted or fails to read.
+  drained: bool,
+  file_id: FileId,
   line: u32,
+  /// The 1-based display column of the last consumed character.
   column: u32,
+  /// The display width of everything consumed on the current line.
+  width: u32,
+  offset: usize,
+  /// The byte offset of the last consumed character.
+  last_offset: usize,
+  errors: Vec<InterpreterError>,
 }
 
 impl<'a> CharacterProvider<'a> {
-  pub fn new(source: &'a str) -> Self {
+  pub fn new(source: impl BufRead + 'a, file_id: FileId) -> Self {
     Self {
-      chars: source.chars().peekable(),
+      source: Box::new(source),
+      lookahead: Default::default(),
+      drained: false,
+      file_id,
       line: 1,
       column: 0,
+      width: 0,
+      offset: 0,
+      last_offset: 0,
+      errors: Default::default(),
     }
   }
 
+  /// Records an error found while reading, to be picked up by
+  /// [`take_errors`](Self::take_errors).
+  pub fn push_error(&mut self, error: InterpreterError) {
+    self.errors.push(error);
+  }
+
+  /// Drains errors found while skipping comments, such as an unterminated
+  /// block comment.
+  pub fn take_errors(&mut self) -> Vec<InterpreterError> {
+    std::mem::take(&mut self.errors)
+  }
+
   // pub fn current_location(&self) -> String {
   //     "NUL".into()
   // }
 
+  pub fn file_id(&self) -> FileId {
+    self.file_id
+  }
+
   pub fn current_position(&self) -> Position {
     Position {
       line: self.line,
@@ -36,49 +98,188 @@ impl<'a> CharacterProvider<'a> {
     self.column
   }
 
+  /// The byte offset into the source just past the last consumed character.
+  pub fn current_offset(&self) -> usize {
+    self.offset
+  }
+
+  /// The byte offset at which the last consumed character starts.
+  pub fn last_offset(&self) -> usize {
+    self.last_offset
+  }
+
+  /// The span from `start` up to the last consumed character.
+  pub fn span_from(&self, start: usize) -> Span {
+    Span::new(self.file_id, start, self.offset)
+  }
+
+  fn advance(&mut self) -> Option<char> {
+    self.fill(0);
+    let (ch, length) = self.lookahead.pop_front()?;
+    self.last_offset = self.offset;
+    self.offset += length;
+    if ch == '\n' {
+      self.line += 1;
+      self.column = 0;
+      self.width = 0;
+    } else {
+      self.column = self.width + 1;
+      self.width = advance_column(self.width, ch);
+    }
+    Some(ch)
+  }
+
   pub fn peek(&mut self) -> Option<&char> {
-    self.chars.peek()
+    self.fill(0);
+    self.lookahead.front().map(|(ch, _)| ch)
+  }
+
+  /// Looks `n` characters past the next one without consuming anything.
+  /// `peek_nth(0)` is equivalent to [`peek`](Self::peek).
+  pub fn peek_nth(&mut self, n: usize) -> Option<char> {
+    self.fill(n);
+    self.lookahead.get(n).map(|&(ch, _)| ch)
+  }
+
+  /// Decodes characters until there are at least `n + 1` waiting, or the
+  /// source runs out.
+  fn fill(&mut self, n: usize) {
+    while self.lookahead.len() <= n {
+      let Some(decoded) = self.decode() else {
+        return;
+      };
+      self.lookahead.push_back(decoded);
+    }
+  }
+
+  /// Decodes the next character from the source. Malformed UTF-8 becomes
+  /// U+FFFD, covering the bytes that were read for it.
+  fn decode(&mut self) -> Option<(char, usize)> {
+    let first = self.next_byte()?;
+    let length = match first {
+      0x00..=0x7f => return Some((first as char, 1)),
+      0xc0..=0xdf => 2,
+      0xe0..=0xef => 3,
+      0xf0..=0xf7 => 4,
+      _ => return Some(self.malformed(1)),
+    };
+
+    let mut bytes = [first, 0, 0, 0];
+    for (read, byte) in bytes.iter_mut().enumerate().take(length).skip(1) {
+      match self.peek_byte() {
+        Some(continuation) if continuation & 0xc0 == 0x80 => {
+          *byte = continuation;
+          self.source.consume(1);
+        }
+        _ => return Some(self.malformed(read)),
+      }
+    }
+
+    match std::str::from_utf8(&bytes[..length])
+      .ok()
+      .and_then(|text| text.chars().next())
+    {
+      Some(ch) => Some((ch, length)),
+      None => Some(self.malformed(length)),
+    }
+  }
+
+  fn malformed(&mut self, length: usize) -> (char, usize) {
+    let start = self.offset + self.lookahead.iter().map(|(_, length)| length).sum::<usize>();
+    self
+      .errors
+      .push(InterpreterError::Other(format!("Source is not valid UTF-8 at byte {start}")));
+    (REPLACEMENT_CHARACTER, length)
+  }
+
+  fn next_byte(&mut self) -> Option<u8> {
+    let byte = self.peek_byte()?;
+    self.source.consume(1);
+    Some(byte)
   }
 
+  fn peek_byte(&mut self) -> Option<u8> {
+    while !self.drained {
+      match self.source.fill_buf() {
+        Ok([]) => self.drained = true,
+        Ok(buffer) => return Some(buffer[0]),
+        Err(error) if error.kind() == ErrorKind::Interrupted => {}
+        Err(error) => {
+          self
+            .errors
+            .push(InterpreterError::Other(format!("Failed to read source: {error}")));
+          self.drained = true;
+        }
+      }
+    }
+    None
+  }
+
+  /// Skips the comment starting at the `/` in `ch`, returning whether one was
+  /// skipped. Doc comments (`///` and `//!`) are not skipped, so that the lexer
+  /// can turn them into tokens.
   fn filtered_comment(&mut self, ch: &mut char) -> Option<bool> {
-    if let Some(peek) = self.chars.peek() {
-      if *peek == '/' {
+    match self.peek().copied() {
+      Some('/') if self.is_doc_comment() => Some(false),
+      Some('/') => {
         while *ch != '\n' {
-          self.column += 1;
-          *ch = self.chars.next()?;
+          *ch = self.advance()?;
         }
-        self.line += 1;
-        self.column = 0;
 
-        return Some(true);
+        Some(true)
       }
+      Some('*') => {
+        self.skip_block_comment();
+        Some(true)
+      }
+      _ => Some(false),
     }
+  }
 
-    Some(false)
+  /// Checks whether the `/` that was just read starts `///` (but not `////`)
+  /// or `//!`.
+  fn is_doc_comment(&mut self) -> bool {
+    match (self.peek_nth(0), self.peek_nth(1), self.peek_nth(2)) {
+      (Some('/'), Some('!'), _) => true,
+      (Some('/'), Some('/'), next) => next != Some('/'),
+      _ => false,
+    }
   }
 
-  pub fn next_with_spaces(&mut self) -> Option<char> {
-    loop {
-      let mut ch = self.chars.next()?;
-      self.column += 1;
-      match ch {
-        whitespace if whitespace.is_whitespace() => {
-          if ch == '\n' {
-            self.line += 1;
-            self.column = 0;
-          } else {
-            break Some(ch);
-          }
+  /// Skips a `/* ... */` comment whose `/` was just read. Block comments nest,
+  /// so every `/*` inside needs its own `*/`.
+  fn skip_block_comment(&mut self) {
+    let start = self.offset - 1;
+    self.next_raw(); // consume the `*`
+
+    let mut depth = 1;
+    while depth > 0 {
+      let Some(ch) = self.next_raw() else {
+        self.errors.push(InterpreterError::UnterminatedBlockComment {
+          span: self.span_from(start),
+        });
+        return;
+      };
+
+      match (ch, self.peek()) {
+        ('/', Some('*')) => {
+          self.next_raw();
+          depth += 1;
         }
-        '/' => {
-          if !self.filtered_comment(&mut ch)? {
-            break Some(ch);
-          }
+        ('*', Some('/')) => {
+          self.next_raw();
+          depth -= 1;
         }
-        _ => break Some(ch),
+        _ => {}
       }
     }
   }
+
+  /// Consumes the next character exactly as it appears in the source, without
+  /// skipping whitespace or comments. Used for the bodies of string literals.
+  pub fn next_raw(&mut self) -> Option<char> {
+    self.advance()
+  }
 }
 
 impl Iterator for CharacterProvider<'_> {
@@ -86,15 +287,9 @@ impl Iterator for CharacterProvider<'_> {
 
   fn next(&mut self) -> Option<Self::Item> {
     loop {
-      let mut ch = self.chars.next()?;
-      self.column += 1;
+      let mut ch = self.advance()?;
       match ch {
-        whitespace if whitespace.is_whitespace() => {
-          if ch == '\n' {
-            self.line += 1;
-            self.column = 0;
-          }
-        }
+        whitespace if whitespace.is_whitespace() => {}
         '/' => {
           if !self.filtered_comment(&mut ch)? {
             break Some(ch);
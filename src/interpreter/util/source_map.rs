@@ -0,0 +1,271 @@
+use std::{
+  cell::OnceCell,
+  fs,
+  path::{Path, PathBuf},
+};
+
+use super::character_provider::{advance_column, CharacterProvider};
+use crate::interpreter::grammar::{
+  span::{FileId, Span},
+  token::Position,
+};
+
+struct SourceFile {
+  name: String,
+  /// Where the text can be read back from, for files that were streamed
+  /// through the lexer instead of handed over whole.
+  path: Option<PathBuf>,
+  text: OnceCell<SourceText>,
+}
+
+struct SourceText {
+  source: String,
+  /// Byte offset of the first character of every line.
+  line_starts: Vec<usize>,
+  /// The malformed UTF-8 of a file read back from disk, as the offset in the
+  /// file just past each malformed sequence and its length there. Each became
+  /// a U+FFFD in `source`, which may be longer or shorter.
+  malformed: Vec<(usize, usize)>,
+}
+
+impl SourceText {
+  fn new(source: String) -> Self {
+    let line_starts = std::iter::once(0)
+      .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+      .collect();
+
+    Self {
+      source,
+      line_starts,
+      malformed: Vec::new(),
+    }
+  }
+
+  /// Decodes `bytes` the way the lexer does, so that the characters errors
+  /// point at are the ones it saw.
+  fn decode(bytes: &[u8], file_id: FileId) -> Self {
+    let mut characters = CharacterProvider::new(bytes, file_id);
+    let mut source = String::with_capacity(bytes.len());
+    let mut malformed = Vec::new();
+    while let Some(ch) = characters.next_raw() {
+      let length = characters.current_offset() - characters.last_offset();
+      if ch.len_utf8() != length {
+        malformed.push((characters.current_offset(), length));
+      }
+      source.push(ch);
+    }
+
+    Self {
+      malformed,
+      ..Self::new(source)
+    }
+  }
+
+  /// Where `offset` into the file is in `source`.
+  fn offset(&self, offset: usize) -> usize {
+    let replaced = self.malformed.iter().take_while(|(end, _)| *end <= offset);
+    replaced.fold(offset, |offset, (_, length)| {
+      offset + char::REPLACEMENT_CHARACTER.len_utf8() - length
+    })
+  }
+}
+
+/// Owns the text of every file handed to the interpreter, and turns the byte
+/// offsets in a [`Span`] back into lines and columns.
+#[derive(Default)]
+pub struct SourceMap {
+  files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn add_file(&mut self, name: impl Into<String>, source: impl Into<String>) -> FileId {
+    self.files.push(SourceFile {
+      name: name.into(),
+      path: None,
+      text: OnceCell::from(SourceText::new(source.into())),
+    });
+
+    FileId(self.files.len() as u32 - 1)
+  }
+
+  /// Adds a file without reading it. Its text is only read from `path` the
+  /// first time it's needed, which is usually to show an error.
+  pub fn add_path(&mut self, path: impl AsRef<Path>) -> FileId {
+    let path = path.as_ref();
+    self.files.push(SourceFile {
+      name: path.display().to_string(),
+      path: Some(path.to_path_buf()),
+      text: OnceCell::new(),
+    });
+
+    FileId(self.files.len() as u32 - 1)
+  }
+
+  pub fn name(&self, file_id: FileId) -> &str {
+    &self.file(file_id).name
+  }
+
+  pub fn source(&self, file_id: FileId) -> &str {
+    &self.text(file_id).source
+  }
+
+  /// The text covered by `span`.
+  pub fn slice(&self, span: Span) -> &str {
+    let file = self.text(span.file_id);
+    &file.source[file.offset(span.start)..file.offset(span.end)]
+  }
+
+  /// The 1-based line and column of the character at `offset`. Columns are
+  /// display columns, the same ones an editor shows: tabs expand to the next
+  /// tab stop and wide characters count twice.
+  pub fn position(&self, file_id: FileId, offset: usize) -> Position {
+    let file = self.text(file_id);
+    let offset = file.offset(offset).min(file.source.len());
+    let line = file.line_starts.partition_point(|&start| start <= offset) - 1;
+    let column = display_width(&file.source[file.line_starts[line]..offset]);
+
+    Position::new(line as u32 + 1, column + 1)
+  }
+
+  /// The positions of the first character of `span` and of the character just
+  /// past its end.
+  pub fn location(&self, span: Span) -> (Position, Position) {
+    (self.position(span.file_id, span.start), self.position(span.file_id, span.end))
+  }
+
+  /// Renders `message` with the file, line and column of `span`, followed by
+  /// the source line with the span underlined.
+  pub fn render(&self, span: Span, message: &str) -> String {
+    let name = &self.file(span.file_id).name;
+    let file = self.text(span.file_id);
+    let (start, end) = self.location(span);
+    let line_start = file.line_starts[start.line as usize - 1];
+    let line_text = expand_tabs(file.source[line_start..].lines().next().unwrap_or_default());
+
+    let underline_start = start.column as usize - 1;
+    let underline_length = if end.line == start.line {
+      (end.column - start.column).max(1) as usize
+    } else {
+      (display_width(&line_text) as usize - underline_start).max(1)
+    };
+
+    let gutter = " ".repeat(start.line.to_string().len());
+    format!(
+      "{message}\n{gutter}--> {}:{}:{}\n{gutter} |\n{} | {line_text}\n{gutter} | {}{}",
+      name,
+      start.line,
+      start.column,
+      start.line,
+      " ".repeat(underline_start),
+      "^".repeat(underline_length)
+    )
+  }
+
+  fn file(&self, file_id: FileId) -> &SourceFile {
+    &self.files[file_id.0 as usize]
+  }
+
+  /// The text of a file, reading it back from disk if it was streamed. A file
+  /// that can no longer be read is treated as empty.
+  fn text(&self, file_id: FileId) -> &SourceText {
+    let file = self.file(file_id);
+    file.text.get_or_init(|| {
+      let bytes = file.path.as_ref().and_then(|path| fs::read(path).ok());
+      SourceText::decode(&bytes.unwrap_or_default(), file_id)
+    })
+  }
+}
+
+fn display_width(text: &str) -> u32 {
+  text.chars().fold(0, advance_column)
+}
+
+/// Replaces tabs with spaces up to the next tab stop, so that the underline
+/// printed beneath a line lines up with it regardless of the terminal's tab
+/// width.
+fn expand_tabs(line: &str) -> String {
+  let mut column = 0;
+  let mut expanded = String::with_capacity(line.len());
+  for ch in line.chars() {
+    let next_column = advance_column(column, ch);
+    if ch == '\t' {
+      expanded.extend(std::iter::repeat_n(' ', (next_column - column) as usize));
+    } else {
+      expanded.push(ch);
+    }
+    column = next_column;
+  }
+  expanded
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn finds_positions_past_malformed_utf8() {
+    let path = std::env::temp_dir().join(format!("kon-source-map-{}.kon", std::process::id()));
+    fs::write(&path, b"x := 1;\ns := \"\xff\xfe a\"; y := );\n").unwrap();
+    let mut source_map = SourceMap::new();
+    let file_id = source_map.add_path(&path);
+    let offset = b"x := 1;\ns := \"\xff\xfe a\"; y := ".len();
+    let span = Span::new(file_id, offset, offset + 1);
+
+    assert_eq!(source_map.position(file_id, offset), Position::new(2, 19));
+    assert_eq!(source_map.slice(span), ")");
+    assert!(source_map
+      .render(span, "oops")
+      .contains("2 | s := \"\u{fffd}\u{fffd} a\"; y := );"));
+    fs::remove_file(&path).unwrap();
+  }
+
+  const WIDE: &str = "日本 := 1;\n😀 + e\u{301}x;\n";
+
+  #[test]
+  fn counts_columns_as_they_are_displayed() {
+    let mut source_map = SourceMap::new();
+    let file_id = source_map.add_file("wide.kon", WIDE);
+    let offset = |text: &str| WIDE.find(text).unwrap();
+
+    assert_eq!(source_map.position(file_id, offset(":=")), Position::new(1, 6));
+    assert_eq!(source_map.position(file_id, offset("+")), Position::new(2, 4));
+    assert_eq!(source_map.position(file_id, offset("x")), Position::new(2, 7));
+    let x = Span::new(file_id, offset("x"), offset("x") + 1);
+    assert_eq!(source_map.slice(x), "x");
+    assert_eq!(
+      source_map.render(x, "oops"),
+      "oops\n --> wide.kon:2:7\n  |\n2 | 😀 + e\u{301}x;\n  |       ^"
+    );
+  }
+
+  #[test]
+  fn underlines_spans_across_lines_to_the_end_of_the_first() {
+    let mut source_map = SourceMap::new();
+    let file_id = source_map.add_file("wide.kon", WIDE);
+    let span = Span::new(file_id, WIDE.find("本").unwrap(), WIDE.find("+").unwrap());
+
+    assert_eq!(source_map.location(span), (Position::new(1, 3), Position::new(2, 4)));
+    assert_eq!(
+      source_map.render(span, "oops"),
+      "oops\n --> wide.kon:1:3\n  |\n1 | 日本 := 1;\n  |   ^^^^^^^^"
+    );
+  }
+
+  #[test]
+  fn spans_name_their_file() {
+    let mut source_map = SourceMap::new();
+    let first = source_map.add_file("first.kon", "a := 1;\n");
+    let second = source_map.add_file("second.kon", "b := 2;\nc := b;\n");
+
+    assert_eq!(source_map.name(first), "first.kon");
+    assert_eq!(source_map.name(second), "second.kon");
+    let span = Span::new(second, 13, 14);
+    assert_eq!(source_map.slice(span), "b");
+    assert!(source_map.render(span, "oops").contains("--> second.kon:2:6\n"));
+    assert!(source_map.render(span, "oops").contains("2 | c := b;"));
+  }
+}
@@ -1,97 +1,127 @@
-use std::{iter::Peekable, slice::Iter};
+use std::collections::VecDeque;
 
-use crate::interpreter::grammar::token::{Position, Token};
+use crate::interpreter::grammar::{
+  span::Span,
+  token::{DocCommentToken, Position, Token},
+};
 
 #[derive(Debug, Clone)]
 pub enum Next<T> {
   Token(T),
-  EndOfFile { position: Position },
-  EndOfStream { position: Position },
+  EndOfFile { position: Position, span: Span },
+  EndOfStream { position: Position, span: Span },
 }
 
 impl<T: Clone> Next<&T> {
   pub fn cloned(&self) -> Next<T> {
     match self {
       &Next::Token(token) => Next::Token(token.clone()),
-      Next::EndOfFile { position } => Next::EndOfFile {
+      Next::EndOfFile { position, span } => Next::EndOfFile {
         position: position.clone(),
+        span: *span,
       },
-      Next::EndOfStream { position } => Next::EndOfStream {
+      Next::EndOfStream { position, span } => Next::EndOfStream {
         position: position.clone(),
+        span: *span,
       },
     }
   }
 }
 
+/// Hands tokens to the parser as it asks for them, pulling from the lexer only
+/// as far ahead as the parser has peeked.
 pub struct TokenProvider<'a> {
   previous_valid_token: Token,
-  tokens: Peekable<Iter<'a, Token>>,
-  last_line: u32,
-  last_column: u32,
+  tokens: Box<dyn Iterator<Item = Token> + 'a>,
+  /// Tokens pulled from `tokens` that the parser hasn't consumed yet.
+  lookahead: VecDeque<Token>,
+  docs: Vec<DocCommentToken>,
+  last_position: Position,
+  last_span: Span,
 }
 
 impl<'a> TokenProvider<'a> {
-  pub fn new(tokens: &'a [Token]) -> Self {
+  pub fn new(tokens: impl Iterator<Item = Token> + 'a) -> Self {
     Self {
       previous_valid_token: Token::EndOfFile {
         position: Position::default(),
+        span: Span::default(),
       },
-      tokens: tokens.iter().peekable(),
-      last_line: 0,
-      last_column: 0,
+      tokens: Box::new(tokens),
+      lookahead: Default::default(),
+      docs: Default::default(),
+      last_position: Position::default(),
+      last_span: Span::default(),
     }
   }
 
+  /// Takes the doc comments passed over since the last call, in source order.
+  pub fn take_docs(&mut self) -> Vec<DocCommentToken> {
+    std::mem::take(&mut self.docs)
+  }
+
+  /// Pulls tokens until `n + 1` are waiting, returning whether there are.
+  ///
+  /// Doc comments are set aside rather than handed to the parser, which picks
+  /// them up with [`take_docs`](Self::take_docs) where they belong.
+  fn fill(&mut self, n: usize) -> bool {
+    while self.lookahead.len() <= n {
+      match self.tokens.next() {
+        Some(Token::DocComment(doc)) => self.docs.push(doc),
+        Some(token) => self.lookahead.push_back(token),
+        None => return false,
+      }
+    }
+    true
+  }
+
   pub fn peek(&mut self) -> Next<&Token> {
-    match self.tokens.peek() {
-      Some(token) => match token {
-        Token::EndOfFile { position } => {
-          self.last_line = position.line;
-          self.last_column = position.column;
-          Next::EndOfFile {
-            position: position.clone(),
-          }
-        }
-        &t => Next::Token(t),
-      },
-      None => Next::EndOfStream {
-        position: Position::new(self.last_line, self.last_column),
-      },
+    self.peek_nth(0)
+  }
+
+  /// Looks `n` tokens past the next one without consuming anything.
+  pub fn peek_nth(&mut self, n: usize) -> Next<&Token> {
+    if !self.fill(n) {
+      return Next::EndOfStream {
+        position: self.last_position.clone(),
+        span: self.last_span,
+      };
     }
+
+    if let Token::EndOfFile { position, span } = &self.lookahead[n] {
+      self.last_position = position.clone();
+      self.last_span = *span;
+      return Next::EndOfFile {
+        position: position.clone(),
+        span: *span,
+      };
+    }
+
+    Next::Token(&self.lookahead[n])
   }
 
   pub fn previous_valid(&self) -> &Token {
     &self.previous_valid_token
   }
 
-  pub fn next(&mut self) -> Next<&Token> {
-    match self.tokens.next() {
-      Some(token) => match token {
-        Token::EndOfFile { position } => Next::EndOfFile {
-          position: position.clone(),
-        },
-        t => {
-          self.previous_valid_token = t.clone();
-          Next::Token(t)
-        }
-      },
-      None => Next::EndOfStream {
-        position: Position::new(self.last_line, self.last_column),
-      },
+  pub fn next(&mut self) -> Next<Token> {
+    if !self.fill(0) {
+      return Next::EndOfStream {
+        position: self.last_position.clone(),
+        span: self.last_span,
+      };
+    }
+
+    match self.lookahead.pop_front().expect("lookahead was just filled") {
+      Token::EndOfFile { position, span } => {
+        self.last_position = position.clone();
+        self.last_span = span;
+        Next::EndOfFile { position, span }
+      }
+      token => {
+        self.previous_valid_token = token.clone();
+        Next::Token(token)
+      }
     }
   }
 }
-
-// impl<'a> Iterator for TokenProvider<'a> {
-//     type Item = &'a Token;
-
-//     fn next(&mut self) -> Option<Self::Item> {
-//         match self.tokens.next() {
-//             Some(token) => match token {
-//                 &Token::EndOfFile { .. } => None,
-//                 t => Some(t),
-//             },
-//             None => None,
-//         }
-//     }
-// }
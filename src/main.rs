@@ -1,4 +1,4 @@
-use std::{fs, io::Write};
+use std::io::Write;
 
 use clap::Parser;
 use foxy_utils::start_debug_logging_session;
@@ -14,20 +14,22 @@ fn main() -> Result<(), KonError> {
 
   let cli = Cli::parse();
 
-  if cli.mode.interactive {
-    run_prompt()
-  } else {
-    run_file(cli)
-  }
+  Interpreter::on_large_stack(|mut interpreter| {
+    if let Some(root) = &cli.root {
+      interpreter.set_search_root(root);
+    }
+
+    if cli.mode.interactive {
+      run_prompt(interpreter)
+    } else {
+      run_file(interpreter, cli)
+    }
+  })?
 }
 
-fn run_file(flags: Cli) -> Result<(), KonError> {
+fn run_file(mut interpreter: Interpreter, flags: Cli) -> Result<(), KonError> {
   if let Some(file) = flags.mode.file {
-    let mut interpreter = Interpreter::new();
-
-    let source = fs::read_to_string(file)?;
-
-    let result = interpreter.run(source)?;
+    let result = interpreter.run_file(&file)?;
 
     println!("{result}");
 
@@ -37,9 +39,7 @@ fn run_file(flags: Cli) -> Result<(), KonError> {
   Ok(())
 }
 
-fn run_prompt() -> Result<(), KonError> {
-  let mut interpreter = Interpreter::new();
-
+fn run_prompt(mut interpreter: Interpreter) -> Result<(), KonError> {
   let mut out = StandardStream::stdout(termcolor::ColorChoice::Always);
   let mut was_success = true;
   loop {